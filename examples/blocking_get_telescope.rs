@@ -0,0 +1,33 @@
+use lemonaid::blocking::CitraClient;
+use std::env;
+
+fn main() {
+    // Get API key from environment variable
+    let api_key = env::var("CITRA_PAT").expect("CITRA_PAT environment variable not set");
+
+    // Create client
+    let client = CitraClient::new(&api_key, true);
+
+    // Get telescope ID from command line argument
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        eprintln!(
+            "Usage: cargo run --features blocking --example blocking_get_telescope <telescope-id>"
+        );
+        std::process::exit(1);
+    }
+    let telescope_id = &args[1];
+
+    // Test get_telescope without an async runtime
+    println!("Fetching telescope: {}", telescope_id);
+    match client.get_telescope(telescope_id) {
+        Ok(telescope) => {
+            println!("\n✓ Success!");
+            println!("{:#?}", telescope);
+        }
+        Err(e) => {
+            eprintln!("\n✗ Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -21,13 +21,8 @@ async fn main() {
 
     // Test create_task with debug
     println!("Creating task for telescope: {}", telescope_id);
-    let create_request = CreateTaskRequest {
-        telescope_id: Some(telescope_id.to_string()),
-        satellite_id: satellite_id.to_string(),
-        antenna_id: None,
-        task_start: start_time,
-        task_stop: stop_time,
-    };
+    let create_request =
+        CreateTaskRequest::for_telescope(telescope_id, satellite_id, start_time, stop_time);
 
     match lemonaid::CitraClient::new(&api_key, true).create_task(&create_request).await {
         Ok(created_task) => {
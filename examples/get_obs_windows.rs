@@ -41,15 +41,10 @@ async fn main() {
     let minutes_from_now: i64 = args[2].parse().expect("Invalid number for minutes_from_now");
     let start_time = chrono::Utc::now();
     let end_time = start_time + chrono::Duration::minutes(minutes_from_now);
-    let access_request = lemonaid::SatelliteAccessToGroundstationRequest {
-        groundstation_id: groundstation_id.to_string(),
-        start: start_time,
-        end: end_time,
-        min_elevation_deg: 10.0,
-        min_duration_minutes: 1.0,
-        min_frequency_mhz: None,
-        max_frequency_mhz: None,
-    };
+    let access_request =
+        lemonaid::SatelliteAccessToGroundstationRequest::new(groundstation_id, start_time, end_time)
+            .min_elevation(lemonaid::units::Degrees(10.0))
+            .min_duration_minutes(1.0);
 
     println!("Fetching observation windows for groundstation: {} from {} to {}", 
         groundstation_id, start_time, end_time);
@@ -58,8 +53,8 @@ async fn main() {
             println!("\n✓ Found {} observation window(s) for groundstation {}", windows.len(), groundstation_id);
             for window in windows {
                 println!("  - Satellite: {} ({})", window.satellite_name.unwrap_or("Unknown".to_string()), window.satellite_id);
-                println!("    Start: {} (Az: {:.2}°, El: {:.2}°)", window.start.time, window.start.azimuth_deg, window.start.elevation_deg);
-                println!("    End:   {} (Az: {:.2}°, El: {:.2}°)", window.end.time, window.end.azimuth_deg, window.end.elevation_deg);
+                println!("    Start: {} (Az: {:.2}, El: {:.2})", window.start.time, window.start.azimuth_deg, window.start.elevation_deg);
+                println!("    End:   {} (Az: {:.2}, El: {:.2})", window.end.time, window.end.azimuth_deg, window.end.elevation_deg);
                 println!("    Duration: {:.2} minutes\n", window.duration_minutes);
             }
         }
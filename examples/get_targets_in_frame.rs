@@ -1,4 +1,5 @@
 use lemonaid::CitraClient;
+use lemonaid::units::Degrees;
 use std::env;
 
 #[tokio::main]
@@ -32,16 +33,13 @@ async fn main() {
     let ra_deg: f64 = args[2].parse().expect("Invalid number for ra_deg");
     let dec_deg: f64 = args[3].parse().expect("Invalid number for dec_deg");
     let fov_deg: f64 = args[4].parse().expect("Invalid number for fov_deg");
-    let fov_request = lemonaid::FOVAccessRequest {
-        time: chrono::Utc::now(),
-        right_ascension_deg: ra_deg,
-        declination_deg: dec_deg,
-        field_of_view_deg: fov_deg,
-        sensor_frame: lemonaid::SensorFrame::J2000,
-        sensor_latitude_deg: groundstation.latitude,
-        sensor_longitude_deg: groundstation.longitude,
-        sensor_altitude_km: groundstation.altitude / 1000.0,
-    };
+    let fov_request = lemonaid::FOVAccessRequest::new(
+        &groundstation,
+        chrono::Utc::now(),
+        Degrees(ra_deg),
+        Degrees(dec_deg),
+        Degrees(fov_deg),
+    );
 
     println!("Fetching targets in frame for groundstation: {} at RA: {}, Dec: {}, FOV: {}", 
         groundstation_id, ra_deg, dec_deg, fov_deg);
@@ -22,13 +22,7 @@ async fn main() {
 
     // Test update_task
     println!("Updating task: {}", task_id);
-    let update_request = TaskUpdateRequest {
-        id: task_id.to_string(),
-        status: TaskStatus::Canceled,
-        priority: Some(5),
-        scheduled_start: None,
-        scheduled_stop: None,
-    };
+    let update_request = TaskUpdateRequest::new(task_id, TaskStatus::Canceled).priority(5);
     match client.update_task(&update_request).await {
         Ok(updated_task) => {
             println!("\n✓ Success!");
@@ -0,0 +1,320 @@
+//! Unattended operation of a telescope: polling its scheduled tasks, running
+//! them on the hardware and reporting the results.
+//!
+//! An [`Agent`] wraps a [`TelescopeDriver`] for the site's mount and camera.
+//! Each poll marks the telescope as connected, then runs every `Scheduled` task
+//! whose window has opened: slew, expose, read out, upload the element sets the
+//! readout produced, and set the task to `Succeeded` or `Failed`. Tasks whose
+//! window closed before they could run are set to `Failed` without touching the
+//! hardware.
+//!
+//! The API has no in-progress status, so a task is claimed by setting it to
+//! `Failed` before the driver runs and only set to `Succeeded` once its element
+//! sets are uploaded. An agent that dies mid-observation leaves the task failed
+//! instead of running it again after a restart. The claim is not atomic: two
+//! agents polling the same telescope can still both run a task.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use lemonaid::CitraClient;
+//! use lemonaid::agent::{Agent, SimulatedDriver};
+//!
+//! # async fn run() -> Result<(), lemonaid::LemonaidError> {
+//! let client = CitraClient::new(&std::env::var("CITRA_PAT").unwrap(), false);
+//! let mut agent = Agent::new(client, "telescope-id", SimulatedDriver::new())
+//!     .poll_interval(Duration::from_secs(30))
+//!     .exposure(Duration::from_secs(5));
+//! agent.run().await
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    CitraClient, ElementSet, ElementSetUploadRequest, LemonaidError, Task, TaskStatus,
+    TaskUpdateRequest, Telescope, TelescopeUpdateRequest, Tle,
+};
+
+/// The hardware side of an [`Agent`]. Any error fails the task being run.
+#[async_trait]
+pub trait TelescopeDriver: Send {
+    /// Points the telescope at the task's target, e.g. its `right_ascension`
+    /// and `declination`.
+    async fn slew(&mut self, task: &Task) -> Result<(), LemonaidError>;
+
+    /// Takes an exposure of the given length.
+    async fn expose(&mut self, exposure: Duration) -> Result<(), LemonaidError>;
+
+    /// Reads out the exposure and reduces it to element sets to upload, if any.
+    async fn readout(&mut self, task: &Task)
+    -> Result<Vec<ElementSetUploadRequest>, LemonaidError>;
+}
+
+/// Runs a telescope's scheduled tasks on a [`TelescopeDriver`].
+pub struct Agent<D> {
+    client: CitraClient,
+    telescope_id: String,
+    driver: D,
+    poll_interval: Duration,
+    exposure: Duration,
+}
+
+/// What became of a task the agent ran or expired.
+#[derive(Debug)]
+pub struct TaskOutcome {
+    pub task_id: String,
+    /// The status the agent last set, `None` if it could not claim the task.
+    pub status: Option<TaskStatus>,
+    /// Element sets uploaded from the readout.
+    pub elsets: Vec<ElementSet>,
+    /// Why the task failed or could not be updated. `None` for a task that
+    /// expired before it ran.
+    pub error: Option<LemonaidError>,
+}
+
+impl<D: TelescopeDriver> Agent<D> {
+    /// Polls every minute and exposes for one second by default.
+    pub fn new(client: CitraClient, telescope_id: &str, driver: D) -> Self {
+        Agent {
+            client,
+            telescope_id: telescope_id.to_string(),
+            driver,
+            poll_interval: Duration::from_secs(60),
+            exposure: Duration::from_secs(1),
+        }
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn exposure(mut self, exposure: Duration) -> Self {
+        self.exposure = exposure;
+        self
+    }
+
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+
+    /// Polls until an error that waiting will not fix, such as a revoked token.
+    /// Network failures and rate limits are skipped until the next poll.
+    pub async fn run(&mut self) -> Result<(), LemonaidError> {
+        loop {
+            match self.poll().await {
+                Err(err) if !err.is_retryable() => return Err(err),
+                _ => {}
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Sends a heartbeat, then runs the scheduled tasks that are due, earliest first.
+    /// Tasks whose window has not opened yet are left for a later poll, and
+    /// those whose window has closed are failed. Only the heartbeat and the
+    /// task listing return errors; anything that goes wrong with a single task
+    /// is reported in its [`TaskOutcome`].
+    pub async fn poll(&mut self) -> Result<Vec<TaskOutcome>, LemonaidError> {
+        self.heartbeat().await?;
+        let now = Utc::now();
+        let mut due: Vec<Task> = self
+            .client
+            .get_telescope_tasks_by_status(&self.telescope_id, vec![TaskStatus::Scheduled])
+            .await?
+            .into_iter()
+            .filter(|task| task.scheduled_start.unwrap_or(task.task_start) <= now)
+            .collect();
+        due.sort_by_key(|task| task.scheduled_start.unwrap_or(task.task_start));
+
+        let mut outcomes = Vec::with_capacity(due.len());
+        for task in &due {
+            if stop(task) <= now {
+                outcomes.push(self.expire(task).await);
+            } else {
+                outcomes.extend(self.execute(task).await);
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Sets the telescope's `last_connected_at` to now.
+    pub async fn heartbeat(&self) -> Result<Telescope, LemonaidError> {
+        let update = TelescopeUpdateRequest::new().last_connected(Utc::now());
+        self.client
+            .patch_telescope(&self.telescope_id, &update)
+            .await
+    }
+
+    /// Claims one task and runs it regardless of its schedule. Driver and
+    /// upload failures fail the task. Returns `None` without running anything
+    /// if the task is no longer `Scheduled`, e.g. because it was canceled.
+    pub async fn execute(&mut self, task: &Task) -> Option<TaskOutcome> {
+        let mut outcome = TaskOutcome {
+            task_id: task.id.clone(),
+            status: None,
+            elsets: Vec::new(),
+            error: None,
+        };
+        let task = match self.claim(task).await {
+            Ok(Some(task)) => task,
+            Ok(None) => return None,
+            Err(err) => {
+                outcome.error = Some(err);
+                return Some(outcome);
+            }
+        };
+        outcome.status = Some(TaskStatus::Failed);
+
+        let result = match self.observe(&task).await {
+            Ok(elsets) if elsets.is_empty() => Ok(Vec::new()),
+            Ok(elsets) => self.client.upload_elsets(&elsets).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(elsets) => {
+                outcome.elsets = elsets;
+                match self.set_status(&task, TaskStatus::Succeeded).await {
+                    Ok(_) => outcome.status = Some(TaskStatus::Succeeded),
+                    Err(err) => outcome.error = Some(err),
+                }
+            }
+            Err(err) => outcome.error = Some(err),
+        }
+        Some(outcome)
+    }
+
+    /// Re-reads the task and, if it is still `Scheduled`, sets it to `Failed`
+    /// until it has run.
+    async fn claim(&self, task: &Task) -> Result<Option<Task>, LemonaidError> {
+        let current = self.client.get_task(&task.id).await?;
+        if current.status != TaskStatus::Scheduled {
+            return Ok(None);
+        }
+        self.set_status(&current, TaskStatus::Failed)
+            .await
+            .map(Some)
+    }
+
+    /// Fails a task whose window closed before it ran.
+    async fn expire(&self, task: &Task) -> TaskOutcome {
+        let result = self.set_status(task, TaskStatus::Failed).await;
+        TaskOutcome {
+            task_id: task.id.clone(),
+            status: result.is_ok().then_some(TaskStatus::Failed),
+            elsets: Vec::new(),
+            error: result.err(),
+        }
+    }
+
+    /// Changes the task's status, restating its priority and schedule so that
+    /// the update cannot clear them.
+    async fn set_status(&self, task: &Task, status: TaskStatus) -> Result<Task, LemonaidError> {
+        let mut update = TaskUpdateRequest::new(&task.id, status).priority(task.priority);
+        update.scheduled_start = task.scheduled_start;
+        update.scheduled_stop = task.scheduled_stop;
+        self.client.update_task(&update).await
+    }
+
+    async fn observe(
+        &mut self,
+        task: &Task,
+    ) -> Result<Vec<ElementSetUploadRequest>, LemonaidError> {
+        self.driver.slew(task).await?;
+        self.driver.expose(self.exposure).await?;
+        self.driver.readout(task).await
+    }
+}
+
+/// When the task's window closes.
+fn stop(task: &Task) -> DateTime<Utc> {
+    task.scheduled_stop.unwrap_or(task.task_stop)
+}
+
+/// A step of a [`TelescopeDriver`], as logged by [`SimulatedDriver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriverStep {
+    Slew,
+    Expose,
+    Readout,
+}
+
+/// A driver without hardware that completes every step at once, for testing
+/// agents.
+///
+/// ```
+/// use lemonaid::agent::{DriverStep, SimulatedDriver};
+///
+/// // every task fails while exposing
+/// let driver = SimulatedDriver::new().fail_at(DriverStep::Expose);
+/// ```
+#[derive(Debug, Default)]
+pub struct SimulatedDriver {
+    tle: Option<Tle>,
+    fail_at: Option<DriverStep>,
+    current_task: Option<String>,
+    history: Vec<(String, DriverStep)>,
+}
+
+impl SimulatedDriver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reports `tle` for the task's satellite on every readout.
+    pub fn tle(mut self, tle: Tle) -> Self {
+        self.tle = Some(tle);
+        self
+    }
+
+    /// Fails every task at `step`.
+    pub fn fail_at(mut self, step: DriverStep) -> Self {
+        self.fail_at = Some(step);
+        self
+    }
+
+    /// Steps taken so far with the id of the task they were for, oldest first.
+    pub fn history(&self) -> &[(String, DriverStep)] {
+        &self.history
+    }
+
+    fn step(&mut self, step: DriverStep) -> Result<(), LemonaidError> {
+        let task_id = self.current_task.clone().unwrap_or_default();
+        self.history.push((task_id.clone(), step));
+        if self.fail_at == Some(step) {
+            return Err(LemonaidError::Driver(format!(
+                "simulated {:?} failure for task {}",
+                step, task_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TelescopeDriver for SimulatedDriver {
+    async fn slew(&mut self, task: &Task) -> Result<(), LemonaidError> {
+        self.current_task = Some(task.id.clone());
+        self.step(DriverStep::Slew)
+    }
+
+    async fn expose(&mut self, _exposure: Duration) -> Result<(), LemonaidError> {
+        self.step(DriverStep::Expose)
+    }
+
+    async fn readout(
+        &mut self,
+        task: &Task,
+    ) -> Result<Vec<ElementSetUploadRequest>, LemonaidError> {
+        self.step(DriverStep::Readout)?;
+        Ok(self
+            .tle
+            .iter()
+            .map(|tle| ElementSetUploadRequest::from_tle(&task.satellite_id, tle.clone()))
+            .collect())
+    }
+}
@@ -0,0 +1,311 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::{LemonaidError, request};
+
+/// Supplies the bearer token sent with every request.
+///
+/// Implementations must be cheap to call repeatedly; [`CitraClient`](crate::CitraClient)
+/// asks for a token before each request.
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    /// Returns the token to send as `Authorization: Bearer <token>`.
+    async fn token(&self) -> Result<String, LemonaidError>;
+
+    /// Called when the API rejected the current token with a 401.
+    ///
+    /// Returns `true` if a different token may now be available, in which case the
+    /// request is replayed once with the result of the next [`token`](Self::token) call.
+    async fn invalidate(&self) -> bool {
+        false
+    }
+}
+
+/// A fixed personal access token.
+pub struct StaticToken(String);
+
+impl StaticToken {
+    pub fn new(token: &str) -> Self {
+        StaticToken(token.to_string())
+    }
+}
+
+#[async_trait]
+impl AuthProvider for StaticToken {
+    async fn token(&self) -> Result<String, LemonaidError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// A token read from an environment variable on every request, so updating the
+/// variable takes effect without rebuilding the client.
+pub struct EnvToken {
+    var: String,
+}
+
+impl EnvToken {
+    pub fn new(var: &str) -> Self {
+        EnvToken {
+            var: var.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthProvider for EnvToken {
+    async fn token(&self) -> Result<String, LemonaidError> {
+        std::env::var(&self.var).map_err(|_| {
+            LemonaidError::Config(format!("environment variable {} is not set", self.var))
+        })
+    }
+
+    async fn invalidate(&self) -> bool {
+        // the variable is re-read on every request anyway
+        true
+    }
+}
+
+/// A token read from a file, re-read whenever the file's modification time changes.
+///
+/// Useful for agents whose token is rotated by an external process.
+pub struct FileToken {
+    path: PathBuf,
+    cached: Mutex<Option<(SystemTime, String)>>,
+}
+
+impl FileToken {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileToken {
+            path: path.into(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    async fn read(&self) -> Result<(SystemTime, String), LemonaidError> {
+        let io_error = |err: std::io::Error| {
+            LemonaidError::Config(format!(
+                "cannot read token file {}: {}",
+                self.path.display(),
+                err
+            ))
+        };
+        let modified = self.modified().await.map_err(io_error)?;
+        let token = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(io_error)?;
+        Ok((modified, token.trim().to_string()))
+    }
+
+    async fn modified(&self) -> std::io::Result<SystemTime> {
+        tokio::fs::metadata(&self.path).await?.modified()
+    }
+}
+
+#[async_trait]
+impl AuthProvider for FileToken {
+    async fn token(&self) -> Result<String, LemonaidError> {
+        let modified = self.modified().await.ok();
+        if let Some((cached_at, token)) = self.cached.lock().unwrap().as_ref()
+            && modified == Some(*cached_at)
+        {
+            return Ok(token.clone());
+        }
+        let (modified, token) = self.read().await?;
+        *self.cached.lock().unwrap() = Some((modified, token.clone()));
+        Ok(token)
+    }
+
+    async fn invalidate(&self) -> bool {
+        let previous = self.cached.lock().unwrap().take().map(|(_, token)| token);
+        match self.read().await {
+            Ok((modified, token)) => {
+                let changed = previous.as_deref() != Some(token.as_str());
+                *self.cached.lock().unwrap() = Some((modified, token));
+                changed
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// A token printed by a shell command, e.g. a password manager lookup such as
+/// `pass show citra/prod`.
+///
+/// The command runs on first use and again after the API rejects its token.
+/// Requires the `config` feature.
+#[cfg(feature = "config")]
+pub struct CommandToken {
+    command: String,
+    cached: Mutex<Option<String>>,
+}
+
+#[cfg(feature = "config")]
+impl CommandToken {
+    pub fn new(command: &str) -> Self {
+        CommandToken {
+            command: command.to_string(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    async fn run(&self) -> Result<String, LemonaidError> {
+        let output = if cfg!(windows) {
+            tokio::process::Command::new("cmd")
+                .args(["/C", &self.command])
+                .output()
+                .await
+        } else {
+            tokio::process::Command::new("sh")
+                .args(["-c", &self.command])
+                .output()
+                .await
+        }
+        .map_err(|err| {
+            LemonaidError::Config(format!(
+                "cannot run token command `{}`: {}",
+                self.command, err
+            ))
+        })?;
+        if !output.status.success() {
+            return Err(LemonaidError::Config(format!(
+                "token command `{}` failed ({}): {}",
+                self.command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if token.is_empty() {
+            return Err(LemonaidError::Config(format!(
+                "token command `{}` printed nothing",
+                self.command
+            )));
+        }
+        Ok(token)
+    }
+}
+
+#[cfg(feature = "config")]
+#[async_trait]
+impl AuthProvider for CommandToken {
+    async fn token(&self) -> Result<String, LemonaidError> {
+        if let Some(token) = self.cached.lock().unwrap().as_ref() {
+            return Ok(token.clone());
+        }
+        let token = self.run().await?;
+        *self.cached.lock().unwrap() = Some(token.clone());
+        Ok(token)
+    }
+
+    async fn invalidate(&self) -> bool {
+        let previous = self.cached.lock().unwrap().take();
+        match self.run().await {
+            Ok(token) => {
+                let changed = previous.as_deref() != Some(token.as_str());
+                *self.cached.lock().unwrap() = Some(token);
+                changed
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// OAuth2 client-credentials flow. Tokens are cached until shortly before they
+/// expire, and refetched when the API rejects them.
+pub struct ClientCredentials {
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    scope: Option<String>,
+    http: reqwest::Client,
+    cached: tokio::sync::Mutex<Option<CachedToken>>,
+}
+
+struct CachedToken {
+    access_token: String,
+    expires_at: Option<Instant>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+/// Tokens are refreshed this long before their reported expiry.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+impl ClientCredentials {
+    pub fn new(token_url: &str, client_id: &str, client_secret: &str) -> Self {
+        ClientCredentials {
+            token_url: token_url.to_string(),
+            client_id: client_id.to_string(),
+            client_secret: client_secret.to_string(),
+            scope: None,
+            http: reqwest::Client::new(),
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Requests the given space-separated scopes.
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scope = Some(scope.to_string());
+        self
+    }
+
+    /// Uses the given client for token requests instead of a default one.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http = client;
+        self
+    }
+
+    async fn fetch(&self) -> Result<CachedToken, LemonaidError> {
+        let mut form = vec![
+            ("grant_type", "client_credentials"),
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+        ];
+        if let Some(scope) = &self.scope {
+            form.push(("scope", scope.as_str()));
+        }
+        let response = self.http.post(&self.token_url).form(&form).send().await?;
+        if !response.status().is_success() {
+            return Err(LemonaidError::from_response(response).await);
+        }
+        let token: TokenResponse = request::decode(response).await?;
+        Ok(CachedToken {
+            access_token: token.access_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| Instant::now() + Duration::from_secs(seconds)),
+        })
+    }
+}
+
+#[async_trait]
+impl AuthProvider for ClientCredentials {
+    async fn token(&self) -> Result<String, LemonaidError> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref() {
+            let fresh = token
+                .expires_at
+                .is_none_or(|expires_at| Instant::now() + EXPIRY_MARGIN < expires_at);
+            if fresh {
+                return Ok(token.access_token.clone());
+            }
+        }
+        let token = self.fetch().await?;
+        let access_token = token.access_token.clone();
+        *cached = Some(token);
+        Ok(access_token)
+    }
+
+    async fn invalidate(&self) -> bool {
+        self.cached.lock().await.take();
+        true
+    }
+}
@@ -0,0 +1,791 @@
+//! Command-line client for the Citra Space API.
+//!
+//! Every resource of [`CitraClient`] has a subcommand; run `lemonaid --help`
+//! for the list.
+//!
+//! Settings come from the profile named by `--profile` or `LEMONAID_PROFILE`,
+//! else the default profile of `~/.config/lemonaid/config.toml` if there is
+//! one; see [`lemonaid::config`]. `--dev` and `--base-url` override the
+//! profile's endpoint. The token is taken from `--token`, `--token-file`, the
+//! profile, the `CITRA_PAT` environment variable, or `~/.config/lemonaid/token`,
+//! in that order.
+
+mod output;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use chrono::{DateTime, Duration, Utc};
+use clap::{Args, Parser, Subcommand};
+use lemonaid::config::{Config, Profile};
+use lemonaid::units::{Arcsec, Degrees, Hz, MHz};
+use lemonaid::{
+    AntennaCreateRequest, AntennaUpdateRequest, CitraClient, CreateTaskRequest, FOVAccessRequest,
+    FileToken, GroundstationCreateRequest, GroundstationUpdateRequest, LemonaidError, Precondition,
+    SatelliteAccessToGroundstationRequest, SensorFrame, StaticToken, TaskQuery, TaskStatus,
+    TaskUpdateRequest, TelescopeCreateRequest, TelescopeUpdateRequest,
+};
+
+use crate::output::Format;
+
+#[derive(Parser)]
+#[command(
+    name = "lemonaid",
+    version,
+    about = "Command-line client for the Citra Space API"
+)]
+struct Cli {
+    /// Profile of ~/.config/lemonaid/config.toml to use.
+    #[arg(long, global = true, env = "LEMONAID_PROFILE")]
+    profile: Option<String>,
+    /// Use the dev API instead of prod.
+    #[arg(long, global = true)]
+    dev: bool,
+    /// API base URL, overriding --dev.
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+    /// Personal access token.
+    #[arg(long, global = true)]
+    token: Option<String>,
+    /// File holding the personal access token.
+    #[arg(long, global = true)]
+    token_file: Option<PathBuf>,
+    /// Output format.
+    #[arg(long, short, global = true, value_enum, default_value_t = Format::Table)]
+    output: Format,
+    /// Comma-separated JSON field names to show, e.g. `id,name,status`.
+    #[arg(long, global = true, value_delimiter = ',')]
+    fields: Vec<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Telescopes and their tasks.
+    #[command(subcommand)]
+    Telescopes(TelescopeCommand),
+    /// Ground stations and their tasks.
+    #[command(subcommand)]
+    GroundStations(GroundstationCommand),
+    /// Antennas and their tasks.
+    #[command(subcommand)]
+    Antennas(AntennaCommand),
+    /// Observation tasks.
+    #[command(subcommand)]
+    Tasks(TaskCommand),
+    /// Satellite passes over a ground station.
+    Access(AccessArgs),
+    /// Satellites inside a sensor's field of view.
+    Fov(FovArgs),
+    /// RF captures recorded by antennas.
+    #[command(subcommand)]
+    RfCaptures(RfCaptureCommand),
+}
+
+#[derive(Subcommand)]
+enum TelescopeCommand {
+    List,
+    Get {
+        id: String,
+    },
+    Create {
+        name: String,
+        #[command(flatten)]
+        settings: TelescopeSettings,
+    },
+    /// Changes only the given settings.
+    Update {
+        id: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[command(flatten)]
+        settings: TelescopeSettings,
+        #[command(flatten)]
+        precondition: PreconditionArgs,
+    },
+    Delete {
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    Tasks {
+        id: String,
+        #[command(flatten)]
+        filter: TaskFilter,
+    },
+}
+
+#[derive(Args)]
+struct TelescopeSettings {
+    #[arg(long)]
+    ground_station: Option<String>,
+    /// Field of view, in degrees.
+    #[arg(long)]
+    field_of_view: Option<f64>,
+    /// Astrometric noise, in arcseconds.
+    #[arg(long)]
+    angular_noise: Option<f64>,
+    #[arg(long)]
+    limiting_magnitude: Option<f64>,
+    /// Elevation mask, in degrees.
+    #[arg(long, allow_negative_numbers = true)]
+    min_elevation: Option<f64>,
+    /// In degrees per second.
+    #[arg(long)]
+    max_slew_rate: Option<f64>,
+    /// Park azimuth, in degrees.
+    #[arg(long)]
+    home_azimuth: Option<f64>,
+    /// Park elevation, in degrees.
+    #[arg(long, allow_negative_numbers = true)]
+    home_elevation: Option<f64>,
+    #[arg(long)]
+    automated_scheduling: Option<bool>,
+}
+
+#[derive(Subcommand)]
+enum GroundstationCommand {
+    List,
+    Get {
+        id: String,
+    },
+    Create {
+        name: String,
+        /// Geodetic latitude, in degrees.
+        #[arg(long, allow_negative_numbers = true)]
+        latitude: f64,
+        /// Longitude, in degrees east.
+        #[arg(long, allow_negative_numbers = true)]
+        longitude: f64,
+        /// Altitude above the WGS-84 ellipsoid, in metres.
+        #[arg(long, allow_negative_numbers = true)]
+        altitude: f64,
+    },
+    /// Changes only the given settings.
+    Update {
+        id: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long, allow_negative_numbers = true)]
+        latitude: Option<f64>,
+        #[arg(long, allow_negative_numbers = true)]
+        longitude: Option<f64>,
+        #[arg(long, allow_negative_numbers = true)]
+        altitude: Option<f64>,
+        #[command(flatten)]
+        precondition: PreconditionArgs,
+    },
+    Delete {
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    Tasks {
+        id: String,
+        #[command(flatten)]
+        filter: TaskFilter,
+    },
+}
+
+#[derive(Subcommand)]
+enum AntennaCommand {
+    List,
+    Get {
+        id: String,
+    },
+    Create {
+        name: String,
+        /// Lower end of the frequency range, in MHz.
+        #[arg(long)]
+        min_frequency: f64,
+        /// Upper end of the frequency range, in MHz.
+        #[arg(long)]
+        max_frequency: f64,
+        #[command(flatten)]
+        settings: AntennaSettings,
+    },
+    /// Changes only the given settings.
+    Update {
+        id: String,
+        #[arg(long)]
+        name: Option<String>,
+        /// In MHz, together with --max-frequency.
+        #[arg(long, requires = "max_frequency")]
+        min_frequency: Option<f64>,
+        /// In MHz, together with --min-frequency.
+        #[arg(long, requires = "min_frequency")]
+        max_frequency: Option<f64>,
+        #[command(flatten)]
+        settings: AntennaSettings,
+        #[command(flatten)]
+        precondition: PreconditionArgs,
+    },
+    Delete {
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    Tasks {
+        id: String,
+        #[command(flatten)]
+        filter: TaskFilter,
+    },
+}
+
+#[derive(Args)]
+struct AntennaSettings {
+    #[arg(long)]
+    ground_station: Option<String>,
+    /// Elevation mask, in degrees.
+    #[arg(long, allow_negative_numbers = true)]
+    min_elevation: Option<f64>,
+    /// In degrees per second.
+    #[arg(long)]
+    max_slew_rate: Option<f64>,
+    /// Park azimuth, in degrees.
+    #[arg(long)]
+    home_azimuth: Option<f64>,
+    /// Park elevation, in degrees.
+    #[arg(long, allow_negative_numbers = true)]
+    home_elevation: Option<f64>,
+    /// Half-power beam width, in degrees.
+    #[arg(long)]
+    beam_width: Option<f64>,
+}
+
+#[derive(Args)]
+struct PreconditionArgs {
+    /// Only update if the resource still has this ETag.
+    #[arg(long, conflicts_with = "if_unmodified_since")]
+    if_match: Option<String>,
+    /// Only update if the resource was not changed after this time (RFC 3339).
+    #[arg(long)]
+    if_unmodified_since: Option<DateTime<Utc>>,
+}
+
+impl PreconditionArgs {
+    fn precondition(&self) -> Option<Precondition> {
+        match (&self.if_match, self.if_unmodified_since) {
+            (Some(etag), _) => Some(Precondition::Matches(etag.clone())),
+            (None, Some(time)) => Some(Precondition::UnmodifiedSince(time)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Args)]
+struct TaskFilter {
+    /// Only tasks with this status; may be repeated.
+    #[arg(long, value_parser = parse_status)]
+    status: Vec<TaskStatus>,
+    #[arg(long)]
+    satellite: Option<String>,
+    #[arg(long)]
+    limit: Option<u32>,
+}
+
+impl TaskFilter {
+    fn query(&self) -> TaskQuery {
+        let mut query = TaskQuery::new().statuses(self.status.iter().copied());
+        if let Some(satellite) = &self.satellite {
+            query = query.satellite(satellite);
+        }
+        if let Some(limit) = self.limit {
+            query = query.limit(limit);
+        }
+        query
+    }
+}
+
+#[derive(Subcommand)]
+enum TaskCommand {
+    /// Tasks across all telescopes and antennas visible to you.
+    List {
+        #[command(flatten)]
+        filter: TaskFilter,
+    },
+    /// Tasks you created.
+    Mine,
+    Get {
+        id: String,
+    },
+    Create {
+        #[arg(long)]
+        satellite: String,
+        /// Defaults to the profile's telescope, then its antenna.
+        #[arg(long, conflicts_with = "antenna")]
+        telescope: Option<String>,
+        #[arg(long)]
+        antenna: Option<String>,
+        /// Start of the window (RFC 3339), defaults to now.
+        #[arg(long)]
+        start: Option<DateTime<Utc>>,
+        /// Length of the window, in minutes.
+        #[arg(long, default_value_t = 60)]
+        minutes: i64,
+    },
+    Update {
+        id: String,
+        #[arg(long, value_parser = parse_status)]
+        status: TaskStatus,
+        #[arg(long)]
+        priority: Option<i32>,
+    },
+    Cancel {
+        id: String,
+    },
+    Delete {
+        id: String,
+    },
+}
+
+#[derive(Args)]
+struct AccessArgs {
+    /// Defaults to the profile's ground station.
+    #[arg(long)]
+    ground_station: Option<String>,
+    /// Start of the search (RFC 3339), defaults to now.
+    #[arg(long)]
+    start: Option<DateTime<Utc>>,
+    /// Length of the search, in hours.
+    #[arg(long, default_value_t = 24)]
+    hours: i64,
+    /// Elevation mask, in degrees.
+    #[arg(long, default_value_t = 10.0, allow_negative_numbers = true)]
+    min_elevation: f64,
+    /// Shortest pass to report, in minutes.
+    #[arg(long, default_value_t = 0.0)]
+    min_duration: f64,
+    /// Only satellites transmitting within this range, in MHz, e.g. `435,438`.
+    #[arg(long, value_delimiter = ',', num_args = 2)]
+    frequency_band: Option<Vec<f64>>,
+}
+
+#[derive(Args)]
+struct FovArgs {
+    /// Defaults to the profile's ground station.
+    #[arg(long)]
+    ground_station: Option<String>,
+    /// Right ascension of the boresight, in degrees.
+    #[arg(long)]
+    ra: f64,
+    /// Declination of the boresight, in degrees.
+    #[arg(long, allow_negative_numbers = true)]
+    dec: f64,
+    /// Field of view, in degrees.
+    #[arg(long)]
+    fov: f64,
+    /// Observation time (RFC 3339), defaults to now.
+    #[arg(long)]
+    time: Option<DateTime<Utc>>,
+    /// Use TEME instead of J2000 coordinates.
+    #[arg(long)]
+    teme: bool,
+}
+
+#[derive(Subcommand)]
+enum RfCaptureCommand {
+    Get {
+        id: String,
+    },
+    /// Captures of an antenna or a task.
+    List {
+        #[arg(long, required_unless_present = "task", conflicts_with = "task")]
+        antenna: Option<String>,
+        #[arg(long)]
+        task: Option<String>,
+    },
+}
+
+fn parse_status(value: &str) -> Result<TaskStatus, String> {
+    [
+        TaskStatus::Pending,
+        TaskStatus::Canceled,
+        TaskStatus::Scheduled,
+        TaskStatus::Succeeded,
+        TaskStatus::Failed,
+    ]
+    .into_iter()
+    .find(|status| status.as_str().eq_ignore_ascii_case(value))
+    .ok_or_else(|| format!("unknown task status `{}`", value))
+}
+
+/// The selected profile. Without a configuration file, or without
+/// `--profile` and a default profile in it, an empty one.
+fn profile(cli: &Cli) -> Result<Profile, LemonaidError> {
+    let path = Config::default_path().filter(|path| path.exists());
+    match (path, &cli.profile) {
+        (Some(path), name) => {
+            let config = Config::from_path(path)?;
+            if name.is_none()
+                && config.default_profile.is_none()
+                && !config
+                    .profiles
+                    .contains_key(lemonaid::config::DEFAULT_PROFILE)
+            {
+                return Ok(Profile::default());
+            }
+            config.profile(name.as_deref()).cloned()
+        }
+        (None, Some(name)) => Err(LemonaidError::Config(format!(
+            "profile `{}` requested but there is no configuration file",
+            name
+        ))),
+        (None, None) => Ok(Profile::default()),
+    }
+}
+
+fn client(cli: &Cli, profile: &Profile) -> Result<CitraClient, LemonaidError> {
+    let mut builder = if profile.has_token() {
+        profile.client_builder()?
+    } else {
+        let mut profile = profile.clone();
+        profile.token_env = Some("CITRA_PAT".to_string());
+        profile.client_builder()?
+    };
+    if let Some(token) = &cli.token {
+        builder = builder.auth(StaticToken::new(token));
+    } else if let Some(path) = &cli.token_file {
+        builder = builder.auth(FileToken::new(path));
+    } else if !profile.has_token() && std::env::var_os("CITRA_PAT").is_none() {
+        let path = Config::default_path()
+            .and_then(|path| path.parent().map(|dir| dir.join("token")))
+            .filter(|path| path.exists())
+            .ok_or_else(|| {
+                LemonaidError::Config(
+                    "no token: set CITRA_PAT, pass --token or --token-file, configure a profile, \
+                     or write it to ~/.config/lemonaid/token"
+                        .to_string(),
+                )
+            })?;
+        builder = builder.auth(FileToken::new(path));
+    }
+    builder = builder.user_agent(concat!("lemonaid-cli/", env!("CARGO_PKG_VERSION")));
+    if cli.dev {
+        builder = builder.dev(true);
+    }
+    if let Some(base_url) = &cli.base_url {
+        builder = builder.base_url(base_url);
+    }
+    builder.build()
+}
+
+/// An id given on the command line, else the profile's default.
+fn id_or_default<'a>(
+    given: &'a Option<String>,
+    default: &'a Option<String>,
+    flag: &str,
+) -> Result<&'a str, LemonaidError> {
+    given.as_deref().or(default.as_deref()).ok_or_else(|| {
+        LemonaidError::Config(format!("pass {} or set a default in the profile", flag))
+    })
+}
+
+/// Prints a response, turning output errors into client errors.
+fn show<T: serde::Serialize>(cli: &Cli, value: &T) -> Result<(), LemonaidError> {
+    output::print(cli.output, &cli.fields, value)
+        .map_err(|err| LemonaidError::Config(format!("cannot write output: {}", err)))
+}
+
+async fn run(cli: &Cli) -> Result<(), LemonaidError> {
+    let profile = profile(cli)?;
+    let client = client(cli, &profile)?;
+    match &cli.command {
+        Command::Telescopes(command) => match command {
+            TelescopeCommand::List => show(cli, &client.list_telescopes().await?),
+            TelescopeCommand::Get { id } => show(cli, &client.get_telescope(id).await?),
+            TelescopeCommand::Create { name, settings } => {
+                let request = settings.create(TelescopeCreateRequest::new(name));
+                show(cli, &client.create_telescope(&request).await?)
+            }
+            TelescopeCommand::Update {
+                id,
+                name,
+                settings,
+                precondition,
+            } => {
+                let mut update = settings.update(TelescopeUpdateRequest::new());
+                update.name = name.clone();
+                update.precondition = precondition.precondition();
+                show(cli, &client.patch_telescope(id, &update).await?)
+            }
+            TelescopeCommand::Delete { ids } => {
+                let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+                client.delete_telescopes(&ids).await
+            }
+            TelescopeCommand::Tasks { id, filter } => show(
+                cli,
+                &client
+                    .query_tasks_for_telescope(id, &filter.query())
+                    .await?,
+            ),
+        },
+        Command::GroundStations(command) => match command {
+            GroundstationCommand::List => show(cli, &client.list_groundstations().await?),
+            GroundstationCommand::Get { id } => show(cli, &client.get_groundstation(id).await?),
+            GroundstationCommand::Create {
+                name,
+                latitude,
+                longitude,
+                altitude,
+            } => {
+                let request = GroundstationCreateRequest::new(
+                    name,
+                    Degrees(*latitude),
+                    Degrees(*longitude),
+                    *altitude,
+                );
+                show(cli, &client.create_groundstation(&request).await?)
+            }
+            GroundstationCommand::Update {
+                id,
+                name,
+                latitude,
+                longitude,
+                altitude,
+                precondition,
+            } => {
+                let update = GroundstationUpdateRequest {
+                    name: name.clone(),
+                    latitude: latitude.map(Degrees),
+                    longitude: longitude.map(Degrees),
+                    altitude: *altitude,
+                    precondition: precondition.precondition(),
+                };
+                show(cli, &client.patch_groundstation(id, &update).await?)
+            }
+            GroundstationCommand::Delete { ids } => {
+                let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+                client.delete_groundstations(&ids).await
+            }
+            GroundstationCommand::Tasks { id, filter } => show(
+                cli,
+                &client
+                    .query_tasks_for_groundstation(id, &filter.query())
+                    .await?,
+            ),
+        },
+        Command::Antennas(command) => match command {
+            AntennaCommand::List => show(cli, &client.list_antennas().await?),
+            AntennaCommand::Get { id } => show(cli, &client.get_antenna(id).await?),
+            AntennaCommand::Create {
+                name,
+                min_frequency,
+                max_frequency,
+                settings,
+            } => {
+                let request = settings.create(AntennaCreateRequest::new(
+                    name,
+                    MHz(*min_frequency).into(),
+                    MHz(*max_frequency).into(),
+                ));
+                show(cli, &client.create_antenna(&request).await?)
+            }
+            AntennaCommand::Update {
+                id,
+                name,
+                min_frequency,
+                max_frequency,
+                settings,
+                precondition,
+            } => {
+                let mut update = settings.update(AntennaUpdateRequest::new());
+                update.name = name.clone();
+                update.min_frequency_hz = min_frequency.map(|f| Hz::from(MHz(f)));
+                update.max_frequency_hz = max_frequency.map(|f| Hz::from(MHz(f)));
+                update.precondition = precondition.precondition();
+                show(cli, &client.patch_antenna(id, &update).await?)
+            }
+            AntennaCommand::Delete { ids } => {
+                let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+                client.delete_antennas(&ids).await
+            }
+            AntennaCommand::Tasks { id, filter } => show(
+                cli,
+                &client.query_tasks_for_antenna(id, &filter.query()).await?,
+            ),
+        },
+        Command::Tasks(command) => match command {
+            TaskCommand::List { filter } => show(cli, &client.list_tasks(&filter.query()).await?),
+            TaskCommand::Mine => show(cli, &client.list_my_tasks().await?),
+            TaskCommand::Get { id } => show(cli, &client.get_task(id).await?),
+            TaskCommand::Create {
+                satellite,
+                telescope,
+                antenna,
+                start,
+                minutes,
+            } => {
+                let start = start.unwrap_or_else(Utc::now);
+                let stop = start + Duration::minutes(*minutes);
+                let request = match (telescope, antenna) {
+                    (Some(telescope), _) => {
+                        CreateTaskRequest::for_telescope(telescope, satellite, start, stop)
+                    }
+                    (None, Some(antenna)) => {
+                        CreateTaskRequest::for_antenna(antenna, satellite, start, stop)
+                    }
+                    (None, None) => match (&profile.telescope_id, &profile.antenna_id) {
+                        (Some(telescope), _) => {
+                            CreateTaskRequest::for_telescope(telescope, satellite, start, stop)
+                        }
+                        (None, Some(antenna)) => {
+                            CreateTaskRequest::for_antenna(antenna, satellite, start, stop)
+                        }
+                        (None, None) => {
+                            return Err(LemonaidError::Config(
+                                "pass --telescope or --antenna or set a default in the profile"
+                                    .to_string(),
+                            ));
+                        }
+                    },
+                };
+                show(cli, &client.create_task(&request).await?)
+            }
+            TaskCommand::Update {
+                id,
+                status,
+                priority,
+            } => {
+                let mut update = TaskUpdateRequest::new(id, *status);
+                update.priority = *priority;
+                show(cli, &client.update_task(&update).await?)
+            }
+            TaskCommand::Cancel { id } => show(cli, &client.cancel_task(id).await?),
+            TaskCommand::Delete { id } => client.delete_task(id).await,
+        },
+        Command::Access(args) => {
+            let start = args.start.unwrap_or_else(Utc::now);
+            let groundstation_id = id_or_default(
+                &args.ground_station,
+                &profile.groundstation_id,
+                "--ground-station",
+            )?;
+            let mut request = SatelliteAccessToGroundstationRequest::new(
+                groundstation_id,
+                start,
+                start + Duration::hours(args.hours),
+            )
+            .min_elevation(Degrees(args.min_elevation))
+            .min_duration_minutes(args.min_duration);
+            if let Some(band) = &args.frequency_band {
+                request = request.frequency_band(MHz(band[0]), MHz(band[1]));
+            }
+            show(cli, &client.solve_access_for_groundstation(&request).await?)
+        }
+        Command::Fov(args) => {
+            let groundstation_id = id_or_default(
+                &args.ground_station,
+                &profile.groundstation_id,
+                "--ground-station",
+            )?;
+            let groundstation = client.get_groundstation(groundstation_id).await?;
+            let mut request = FOVAccessRequest::new(
+                &groundstation,
+                args.time.unwrap_or_else(Utc::now),
+                Degrees(args.ra),
+                Degrees(args.dec),
+                Degrees(args.fov),
+            );
+            if args.teme {
+                request = request.frame(SensorFrame::TEME);
+            }
+            show(cli, &client.solve_fov_access(&request).await?)
+        }
+        Command::RfCaptures(command) => match command {
+            RfCaptureCommand::Get { id } => show(cli, &client.get_rf_capture(id).await?),
+            RfCaptureCommand::List { antenna, task } => {
+                let captures = match (antenna, task) {
+                    (Some(antenna), _) => client.list_rf_captures_for_antenna(antenna).await?,
+                    (None, Some(task)) => client.list_rf_captures_for_task(task).await?,
+                    (None, None) => unreachable!("clap requires --antenna or --task"),
+                };
+                show(cli, &captures)
+            }
+        },
+    }
+}
+
+impl TelescopeSettings {
+    fn create(&self, mut request: TelescopeCreateRequest) -> TelescopeCreateRequest {
+        let update = self.update(TelescopeUpdateRequest::new());
+        request.groundstation_id = update.groundstation_id.or(request.groundstation_id);
+        request.field_of_view_deg = update
+            .field_of_view_deg
+            .unwrap_or(request.field_of_view_deg);
+        request.angular_noise_arcsec = update
+            .angular_noise_arcsec
+            .unwrap_or(request.angular_noise_arcsec);
+        request.limiting_magnitude = update
+            .limiting_magnitude
+            .unwrap_or(request.limiting_magnitude);
+        request.min_elevation_deg = update
+            .min_elevation_deg
+            .unwrap_or(request.min_elevation_deg);
+        request.max_slew_rate_deg_per_sec = update
+            .max_slew_rate_deg_per_sec
+            .unwrap_or(request.max_slew_rate_deg_per_sec);
+        request.home_azimuth_deg = update.home_azimuth_deg.unwrap_or(request.home_azimuth_deg);
+        request.home_elevation_deg = update
+            .home_elevation_deg
+            .unwrap_or(request.home_elevation_deg);
+        request.automated_scheduling = update
+            .automated_scheduling
+            .unwrap_or(request.automated_scheduling);
+        request
+    }
+
+    fn update(&self, mut update: TelescopeUpdateRequest) -> TelescopeUpdateRequest {
+        update.groundstation_id = self.ground_station.clone();
+        update.field_of_view_deg = self.field_of_view.map(Degrees);
+        update.angular_noise_arcsec = self.angular_noise.map(Arcsec);
+        update.limiting_magnitude = self.limiting_magnitude;
+        update.min_elevation_deg = self.min_elevation.map(Degrees);
+        update.max_slew_rate_deg_per_sec = self.max_slew_rate;
+        update.home_azimuth_deg = self.home_azimuth.map(Degrees);
+        update.home_elevation_deg = self.home_elevation.map(Degrees);
+        update.automated_scheduling = self.automated_scheduling;
+        update
+    }
+}
+
+impl AntennaSettings {
+    fn create(&self, mut request: AntennaCreateRequest) -> AntennaCreateRequest {
+        let update = self.update(AntennaUpdateRequest::new());
+        request.groundstation_id = update.groundstation_id.or(request.groundstation_id);
+        request.min_elevation_deg = update
+            .min_elevation_deg
+            .unwrap_or(request.min_elevation_deg);
+        request.max_slew_rate_deg_per_sec = update
+            .max_slew_rate_deg_per_sec
+            .unwrap_or(request.max_slew_rate_deg_per_sec);
+        request.home_azimuth_deg = update.home_azimuth_deg.unwrap_or(request.home_azimuth_deg);
+        request.home_elevation_deg = update
+            .home_elevation_deg
+            .unwrap_or(request.home_elevation_deg);
+        request.half_power_beam_width_deg = update
+            .half_power_beam_width_deg
+            .unwrap_or(request.half_power_beam_width_deg);
+        request
+    }
+
+    fn update(&self, mut update: AntennaUpdateRequest) -> AntennaUpdateRequest {
+        update.groundstation_id = self.ground_station.clone();
+        update.min_elevation_deg = self.min_elevation.map(Degrees);
+        update.max_slew_rate_deg_per_sec = self.max_slew_rate;
+        update.home_azimuth_deg = self.home_azimuth.map(Degrees);
+        update.home_elevation_deg = self.home_elevation.map(Degrees);
+        update.half_power_beam_width_deg = self.beam_width.map(Degrees);
+        update
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    /// Aligned columns, one row per item; single items are shown as key/value pairs.
+    Table,
+    /// Pretty-printed JSON, with the API's field names.
+    Json,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+/// Prints a response in the requested format, restricted to `fields` if any
+/// are given. Nested values are shown as compact JSON in table and CSV output.
+pub fn print<T: Serialize>(format: Format, fields: &[String], value: &T) -> io::Result<()> {
+    let value = serde_json::to_value(value).map_err(io::Error::other)?;
+    let mut out = io::stdout().lock();
+    match format {
+        Format::Json => {
+            let value = match value {
+                Value::Array(items) => {
+                    Value::Array(items.into_iter().map(|item| select(item, fields)).collect())
+                }
+                item => select(item, fields),
+            };
+            serde_json::to_writer_pretty(&mut out, &value).map_err(io::Error::other)?;
+            writeln!(out)
+        }
+        Format::Csv => {
+            let rows = rows(value, fields);
+            let columns = columns(&rows, fields);
+            write_csv_row(&mut out, columns.iter().map(String::as_str))?;
+            for row in &rows {
+                let cells: Vec<String> = columns.iter().map(|c| cell(row.get(c))).collect();
+                write_csv_row(&mut out, cells.iter().map(String::as_str))?;
+            }
+            Ok(())
+        }
+        Format::Table => match value {
+            Value::Array(_) => {
+                let rows = rows(value, fields);
+                let columns = columns(&rows, fields);
+                let cells: Vec<Vec<String>> = rows
+                    .iter()
+                    .map(|row| columns.iter().map(|c| cell(row.get(c))).collect())
+                    .collect();
+                write_table(&mut out, &columns, &cells)
+            }
+            item => match select(item, fields) {
+                Value::Object(object) => {
+                    let width = object.keys().map(|k| k.chars().count()).max().unwrap_or(0);
+                    for (key, value) in &object {
+                        writeln!(out, "{:width$}  {}", key, cell(Some(value)), width = width)?;
+                    }
+                    Ok(())
+                }
+                other => writeln!(out, "{}", cell(Some(&other))),
+            },
+        },
+    }
+}
+
+/// Keeps only the selected fields of an object, in the order they were asked for.
+fn select(value: Value, fields: &[String]) -> Value {
+    match value {
+        Value::Object(mut object) if !fields.is_empty() => Value::Object(
+            fields
+                .iter()
+                .map(|field| (field.clone(), object.remove(field).unwrap_or(Value::Null)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn rows(value: Value, fields: &[String]) -> Vec<Map<String, Value>> {
+    let items = match value {
+        Value::Array(items) => items,
+        item => vec![item],
+    };
+    items
+        .into_iter()
+        .map(|item| match select(item, fields) {
+            Value::Object(object) => object,
+            other => Map::from_iter([("value".to_string(), other)]),
+        })
+        .collect()
+}
+
+/// Union of the keys of all rows, in first-seen order.
+fn columns(rows: &[Map<String, Value>], fields: &[String]) -> Vec<String> {
+    if !fields.is_empty() {
+        return fields.to_vec();
+    }
+    let mut columns: Vec<String> = Vec::new();
+    for key in rows.iter().flat_map(|row| row.keys()) {
+        if !columns.contains(key) {
+            columns.push(key.clone());
+        }
+    }
+    columns
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn write_table(out: &mut impl Write, columns: &[String], rows: &[Vec<String>]) -> io::Result<()> {
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([column.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: &mut dyn Iterator<Item = &str>| {
+        cells
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    writeln!(out, "{}", line(&mut columns.iter().map(String::as_str)))?;
+    for row in rows {
+        writeln!(out, "{}", line(&mut row.iter().map(String::as_str)))?;
+    }
+    Ok(())
+}
+
+fn write_csv_row<'a>(out: &mut impl Write, cells: impl Iterator<Item = &'a str>) -> io::Result<()> {
+    let escaped: Vec<String> = cells
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect();
+    writeln!(out, "{}", escaped.join(","))
+}
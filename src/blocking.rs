@@ -0,0 +1,534 @@
+//! A synchronous client for scripts and programs without an async runtime.
+//!
+//! [`CitraClient`] wraps the async [`crate::CitraClient`] together with a
+//! single-threaded tokio runtime and blocks on each call, so it offers the same
+//! methods, retries and authentication. Like `reqwest::blocking`, it must not be
+//! used from within an async context.
+
+use chrono::{DateTime, Utc};
+use tokio::runtime::Runtime;
+
+use crate::pagination::PageIter;
+use crate::{
+    Antenna, AntennaCreateRequest, AntennaUpdateRequest, CitraClientBuilder,
+    CreateRFCaptureRequest, CreateTaskRequest, ElementSet, ElementSetUploadRequest,
+    FOVAccessRequest, FOVAccessResponse, Groundstation, GroundstationCreateRequest,
+    GroundstationUpdateRequest, HorizonAccess, LemonaidError, Page, PageRequest, Propagator,
+    RFCapture, RFCaptureSummary, Satellite, SatelliteAccessToGroundstationRequest, SatelliteQuery,
+    Task, TaskQuery, TaskStatus, TaskUpdateRequest, Telescope, TelescopeCreateRequest,
+    TelescopeUpdateRequest, Versioned,
+};
+
+pub struct CitraClient {
+    inner: crate::CitraClient,
+    runtime: Runtime,
+}
+
+impl CitraClient {
+    /// Creates a client for the hosted dev or prod API with default settings.
+    pub fn new(api_key: &str, dev: bool) -> Self {
+        Self::from_async(crate::CitraClient::new(api_key, dev))
+            .expect("failed to start the blocking client's runtime")
+    }
+
+    /// Wraps an async client, e.g. one configured through [`CitraClientBuilder`].
+    pub fn from_async(inner: crate::CitraClient) -> Result<Self, LemonaidError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| LemonaidError::Config(format!("cannot start runtime: {}", err)))?;
+        Ok(CitraClient { inner, runtime })
+    }
+
+    pub fn builder(api_key: &str) -> CitraClientBuilder {
+        CitraClientBuilder::new(api_key)
+    }
+
+    /// See [`crate::CitraClient::from_profile`].
+    #[cfg(feature = "config")]
+    pub fn from_profile(name: &str) -> Result<Self, LemonaidError> {
+        Self::from_async(crate::CitraClient::from_profile(name)?)
+    }
+
+    /// See [`crate::CitraClient::from_env`].
+    #[cfg(feature = "config")]
+    pub fn from_env() -> Result<Self, LemonaidError> {
+        Self::from_async(crate::CitraClient::from_env()?)
+    }
+
+    pub fn get_telescope(&self, telescope_id: &str) -> Result<Telescope, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.get_telescope(telescope_id))
+    }
+
+    pub fn get_telescope_versioned(
+        &self,
+        telescope_id: &str,
+    ) -> Result<Versioned<Telescope>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.get_telescope_versioned(telescope_id))
+    }
+
+    pub fn list_telescopes(&self) -> Result<Vec<Telescope>, LemonaidError> {
+        self.runtime.block_on(self.inner.list_telescopes())
+    }
+
+    pub fn list_telescopes_page(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<Telescope>, LemonaidError> {
+        self.runtime.block_on(self.inner.list_telescopes_page(page))
+    }
+
+    /// Iterates over all telescopes, fetching `page_size` at a time.
+    pub fn iter_telescopes(
+        &self,
+        page_size: u32,
+    ) -> impl Iterator<Item = Result<Telescope, LemonaidError>> + '_ {
+        PageIter::new(page_size, move |page| self.list_telescopes_page(page))
+    }
+
+    pub fn create_telescope(
+        &self,
+        telescope: &TelescopeCreateRequest,
+    ) -> Result<Telescope, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.create_telescope(telescope))
+    }
+
+    pub fn create_telescopes(
+        &self,
+        telescopes: &[TelescopeCreateRequest],
+    ) -> Result<Vec<Telescope>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.create_telescopes(telescopes))
+    }
+
+    pub fn delete_telescope(&self, telescope_id: &str) -> Result<(), LemonaidError> {
+        self.runtime
+            .block_on(self.inner.delete_telescope(telescope_id))
+    }
+
+    pub fn delete_telescopes(&self, telescope_ids: &[&str]) -> Result<(), LemonaidError> {
+        self.runtime
+            .block_on(self.inner.delete_telescopes(telescope_ids))
+    }
+
+    pub fn update_telescope(&self, telescope: &Telescope) -> Result<Telescope, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.update_telescope(telescope))
+    }
+
+    pub fn update_telescopes(
+        &self,
+        telescopes: &[Telescope],
+    ) -> Result<Vec<Telescope>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.update_telescopes(telescopes))
+    }
+
+    pub fn patch_telescope(
+        &self,
+        telescope_id: &str,
+        update: &TelescopeUpdateRequest,
+    ) -> Result<Telescope, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.patch_telescope(telescope_id, update))
+    }
+
+    pub fn get_groundstation(
+        &self,
+        groundstation_id: &str,
+    ) -> Result<Groundstation, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.get_groundstation(groundstation_id))
+    }
+
+    pub fn get_groundstation_versioned(
+        &self,
+        groundstation_id: &str,
+    ) -> Result<Versioned<Groundstation>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.get_groundstation_versioned(groundstation_id))
+    }
+
+    pub fn list_groundstations(&self) -> Result<Vec<Groundstation>, LemonaidError> {
+        self.runtime.block_on(self.inner.list_groundstations())
+    }
+
+    pub fn list_groundstations_page(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<Groundstation>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.list_groundstations_page(page))
+    }
+
+    /// Iterates over all ground stations, fetching `page_size` at a time.
+    pub fn iter_groundstations(
+        &self,
+        page_size: u32,
+    ) -> impl Iterator<Item = Result<Groundstation, LemonaidError>> + '_ {
+        PageIter::new(page_size, move |page| self.list_groundstations_page(page))
+    }
+
+    pub fn create_groundstation(
+        &self,
+        groundstation: &GroundstationCreateRequest,
+    ) -> Result<Groundstation, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.create_groundstation(groundstation))
+    }
+
+    pub fn create_groundstations(
+        &self,
+        groundstations: &[GroundstationCreateRequest],
+    ) -> Result<Vec<Groundstation>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.create_groundstations(groundstations))
+    }
+
+    pub fn delete_groundstation(&self, groundstation_id: &str) -> Result<(), LemonaidError> {
+        self.runtime
+            .block_on(self.inner.delete_groundstation(groundstation_id))
+    }
+
+    pub fn delete_groundstations(&self, groundstation_ids: &[&str]) -> Result<(), LemonaidError> {
+        self.runtime
+            .block_on(self.inner.delete_groundstations(groundstation_ids))
+    }
+
+    pub fn update_groundstation(
+        &self,
+        groundstation_id: &str,
+        groundstation: &GroundstationCreateRequest,
+    ) -> Result<Groundstation, LemonaidError> {
+        self.runtime.block_on(
+            self.inner
+                .update_groundstation(groundstation_id, groundstation),
+        )
+    }
+
+    pub fn patch_groundstation(
+        &self,
+        groundstation_id: &str,
+        update: &GroundstationUpdateRequest,
+    ) -> Result<Groundstation, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.patch_groundstation(groundstation_id, update))
+    }
+
+    pub fn query_tasks_for_groundstation(
+        &self,
+        groundstation_id: &str,
+        query: &TaskQuery,
+    ) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime.block_on(
+            self.inner
+                .query_tasks_for_groundstation(groundstation_id, query),
+        )
+    }
+
+    pub fn solve_access_for_groundstation(
+        &self,
+        access_request: &SatelliteAccessToGroundstationRequest,
+    ) -> Result<Vec<HorizonAccess>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.solve_access_for_groundstation(access_request))
+    }
+
+    pub fn solve_fov_access(
+        &self,
+        fov_request: &FOVAccessRequest,
+    ) -> Result<Vec<FOVAccessResponse>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.solve_fov_access(fov_request))
+    }
+
+    pub fn list_tasks_for_telescope(&self, telescope_id: &str) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.list_tasks_for_telescope(telescope_id))
+    }
+
+    pub fn get_task(&self, task_id: &str) -> Result<Task, LemonaidError> {
+        self.runtime.block_on(self.inner.get_task(task_id))
+    }
+
+    pub fn delete_task(&self, task_id: &str) -> Result<(), LemonaidError> {
+        self.runtime.block_on(self.inner.delete_task(task_id))
+    }
+
+    pub fn cancel_task(&self, task_id: &str) -> Result<Task, LemonaidError> {
+        self.runtime.block_on(self.inner.cancel_task(task_id))
+    }
+
+    pub fn list_tasks_for_satellite(&self, satellite_id: &str) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.list_tasks_for_satellite(satellite_id))
+    }
+
+    pub fn list_tasks_for_telescope_page(
+        &self,
+        telescope_id: &str,
+        page: PageRequest,
+    ) -> Result<Page<Task>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.list_tasks_for_telescope_page(telescope_id, page))
+    }
+
+    /// Iterates over all tasks of a telescope, fetching `page_size` at a time.
+    pub fn iter_tasks_for_telescope<'a>(
+        &'a self,
+        telescope_id: &'a str,
+        page_size: u32,
+    ) -> impl Iterator<Item = Result<Task, LemonaidError>> + 'a {
+        PageIter::new(page_size, move |page| {
+            self.list_tasks_for_telescope_page(telescope_id, page)
+        })
+    }
+
+    pub fn get_telescope_tasks_by_status(
+        &self,
+        telescope_id: &str,
+        statuses: Vec<TaskStatus>,
+    ) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime.block_on(
+            self.inner
+                .get_telescope_tasks_by_status(telescope_id, statuses),
+        )
+    }
+
+    pub fn query_tasks_for_telescope(
+        &self,
+        telescope_id: &str,
+        query: &TaskQuery,
+    ) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.query_tasks_for_telescope(telescope_id, query))
+    }
+
+    pub fn update_task(&self, task: &TaskUpdateRequest) -> Result<Task, LemonaidError> {
+        self.runtime.block_on(self.inner.update_task(task))
+    }
+
+    pub fn create_task(&self, task: &CreateTaskRequest) -> Result<Task, LemonaidError> {
+        self.runtime.block_on(self.inner.create_task(task))
+    }
+
+    pub fn list_tasks(&self, query: &TaskQuery) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime.block_on(self.inner.list_tasks(query))
+    }
+
+    pub fn list_my_tasks(&self) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime.block_on(self.inner.list_my_tasks())
+    }
+
+    pub fn list_antennas(&self) -> Result<Vec<Antenna>, LemonaidError> {
+        self.runtime.block_on(self.inner.list_antennas())
+    }
+
+    pub fn list_antennas_page(&self, page: PageRequest) -> Result<Page<Antenna>, LemonaidError> {
+        self.runtime.block_on(self.inner.list_antennas_page(page))
+    }
+
+    /// Iterates over all antennas, fetching `page_size` at a time.
+    pub fn iter_antennas(
+        &self,
+        page_size: u32,
+    ) -> impl Iterator<Item = Result<Antenna, LemonaidError>> + '_ {
+        PageIter::new(page_size, move |page| self.list_antennas_page(page))
+    }
+
+    pub fn get_antenna(&self, antenna_id: &str) -> Result<Antenna, LemonaidError> {
+        self.runtime.block_on(self.inner.get_antenna(antenna_id))
+    }
+
+    pub fn get_antenna_versioned(
+        &self,
+        antenna_id: &str,
+    ) -> Result<Versioned<Antenna>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.get_antenna_versioned(antenna_id))
+    }
+
+    pub fn create_antenna(&self, antenna: &AntennaCreateRequest) -> Result<Antenna, LemonaidError> {
+        self.runtime.block_on(self.inner.create_antenna(antenna))
+    }
+
+    pub fn create_antennas(
+        &self,
+        antennas: &[AntennaCreateRequest],
+    ) -> Result<Vec<Antenna>, LemonaidError> {
+        self.runtime.block_on(self.inner.create_antennas(antennas))
+    }
+
+    pub fn delete_antenna(&self, antenna_id: &str) -> Result<(), LemonaidError> {
+        self.runtime.block_on(self.inner.delete_antenna(antenna_id))
+    }
+
+    pub fn delete_antennas(&self, antenna_ids: &[&str]) -> Result<(), LemonaidError> {
+        self.runtime
+            .block_on(self.inner.delete_antennas(antenna_ids))
+    }
+
+    pub fn update_antenna(&self, antenna: &Antenna) -> Result<Antenna, LemonaidError> {
+        self.runtime.block_on(self.inner.update_antenna(antenna))
+    }
+
+    pub fn update_antennas(&self, antennas: &[Antenna]) -> Result<Vec<Antenna>, LemonaidError> {
+        self.runtime.block_on(self.inner.update_antennas(antennas))
+    }
+
+    pub fn patch_antenna(
+        &self,
+        antenna_id: &str,
+        update: &AntennaUpdateRequest,
+    ) -> Result<Antenna, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.patch_antenna(antenna_id, update))
+    }
+
+    pub fn list_tasks_for_antenna(&self, antenna_id: &str) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.list_tasks_for_antenna(antenna_id))
+    }
+
+    pub fn get_antenna_tasks_by_status(
+        &self,
+        antenna_id: &str,
+        statuses: Vec<TaskStatus>,
+    ) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.get_antenna_tasks_by_status(antenna_id, statuses))
+    }
+
+    pub fn query_tasks_for_antenna(
+        &self,
+        antenna_id: &str,
+        query: &TaskQuery,
+    ) -> Result<Vec<Task>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.query_tasks_for_antenna(antenna_id, query))
+    }
+
+    pub fn create_rf_capture(
+        &self,
+        rf_capture_request: &CreateRFCaptureRequest,
+    ) -> Result<RFCapture, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.create_rf_capture(rf_capture_request))
+    }
+
+    pub fn get_rf_capture(&self, rf_capture_id: &str) -> Result<RFCapture, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.get_rf_capture(rf_capture_id))
+    }
+
+    pub fn list_rf_captures_for_antenna(
+        &self,
+        antenna_id: &str,
+    ) -> Result<Vec<RFCaptureSummary>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.list_rf_captures_for_antenna(antenna_id))
+    }
+
+    pub fn list_rf_captures_for_antenna_page(
+        &self,
+        antenna_id: &str,
+        page: PageRequest,
+    ) -> Result<Page<RFCaptureSummary>, LemonaidError> {
+        self.runtime.block_on(
+            self.inner
+                .list_rf_captures_for_antenna_page(antenna_id, page),
+        )
+    }
+
+    /// Iterates over all RF captures of an antenna, fetching `page_size` at a time.
+    pub fn iter_rf_captures_for_antenna<'a>(
+        &'a self,
+        antenna_id: &'a str,
+        page_size: u32,
+    ) -> impl Iterator<Item = Result<RFCaptureSummary, LemonaidError>> + 'a {
+        PageIter::new(page_size, move |page| {
+            self.list_rf_captures_for_antenna_page(antenna_id, page)
+        })
+    }
+
+    pub fn list_rf_captures_for_task(
+        &self,
+        task_id: &str,
+    ) -> Result<Vec<RFCaptureSummary>, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.list_rf_captures_for_task(task_id))
+    }
+
+    pub fn get_satellite(&self, satellite_id: &str) -> Result<Satellite, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.get_satellite(satellite_id))
+    }
+
+    pub fn search_satellites(
+        &self,
+        query: &SatelliteQuery,
+    ) -> Result<Vec<Satellite>, LemonaidError> {
+        self.runtime.block_on(self.inner.search_satellites(query))
+    }
+
+    pub fn list_satellites(&self) -> Result<Vec<Satellite>, LemonaidError> {
+        self.runtime.block_on(self.inner.list_satellites())
+    }
+
+    pub fn list_satellites_page(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<Satellite>, LemonaidError> {
+        self.runtime.block_on(self.inner.list_satellites_page(page))
+    }
+
+    /// Iterates over the whole satellite catalog, fetching `page_size` at a time.
+    pub fn iter_satellites(
+        &self,
+        page_size: u32,
+    ) -> impl Iterator<Item = Result<Satellite, LemonaidError>> + '_ {
+        PageIter::new(page_size, move |page| self.list_satellites_page(page))
+    }
+
+    pub fn get_latest_elset(&self, satellite_id: &str) -> Result<ElementSet, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.get_latest_elset(satellite_id))
+    }
+
+    pub fn list_elsets_for_satellite(
+        &self,
+        satellite_id: &str,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ElementSet>, LemonaidError> {
+        self.runtime.block_on(
+            self.inner
+                .list_elsets_for_satellite(satellite_id, start, end),
+        )
+    }
+
+    pub fn propagator_for_satellite(
+        &self,
+        satellite_id: &str,
+    ) -> Result<Propagator, LemonaidError> {
+        self.runtime
+            .block_on(self.inner.propagator_for_satellite(satellite_id))
+    }
+
+    pub fn upload_elset(
+        &self,
+        elset: &ElementSetUploadRequest,
+    ) -> Result<ElementSet, LemonaidError> {
+        self.runtime.block_on(self.inner.upload_elset(elset))
+    }
+
+    pub fn upload_elsets(
+        &self,
+        elsets: &[ElementSetUploadRequest],
+    ) -> Result<Vec<ElementSet>, LemonaidError> {
+        self.runtime.block_on(self.inner.upload_elsets(elsets))
+    }
+}
@@ -0,0 +1,172 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{AuthProvider, CitraClient, LemonaidError, RetryPolicy, StaticToken};
+
+pub(crate) const PROD_BASE_URL: &str = "https://api.citra.space/";
+pub(crate) const DEV_BASE_URL: &str = "https://dev.api.citra.space/";
+
+/// Builder for a [`CitraClient`] with a custom base URL, timeouts, proxies or
+/// an injected `reqwest::Client`.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// let client = lemonaid::CitraClient::builder("my-token")
+///     .base_url("http://localhost:8080")
+///     .connect_timeout(Duration::from_secs(5))
+///     .read_timeout(Duration::from_secs(30))
+///     .build()
+///     .unwrap();
+/// ```
+pub struct CitraClientBuilder {
+    auth: Arc<dyn AuthProvider>,
+    base_url: String,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxies: Vec<reqwest::Proxy>,
+    no_proxy: bool,
+    client: Option<reqwest::Client>,
+    retry_policy: RetryPolicy,
+}
+
+impl CitraClientBuilder {
+    /// Creates a builder pointing at the production API.
+    pub fn new(api_key: &str) -> Self {
+        CitraClientBuilder {
+            auth: Arc::new(StaticToken::new(api_key)),
+            base_url: PROD_BASE_URL.to_string(),
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            user_agent: None,
+            proxies: Vec::new(),
+            no_proxy: false,
+            client: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Replaces the personal access token passed to [`new`](Self::new) with
+    /// another way of obtaining bearer tokens.
+    pub fn auth<P: AuthProvider + 'static>(mut self, provider: P) -> Self {
+        self.auth = Arc::new(provider);
+        self
+    }
+
+    /// Selects the hosted dev (`true`) or prod (`false`) API.
+    pub fn dev(mut self, dev: bool) -> Self {
+        self.base_url = if dev { DEV_BASE_URL } else { PROD_BASE_URL }.to_string();
+        self
+    }
+
+    /// Uses an arbitrary base URL, e.g. an on-prem mirror or a local mock server.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// Timeout for establishing the TCP/TLS connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for each read from the connection.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Total timeout for a request, from connecting until the body has been read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Overrides the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Routes requests through the given proxy. May be called multiple times.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Disables proxies, including those picked up from the environment.
+    pub fn no_proxy(mut self) -> Self {
+        self.no_proxy = true;
+        self
+    }
+
+    /// Uses an already configured `reqwest::Client`.
+    ///
+    /// The timeout, user agent and proxy settings of this builder are ignored
+    /// in that case, since they belong to the injected client.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Sets how transient failures are retried. Defaults to [`RetryPolicy::default`].
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Builds a synchronous [`blocking::CitraClient`](crate::blocking::CitraClient).
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> Result<crate::blocking::CitraClient, LemonaidError> {
+        crate::blocking::CitraClient::from_async(self.build()?)
+    }
+
+    pub fn build(self) -> Result<CitraClient, LemonaidError> {
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder =
+                    reqwest::Client::builder().user_agent(self.user_agent.unwrap_or_else(|| {
+                        concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")).to_string()
+                    }));
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.read_timeout {
+                    builder = builder.read_timeout(timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if self.no_proxy {
+                    builder = builder.no_proxy();
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                builder.build()?
+            }
+        };
+
+        let base_url = reqwest::Url::parse(&self.base_url).map_err(|err| {
+            LemonaidError::Config(format!("invalid base URL `{}`: {}", self.base_url, err))
+        })?;
+        if base_url.cannot_be_a_base() {
+            return Err(LemonaidError::Config(format!(
+                "base URL `{}` cannot have path segments",
+                self.base_url
+            )));
+        }
+
+        Ok(CitraClient {
+            base_url,
+            auth: self.auth,
+            client,
+            retry_policy: self.retry_policy,
+        })
+    }
+}
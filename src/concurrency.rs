@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderName, IF_MATCH, IF_UNMODIFIED_SINCE};
+
+/// Condition the resource must still meet for a partial update to be applied.
+///
+/// If another client changed the resource in the meantime the API answers
+/// `409`/`412` and the update fails with [`LemonaidError::Conflict`](crate::LemonaidError::Conflict).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Precondition {
+    /// Sent as `If-Match`: the resource's ETag, as returned by one of the
+    /// `get_*_versioned` calls, must be unchanged.
+    Matches(String),
+    /// Sent as `If-Unmodified-Since`: the resource must not have been updated
+    /// after this time, e.g. the `updated_at` of a previously fetched copy.
+    /// HTTP dates have whole-second precision.
+    UnmodifiedSince(DateTime<Utc>),
+}
+
+impl Precondition {
+    pub(crate) fn header(&self) -> (HeaderName, String) {
+        match self {
+            Precondition::Matches(etag) => (IF_MATCH, etag.clone()),
+            Precondition::UnmodifiedSince(time) => (
+                IF_UNMODIFIED_SINCE,
+                time.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ),
+        }
+    }
+}
+
+/// A resource together with the `ETag` the API returned for it, if any.
+#[derive(Debug, Clone)]
+pub struct Versioned<T> {
+    pub value: T,
+    pub etag: Option<String>,
+}
+
+impl<T> Versioned<T> {
+    /// Precondition that the resource is still at this version.
+    ///
+    /// `None` if the API did not return an ETag for it.
+    pub fn precondition(&self) -> Option<Precondition> {
+        self.etag.clone().map(Precondition::Matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unmodified_since_uses_http_dates() {
+        let time = "2025-03-07T09:05:01.750Z".parse().unwrap();
+        let (name, value) = Precondition::UnmodifiedSince(time).header();
+        assert_eq!(name, IF_UNMODIFIED_SINCE);
+        assert_eq!(value, "Fri, 07 Mar 2025 09:05:01 GMT");
+    }
+
+    #[test]
+    fn partial_updates_send_only_set_fields() {
+        let update = crate::TelescopeUpdateRequest::new()
+            .min_elevation(crate::units::Degrees(30.0))
+            .precondition(Precondition::Matches("\"v7\"".to_string()));
+        assert_eq!(
+            serde_json::to_string(&update).unwrap(),
+            r#"{"minElevation":30.0}"#
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Named connection profiles, loaded from `~/.config/lemonaid/config.toml`.
+//!
+//! ```toml
+//! default_profile = "dev"
+//!
+//! [profiles.dev]
+//! dev = true
+//! token_command = "pass show citra/dev"
+//! ground_station = "0a5f..."
+//! telescope = "91c2..."
+//!
+//! [profiles.prod]
+//! token_env = "CITRA_PROD_PAT"
+//! timeout_secs = 30
+//! ```
+//!
+//! A profile takes its token from exactly one of `token`, `token_env`,
+//! `token_file` or `token_command`. `base_url` overrides `dev`; with neither,
+//! the production API is used.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::{CitraClient, CitraClientBuilder, CommandToken, EnvToken, FileToken, LemonaidError};
+
+/// Name of the profile used when neither the caller nor the file picks one.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Environment variable selecting the profile for [`CitraClient::from_env`].
+pub const PROFILE_ENV: &str = "LEMONAID_PROFILE";
+
+/// Contents of a configuration file.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Profile used when none is named explicitly.
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Connection settings and defaults for one environment or account.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Use the hosted dev API instead of prod.
+    #[serde(default)]
+    pub dev: bool,
+    pub base_url: Option<String>,
+    /// The personal access token itself. Prefer one of the other token sources
+    /// in shared files.
+    pub token: Option<String>,
+    /// Environment variable holding the token.
+    pub token_env: Option<String>,
+    /// File holding the token, re-read when it changes.
+    pub token_file: Option<PathBuf>,
+    /// Shell command printing the token.
+    pub token_command: Option<String>,
+    /// Ground station id used by tools when none is given.
+    #[serde(rename = "ground_station")]
+    pub groundstation_id: Option<String>,
+    /// Telescope id used by tools when none is given.
+    #[serde(rename = "telescope")]
+    pub telescope_id: Option<String>,
+    /// Antenna id used by tools when none is given.
+    #[serde(rename = "antenna")]
+    pub antenna_id: Option<String>,
+    pub connect_timeout_secs: Option<f64>,
+    pub read_timeout_secs: Option<f64>,
+    pub timeout_secs: Option<f64>,
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/lemonaid/config.toml`, falling back to
+    /// `~/.config/lemonaid/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("lemonaid").join("config.toml"))
+    }
+
+    /// Loads the file at [`default_path`](Self::default_path).
+    pub fn load() -> Result<Config, LemonaidError> {
+        let path = Self::default_path().ok_or_else(|| {
+            LemonaidError::Config("cannot locate the configuration directory".to_string())
+        })?;
+        Self::from_path(path)
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Config, LemonaidError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|err| {
+            LemonaidError::Config(format!("cannot read {}: {}", path.display(), err))
+        })?;
+        text.parse().map_err(|err| match err {
+            LemonaidError::Config(message) => {
+                LemonaidError::Config(format!("{}: {}", path.display(), message))
+            }
+            other => other,
+        })
+    }
+
+    /// The named profile, or the default one if `name` is `None`.
+    pub fn profile(&self, name: Option<&str>) -> Result<&Profile, LemonaidError> {
+        let name = name
+            .or(self.default_profile.as_deref())
+            .unwrap_or(DEFAULT_PROFILE);
+        self.profiles.get(name).ok_or_else(|| {
+            LemonaidError::Config(format!("no profile named `{}` in the configuration", name))
+        })
+    }
+}
+
+impl FromStr for Config {
+    type Err = LemonaidError;
+
+    fn from_str(text: &str) -> Result<Config, LemonaidError> {
+        let config: Config =
+            toml::from_str(text).map_err(|err| LemonaidError::Config(err.to_string()))?;
+        for (name, profile) in &config.profiles {
+            profile.check().map_err(|message| {
+                LemonaidError::Config(format!("profile `{}`: {}", name, message))
+            })?;
+        }
+        Ok(config)
+    }
+}
+
+impl Profile {
+    fn check(&self) -> Result<(), String> {
+        let sources = [
+            self.token.is_some(),
+            self.token_env.is_some(),
+            self.token_file.is_some(),
+            self.token_command.is_some(),
+        ];
+        if sources.iter().filter(|set| **set).count() > 1 {
+            return Err(
+                "set only one of token, token_env, token_file and token_command".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether the profile names a token source.
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+            || self.token_env.is_some()
+            || self.token_file.is_some()
+            || self.token_command.is_some()
+    }
+
+    /// A client builder with this profile's endpoint, token and timeouts, for
+    /// further customization.
+    pub fn client_builder(&self) -> Result<CitraClientBuilder, LemonaidError> {
+        let mut builder = CitraClientBuilder::new(self.token.as_deref().unwrap_or_default());
+        if let Some(var) = &self.token_env {
+            builder = builder.auth(EnvToken::new(var));
+        } else if let Some(path) = &self.token_file {
+            builder = builder.auth(FileToken::new(path));
+        } else if let Some(command) = &self.token_command {
+            builder = builder.auth(CommandToken::new(command));
+        } else if self.token.is_none() {
+            return Err(LemonaidError::Config(
+                "profile has no token, token_env, token_file or token_command".to_string(),
+            ));
+        }
+
+        builder = builder.dev(self.dev);
+        if let Some(base_url) = &self.base_url {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(timeout) = seconds(self.connect_timeout_secs, "connect_timeout_secs")? {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = seconds(self.read_timeout_secs, "read_timeout_secs")? {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(timeout) = seconds(self.timeout_secs, "timeout_secs")? {
+            builder = builder.timeout(timeout);
+        }
+        Ok(builder)
+    }
+}
+
+fn seconds(value: Option<f64>, name: &str) -> Result<Option<Duration>, LemonaidError> {
+    value
+        .map(|secs| {
+            Duration::try_from_secs_f64(secs)
+                .map_err(|err| LemonaidError::Config(format!("invalid {}: {}", name, err)))
+        })
+        .transpose()
+}
+
+impl CitraClient {
+    /// Creates a client from a profile of the default configuration file.
+    pub fn from_profile(name: &str) -> Result<CitraClient, LemonaidError> {
+        Config::load()?
+            .profile(Some(name))?
+            .client_builder()?
+            .build()
+    }
+
+    /// Creates a client from the environment.
+    ///
+    /// If `LEMONAID_PROFILE` is set, that profile of the configuration file is
+    /// used. Otherwise the token is read from `CITRA_PAT`, and the API is chosen
+    /// by `CITRA_BASE_URL` or, if `CITRA_DEV` is `1` or `true`, the dev API.
+    pub fn from_env() -> Result<CitraClient, LemonaidError> {
+        if let Ok(name) = std::env::var(PROFILE_ENV) {
+            return Self::from_profile(&name);
+        }
+        let token = std::env::var("CITRA_PAT").map_err(|_| {
+            LemonaidError::Config(format!("neither {} nor CITRA_PAT is set", PROFILE_ENV))
+        })?;
+        let dev = std::env::var("CITRA_DEV")
+            .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        let mut builder = CitraClientBuilder::new(&token).dev(dev);
+        if let Ok(base_url) = std::env::var("CITRA_BASE_URL") {
+            builder = builder.base_url(&base_url);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+        default_profile = "dev"
+
+        [profiles.dev]
+        dev = true
+        token_command = "echo dev-token"
+        ground_station = "gs-1"
+
+        [profiles.prod]
+        token_env = "CITRA_PROD_PAT"
+        timeout_secs = 2.5
+    "#;
+
+    #[test]
+    fn picks_the_named_or_default_profile() {
+        let config: Config = EXAMPLE.parse().unwrap();
+        let dev = config.profile(None).unwrap();
+        assert!(dev.dev);
+        assert_eq!(dev.groundstation_id.as_deref(), Some("gs-1"));
+        let prod = config.profile(Some("prod")).unwrap();
+        assert_eq!(prod.timeout_secs, Some(2.5));
+        assert!(prod.client_builder().is_ok());
+        assert!(matches!(
+            config.profile(Some("staging")),
+            Err(LemonaidError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_ambiguous_token_sources() {
+        let text = "[profiles.default]\ntoken = \"abc\"\ntoken_env = \"CITRA_PAT\"\n";
+        let err = text.parse::<Config>().unwrap_err();
+        assert!(err.to_string().contains("profile `default`"));
+        let typo = "[profiles.default]\ntokn = \"abc\"\n";
+        assert!(typo.parse::<Config>().is_err());
+    }
+
+    #[test]
+    fn profile_without_token_cannot_build_a_client() {
+        let profile = Profile {
+            dev: true,
+            ..Profile::default()
+        };
+        assert!(!profile.has_token());
+        assert!(profile.client_builder().is_err());
+    }
+}
@@ -1,5 +1,8 @@
 use chrono::{DateTime, Utc};
-use serde::{Serialize, Deserialize};
+use serde::{Deserialize, Serialize};
+
+use crate::Groundstation;
+use crate::units::{Degrees, Km, MHz};
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -9,11 +12,55 @@ pub struct SatelliteAccessToGroundstationRequest {
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
     #[serde(rename = "minElevation")]
-    pub min_elevation_deg: f64,
+    pub min_elevation_deg: Degrees,
     #[serde(rename = "minDuration")]
     pub min_duration_minutes: f64,
-    pub min_frequency_mhz: Option<f64>,
-    pub max_frequency_mhz: Option<f64>
+    pub min_frequency_mhz: Option<MHz>,
+    pub max_frequency_mhz: Option<MHz>,
+}
+
+impl SatelliteAccessToGroundstationRequest {
+    /// All passes over the ground station in `[start, end]` above the horizon,
+    /// of any duration.
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use lemonaid::SatelliteAccessToGroundstationRequest;
+    /// use lemonaid::units::Degrees;
+    ///
+    /// let now = Utc::now();
+    /// let request = SatelliteAccessToGroundstationRequest::new("gs-1", now, now + Duration::hours(6))
+    ///     .min_elevation(Degrees(10.0))
+    ///     .min_duration_minutes(2.0);
+    /// ```
+    pub fn new(groundstation_id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        SatelliteAccessToGroundstationRequest {
+            groundstation_id: groundstation_id.to_string(),
+            start,
+            end,
+            min_elevation_deg: Degrees(0.0),
+            min_duration_minutes: 0.0,
+            min_frequency_mhz: None,
+            max_frequency_mhz: None,
+        }
+    }
+
+    pub fn min_elevation(mut self, elevation: Degrees) -> Self {
+        self.min_elevation_deg = elevation;
+        self
+    }
+
+    pub fn min_duration_minutes(mut self, minutes: f64) -> Self {
+        self.min_duration_minutes = minutes;
+        self
+    }
+
+    /// Only satellites with a frequency range overlapping `[min_mhz, max_mhz]`.
+    pub fn frequency_band(mut self, min_mhz: MHz, max_mhz: MHz) -> Self {
+        self.min_frequency_mhz = Some(min_mhz);
+        self.max_frequency_mhz = Some(max_mhz);
+        self
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -22,15 +69,15 @@ pub struct TrackingParameters {
     #[serde(rename = "epoch")]
     pub time: DateTime<Utc>,
     #[serde(rename = "azimuth")]
-    pub azimuth_deg: f64,
+    pub azimuth_deg: Degrees,
     #[serde(rename = "elevation")]
-    pub elevation_deg: f64,
+    pub elevation_deg: Degrees,
     #[serde(rename = "azimuthRate")]
     pub azimuth_rate_deg_s: Option<f64>,
     #[serde(rename = "elevationRate")]
     pub elevation_rate_deg_s: Option<f64>,
     #[serde(rename = "range")]
-    pub range_km: Option<f64>,
+    pub range_km: Option<Km>,
     #[serde(rename = "rangeRate")]
     pub range_rate_km_s: Option<f64>,
 }
@@ -50,11 +97,11 @@ pub struct HorizonAccess {
     pub duration_minutes: f64,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum SensorFrame {
     TEME,
-    J2000
+    J2000,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -63,18 +110,46 @@ pub struct FOVAccessRequest {
     #[serde(rename = "epoch")]
     pub time: DateTime<Utc>,
     #[serde(rename = "rightAscension")]
-    pub right_ascension_deg: f64,
+    pub right_ascension_deg: Degrees,
     #[serde(rename = "declination")]
-    pub declination_deg: f64,
+    pub declination_deg: Degrees,
     #[serde(rename = "fieldOfView")]
-    pub field_of_view_deg: f64,
+    pub field_of_view_deg: Degrees,
     #[serde(rename = "sensorLatitude")]
-    pub sensor_latitude_deg: f64,
+    pub sensor_latitude_deg: Degrees,
     #[serde(rename = "sensorLongitude")]
-    pub sensor_longitude_deg: f64,
+    pub sensor_longitude_deg: Degrees,
     #[serde(rename = "sensorAltitude")]
-    pub sensor_altitude_km: f64,
-    pub sensor_frame: SensorFrame
+    pub sensor_altitude_km: Km,
+    pub sensor_frame: SensorFrame,
+}
+
+impl FOVAccessRequest {
+    /// A sensor at the ground station pointed at `(ra, dec)` in J2000.
+    pub fn new(
+        groundstation: &Groundstation,
+        time: DateTime<Utc>,
+        right_ascension: Degrees,
+        declination: Degrees,
+        field_of_view: Degrees,
+    ) -> Self {
+        FOVAccessRequest {
+            time,
+            right_ascension_deg: right_ascension,
+            declination_deg: declination,
+            field_of_view_deg: field_of_view,
+            sensor_latitude_deg: groundstation.latitude,
+            sensor_longitude_deg: groundstation.longitude,
+            sensor_altitude_km: groundstation.altitude_km(),
+            sensor_frame: SensorFrame::J2000,
+        }
+    }
+
+    /// Frame of the right ascension and declination.
+    pub fn frame(mut self, frame: SensorFrame) -> Self {
+        self.sensor_frame = frame;
+        self
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -84,7 +159,7 @@ pub struct FOVAccessResponse {
     #[serde(rename = "name")]
     pub satellite_name: Option<String>,
     #[serde(rename = "rightAscension")]
-    pub right_ascension_deg: f64,
+    pub right_ascension_deg: Degrees,
     #[serde(rename = "declination")]
-    pub declination_deg: f64
+    pub declination_deg: Degrees,
 }
@@ -1,5 +1,8 @@
 use chrono::{DateTime, Utc};
-use serde::{Serialize, Deserialize};
+use serde::{Deserialize, Serialize};
+
+use crate::Precondition;
+use crate::units::{Degrees, Hz};
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -16,17 +19,221 @@ pub struct Antenna {
     pub last_connected_at: Option<DateTime<Utc>>,
     pub name: String,
     #[serde(rename = "minFrequency")]
-    pub min_frequency_hz: f64,
+    pub min_frequency_hz: Hz,
+    #[serde(rename = "maxFrequency")]
+    pub max_frequency_hz: Hz,
+    #[serde(rename = "minElevation")]
+    pub min_elevation_deg: Degrees,
+    #[serde(rename = "maxSlewRate")]
+    pub max_slew_rate_deg_per_sec: f64,
+    #[serde(rename = "homeAzimuth")]
+    pub home_azimuth_deg: Degrees,
+    #[serde(rename = "homeElevation")]
+    pub home_elevation_deg: Degrees,
+    #[serde(rename = "halfPowerBeamWidth")]
+    pub half_power_beam_width_deg: Degrees,
+}
+
+/// User-settable antenna fields, for [`create_antenna`](crate::CitraClient::create_antenna).
+///
+/// ```
+/// use lemonaid::AntennaCreateRequest;
+/// use lemonaid::units::{Degrees, MHz};
+///
+/// let antenna = AntennaCreateRequest::new("UHF yagi", MHz(430.0).into(), MHz(440.0).into())
+///     .groundstation("gs-1")
+///     .half_power_beam_width(Degrees(30.0));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AntennaCreateRequest {
+    pub name: String,
+    pub user_group_id: Option<String>,
+    #[serde(rename = "groundStationId")]
+    pub groundstation_id: Option<String>,
+    pub satellite_id: Option<String>,
+    #[serde(rename = "minFrequency")]
+    pub min_frequency_hz: Hz,
     #[serde(rename = "maxFrequency")]
-    pub max_frequency_hz: f64,
+    pub max_frequency_hz: Hz,
     #[serde(rename = "minElevation")]
-    pub min_elevation_deg: f64,
+    pub min_elevation_deg: Degrees,
     #[serde(rename = "maxSlewRate")]
     pub max_slew_rate_deg_per_sec: f64,
     #[serde(rename = "homeAzimuth")]
-    pub home_azimuth_deg: f64,
+    pub home_azimuth_deg: Degrees,
     #[serde(rename = "homeElevation")]
-    pub home_elevation_deg: f64,
+    pub home_elevation_deg: Degrees,
     #[serde(rename = "halfPowerBeamWidth")]
-    pub half_power_beam_width_deg: f64
-}
\ No newline at end of file
+    pub half_power_beam_width_deg: Degrees,
+}
+
+/// A small tracking yagi: 10° elevation mask, 5°/s slew, 30° beam width, parked
+/// at the zenith. The frequency range is empty and must be set.
+impl Default for AntennaCreateRequest {
+    fn default() -> Self {
+        AntennaCreateRequest {
+            name: String::new(),
+            user_group_id: None,
+            groundstation_id: None,
+            satellite_id: None,
+            min_frequency_hz: Hz(0.0),
+            max_frequency_hz: Hz(0.0),
+            min_elevation_deg: Degrees(10.0),
+            max_slew_rate_deg_per_sec: 5.0,
+            home_azimuth_deg: Degrees(0.0),
+            home_elevation_deg: Degrees(90.0),
+            half_power_beam_width_deg: Degrees(30.0),
+        }
+    }
+}
+
+impl AntennaCreateRequest {
+    pub fn new(name: &str, min_frequency: Hz, max_frequency: Hz) -> Self {
+        AntennaCreateRequest {
+            name: name.to_string(),
+            min_frequency_hz: min_frequency,
+            max_frequency_hz: max_frequency,
+            ..Self::default()
+        }
+    }
+
+    pub fn groundstation(mut self, groundstation_id: &str) -> Self {
+        self.groundstation_id = Some(groundstation_id.to_string());
+        self
+    }
+
+    pub fn user_group(mut self, user_group_id: &str) -> Self {
+        self.user_group_id = Some(user_group_id.to_string());
+        self
+    }
+
+    pub fn min_elevation(mut self, elevation: Degrees) -> Self {
+        self.min_elevation_deg = elevation;
+        self
+    }
+
+    pub fn max_slew_rate(mut self, deg_per_sec: f64) -> Self {
+        self.max_slew_rate_deg_per_sec = deg_per_sec;
+        self
+    }
+
+    /// Park position between tasks.
+    pub fn home(mut self, azimuth: Degrees, elevation: Degrees) -> Self {
+        self.home_azimuth_deg = azimuth;
+        self.home_elevation_deg = elevation;
+        self
+    }
+
+    pub fn half_power_beam_width(mut self, beam_width: Degrees) -> Self {
+        self.half_power_beam_width_deg = beam_width;
+        self
+    }
+}
+
+/// Copies the settings of an existing antenna.
+impl From<&Antenna> for AntennaCreateRequest {
+    fn from(antenna: &Antenna) -> Self {
+        AntennaCreateRequest {
+            name: antenna.name.clone(),
+            user_group_id: antenna.user_group_id.clone(),
+            groundstation_id: antenna.groundstation_id.clone(),
+            satellite_id: antenna.satellite_id.clone(),
+            min_frequency_hz: antenna.min_frequency_hz,
+            max_frequency_hz: antenna.max_frequency_hz,
+            min_elevation_deg: antenna.min_elevation_deg,
+            max_slew_rate_deg_per_sec: antenna.max_slew_rate_deg_per_sec,
+            home_azimuth_deg: antenna.home_azimuth_deg,
+            home_elevation_deg: antenna.home_elevation_deg,
+            half_power_beam_width_deg: antenna.half_power_beam_width_deg,
+        }
+    }
+}
+
+/// Partial antenna update for [`patch_antenna`](crate::CitraClient::patch_antenna).
+/// Only the fields that are set are sent, so concurrent edits of other fields
+/// are not overwritten.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AntennaUpdateRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_group_id: Option<String>,
+    #[serde(rename = "groundStationId", skip_serializing_if = "Option::is_none")]
+    pub groundstation_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub satellite_id: Option<String>,
+    #[serde(rename = "minFrequency", skip_serializing_if = "Option::is_none")]
+    pub min_frequency_hz: Option<Hz>,
+    #[serde(rename = "maxFrequency", skip_serializing_if = "Option::is_none")]
+    pub max_frequency_hz: Option<Hz>,
+    #[serde(rename = "minElevation", skip_serializing_if = "Option::is_none")]
+    pub min_elevation_deg: Option<Degrees>,
+    #[serde(rename = "maxSlewRate", skip_serializing_if = "Option::is_none")]
+    pub max_slew_rate_deg_per_sec: Option<f64>,
+    #[serde(rename = "homeAzimuth", skip_serializing_if = "Option::is_none")]
+    pub home_azimuth_deg: Option<Degrees>,
+    #[serde(rename = "homeElevation", skip_serializing_if = "Option::is_none")]
+    pub home_elevation_deg: Option<Degrees>,
+    #[serde(rename = "halfPowerBeamWidth", skip_serializing_if = "Option::is_none")]
+    pub half_power_beam_width_deg: Option<Degrees>,
+    /// Sent as a request header rather than in the body.
+    #[serde(skip)]
+    pub precondition: Option<Precondition>,
+}
+
+impl AntennaUpdateRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn groundstation(mut self, groundstation_id: &str) -> Self {
+        self.groundstation_id = Some(groundstation_id.to_string());
+        self
+    }
+
+    pub fn user_group(mut self, user_group_id: &str) -> Self {
+        self.user_group_id = Some(user_group_id.to_string());
+        self
+    }
+
+    pub fn frequency_range(mut self, min_frequency: Hz, max_frequency: Hz) -> Self {
+        self.min_frequency_hz = Some(min_frequency);
+        self.max_frequency_hz = Some(max_frequency);
+        self
+    }
+
+    pub fn min_elevation(mut self, elevation: Degrees) -> Self {
+        self.min_elevation_deg = Some(elevation);
+        self
+    }
+
+    pub fn max_slew_rate(mut self, deg_per_sec: f64) -> Self {
+        self.max_slew_rate_deg_per_sec = Some(deg_per_sec);
+        self
+    }
+
+    /// Park position between tasks.
+    pub fn home(mut self, azimuth: Degrees, elevation: Degrees) -> Self {
+        self.home_azimuth_deg = Some(azimuth);
+        self.home_elevation_deg = Some(elevation);
+        self
+    }
+
+    pub fn half_power_beam_width(mut self, beam_width: Degrees) -> Self {
+        self.half_power_beam_width_deg = Some(beam_width);
+        self
+    }
+
+    /// Only applies the update if the antenna still meets `precondition`.
+    pub fn precondition(mut self, precondition: Precondition) -> Self {
+        self.precondition = Some(precondition);
+        self
+    }
+}
@@ -0,0 +1,717 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::ElsetError;
+
+/// An orbital element set as stored by the API, given as a TLE, an OMM or both.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementSet {
+    pub id: String,
+    pub satellite_id: String,
+    pub epoch: DateTime<Utc>,
+    pub source: Option<String>,
+    #[serde(rename = "creationEpoch")]
+    pub created_at: DateTime<Utc>,
+    pub tle: Option<Tle>,
+    pub omm: Option<Omm>,
+}
+
+impl ElementSet {
+    /// SGP4 mean elements of this set, preferring the OMM over the TLE.
+    pub fn mean_elements(&self) -> Result<Omm, ElsetError> {
+        match (&self.omm, &self.tle) {
+            (Some(omm), _) => Ok(omm.clone()),
+            (None, Some(tle)) => tle.to_omm(),
+            (None, None) => Err(ElsetError::MissingField("tle or omm".to_string())),
+        }
+    }
+}
+
+/// An element set to upload, e.g. from our own orbit determination.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementSetUploadRequest {
+    pub satellite_id: String,
+    pub source: Option<String>,
+    pub tle: Option<Tle>,
+    pub omm: Option<Omm>,
+}
+
+impl ElementSetUploadRequest {
+    pub fn from_tle(satellite_id: &str, tle: Tle) -> Self {
+        ElementSetUploadRequest {
+            satellite_id: satellite_id.to_string(),
+            source: None,
+            tle: Some(tle),
+            omm: None,
+        }
+    }
+
+    pub fn from_omm(satellite_id: &str, omm: Omm) -> Self {
+        ElementSetUploadRequest {
+            satellite_id: satellite_id.to_string(),
+            source: None,
+            tle: None,
+            omm: Some(omm),
+        }
+    }
+
+    /// Checks only the TLE checksums and OMM value ranges. Uploads are checked
+    /// with [`Validate`](crate::Validate), which also covers the satellite id.
+    pub fn check_elements(&self) -> Result<(), ElsetError> {
+        if self.tle.is_none() && self.omm.is_none() {
+            return Err(ElsetError::MissingField("tle or omm".to_string()));
+        }
+        if let Some(tle) = &self.tle {
+            tle.validate()?;
+        }
+        if let Some(omm) = &self.omm {
+            omm.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// A NORAD two-line element set.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Tle {
+    pub line1: String,
+    pub line2: String,
+}
+
+impl Tle {
+    /// Builds a TLE from its two lines, validating format and checksums.
+    pub fn new(line1: &str, line2: &str) -> Result<Self, ElsetError> {
+        let tle = Tle {
+            line1: line1.trim_end().to_string(),
+            line2: line2.trim_end().to_string(),
+        };
+        tle.validate()?;
+        Ok(tle)
+    }
+
+    /// Parses a TLE from text with two lines, or three lines where the first is
+    /// the object name.
+    pub fn parse(text: &str) -> Result<Self, ElsetError> {
+        let lines: Vec<&str> = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        match lines.as_slice() {
+            [line1, line2] | [_, line1, line2] => Tle::new(line1, line2),
+            _ => Err(ElsetError::Format(format!(
+                "expected 2 or 3 lines, found {}",
+                lines.len()
+            ))),
+        }
+    }
+
+    /// Modulo-10 checksum over the first 68 columns: digits count their value,
+    /// minus signs count one.
+    pub fn checksum(line: &str) -> u32 {
+        line.chars()
+            .take(68)
+            .map(|c| match c {
+                '0'..='9' => c as u32 - '0' as u32,
+                '-' => 1,
+                _ => 0,
+            })
+            .sum::<u32>()
+            % 10
+    }
+
+    pub fn validate(&self) -> Result<(), ElsetError> {
+        for (number, line) in [(1, &self.line1), (2, &self.line2)] {
+            if line.len() != 69 || !line.is_ascii() {
+                return Err(ElsetError::Format(format!(
+                    "line {} must be 69 ASCII characters, found {}",
+                    number,
+                    line.chars().count()
+                )));
+            }
+            if !line.starts_with(&format!("{} ", number)) {
+                return Err(ElsetError::Format(format!(
+                    "line {} must start with `{} `",
+                    number, number
+                )));
+            }
+            let found = line[68..69].parse::<u32>().map_err(|_| {
+                ElsetError::Format(format!("line {} has no checksum digit", number))
+            })?;
+            let expected = Tle::checksum(line);
+            if found != expected {
+                return Err(ElsetError::Checksum {
+                    line: number,
+                    expected,
+                    found,
+                });
+            }
+        }
+        if self.line1[2..7] != self.line2[2..7] {
+            return Err(ElsetError::Format(
+                "catalog numbers of line 1 and line 2 differ".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn norad_id(&self) -> Result<u32, ElsetError> {
+        let field = self.line1.get(2..7).ok_or_else(|| {
+            ElsetError::Format("line 1 is too short for a catalog number".to_string())
+        })?;
+        parse_catalog_number(field)
+    }
+
+    /// Converts the fixed-column TLE fields into OMM mean elements.
+    pub fn to_omm(&self) -> Result<Omm, ElsetError> {
+        self.validate()?;
+        let l1 = &self.line1;
+        let l2 = &self.line2;
+
+        let year = parse_field::<i32>("epoch year", &l1[18..20])?;
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+        let day = parse_field::<f64>("epoch day", &l1[20..32])?;
+        let epoch = Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap()
+            + Duration::microseconds(((day - 1.0) * 86_400_000_000.0).round() as i64);
+
+        let designator = l1[9..17].trim();
+        let object_id = if designator.len() >= 5 {
+            let launch_year = parse_field::<i32>("international designator", &designator[0..2])?;
+            let launch_year = if launch_year < 57 {
+                2000 + launch_year
+            } else {
+                1900 + launch_year
+            };
+            Some(format!("{}-{}", launch_year, &designator[2..]))
+        } else {
+            None
+        };
+
+        Ok(Omm {
+            object_name: None,
+            object_id,
+            norad_cat_id: Some(self.norad_id()?),
+            classification_type: Some(l1[7..8].to_string()),
+            epoch,
+            mean_motion_rev_per_day: parse_field("mean motion", &l2[52..63])?,
+            eccentricity: parse_field::<f64>("eccentricity", &format!("0.{}", l2[26..33].trim()))?,
+            inclination_deg: parse_field("inclination", &l2[8..16])?,
+            ra_of_asc_node_deg: parse_field("right ascension of ascending node", &l2[17..25])?,
+            arg_of_pericenter_deg: parse_field("argument of pericenter", &l2[34..42])?,
+            mean_anomaly_deg: parse_field("mean anomaly", &l2[43..51])?,
+            ephemeris_type: parse_field::<u8>("ephemeris type", &l1[62..63]).unwrap_or(0),
+            element_set_no: parse_field::<u32>("element set number", &l1[64..68]).unwrap_or(0),
+            rev_at_epoch: parse_field::<u32>("revolution number", &l2[63..68]).unwrap_or(0),
+            bstar: parse_implied_decimal("bstar", &l1[53..61])?,
+            mean_motion_dot: parse_field("mean motion derivative", &l1[33..43])?,
+            mean_motion_ddot: parse_implied_decimal("mean motion second derivative", &l1[44..52])?,
+        })
+    }
+}
+
+impl fmt::Display for Tle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}", self.line1, self.line2)
+    }
+}
+
+/// CCSDS Orbit Mean-elements Message with SGP4 mean elements, as published in
+/// JSON, KVN or XML form.
+///
+/// `mean_motion_dot` and `mean_motion_ddot` follow the TLE convention of holding
+/// the first derivative divided by two and the second divided by six.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(
+    rename_all = "SCREAMING_SNAKE_CASE",
+    try_from = "BTreeMap<String, serde_json::Value>"
+)]
+pub struct Omm {
+    pub object_name: Option<String>,
+    /// International (COSPAR) designator, e.g. `1998-067A`.
+    pub object_id: Option<String>,
+    pub norad_cat_id: Option<u32>,
+    pub classification_type: Option<String>,
+    #[serde(serialize_with = "serialize_epoch")]
+    pub epoch: DateTime<Utc>,
+    #[serde(rename = "MEAN_MOTION")]
+    pub mean_motion_rev_per_day: f64,
+    pub eccentricity: f64,
+    #[serde(rename = "INCLINATION")]
+    pub inclination_deg: f64,
+    #[serde(rename = "RA_OF_ASC_NODE")]
+    pub ra_of_asc_node_deg: f64,
+    #[serde(rename = "ARG_OF_PERICENTER")]
+    pub arg_of_pericenter_deg: f64,
+    #[serde(rename = "MEAN_ANOMALY")]
+    pub mean_anomaly_deg: f64,
+    pub ephemeris_type: u8,
+    pub element_set_no: u32,
+    pub rev_at_epoch: u32,
+    pub bstar: f64,
+    pub mean_motion_dot: f64,
+    pub mean_motion_ddot: f64,
+}
+
+impl Omm {
+    /// Parses an OMM in any of the JSON, XML or KVN encodings.
+    pub fn parse(text: &str) -> Result<Self, ElsetError> {
+        match text.trim_start().chars().next() {
+            Some('{') | Some('[') => Omm::from_json(text),
+            Some('<') => Omm::from_xml(text),
+            _ => Omm::from_kvn(text),
+        }
+    }
+
+    /// Parses a JSON OMM object, or the first object of a JSON array as served
+    /// by CelesTrak.
+    pub fn from_json(text: &str) -> Result<Self, ElsetError> {
+        let value: serde_json::Value =
+            serde_json::from_str(text).map_err(|err| ElsetError::Format(err.to_string()))?;
+        let object = match value {
+            serde_json::Value::Array(items) => items
+                .into_iter()
+                .next()
+                .ok_or_else(|| ElsetError::Format("empty OMM array".to_string()))?,
+            other => other,
+        };
+        let fields: BTreeMap<String, serde_json::Value> =
+            serde_json::from_value(object).map_err(|err| ElsetError::Format(err.to_string()))?;
+        Omm::try_from(fields)
+    }
+
+    /// Parses the `KEY = value` encoding. Comments, header keys and `[unit]`
+    /// suffixes are ignored.
+    pub fn from_kvn(text: &str) -> Result<Self, ElsetError> {
+        let mut fields = BTreeMap::new();
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = match value.find('[') {
+                Some(unit) => &value[..unit],
+                None => value,
+            };
+            fields.insert(
+                key.trim().to_uppercase(),
+                serde_json::Value::String(value.trim().to_string()),
+            );
+        }
+        Omm::try_from(fields)
+    }
+
+    /// Parses the XML encoding by collecting the text of all leaf elements.
+    pub fn from_xml(text: &str) -> Result<Self, ElsetError> {
+        use quick_xml::events::Event;
+
+        let mut reader = quick_xml::Reader::from_str(text);
+        reader.config_mut().trim_text(true);
+        let mut fields = BTreeMap::new();
+        let mut current: Option<String> = None;
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(start)) => {
+                    current =
+                        Some(String::from_utf8_lossy(start.local_name().as_ref()).to_uppercase());
+                }
+                Ok(Event::Text(content)) => {
+                    if let Some(key) = current.take() {
+                        let value = content
+                            .unescape()
+                            .map_err(|err| ElsetError::Format(err.to_string()))?;
+                        fields.insert(key, serde_json::Value::String(value.trim().to_string()));
+                    }
+                }
+                Ok(Event::End(_)) => current = None,
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(err) => return Err(ElsetError::Format(err.to_string())),
+            }
+        }
+        Omm::try_from(fields)
+    }
+
+    /// Checks that the elements describe a bound SGP4 orbit.
+    pub fn validate(&self) -> Result<(), ElsetError> {
+        let invalid = |field: &str, message: &str| {
+            Err(ElsetError::InvalidValue {
+                field: field.to_string(),
+                message: message.to_string(),
+            })
+        };
+        if !self.mean_motion_rev_per_day.is_finite() || self.mean_motion_rev_per_day <= 0.0 {
+            return invalid("MEAN_MOTION", "must be positive");
+        }
+        if !(0.0..1.0).contains(&self.eccentricity) {
+            return invalid("ECCENTRICITY", "must be in [0, 1)");
+        }
+        if !(0.0..=180.0).contains(&self.inclination_deg) {
+            return invalid("INCLINATION", "must be in [0, 180] degrees");
+        }
+        for (field, value) in [
+            ("RA_OF_ASC_NODE", self.ra_of_asc_node_deg),
+            ("ARG_OF_PERICENTER", self.arg_of_pericenter_deg),
+            ("MEAN_ANOMALY", self.mean_anomaly_deg),
+        ] {
+            if !(0.0..360.0).contains(&value) {
+                return invalid(field, "must be in [0, 360) degrees");
+            }
+        }
+        if !self.bstar.is_finite()
+            || !self.mean_motion_dot.is_finite()
+            || !self.mean_motion_ddot.is_finite()
+        {
+            return invalid("BSTAR", "drag terms must be finite");
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<BTreeMap<String, serde_json::Value>> for Omm {
+    type Error = ElsetError;
+
+    fn try_from(fields: BTreeMap<String, serde_json::Value>) -> Result<Self, Self::Error> {
+        // values may be JSON numbers or strings depending on the encoding
+        let text = |key: &str| -> Option<String> {
+            match fields.get(key)? {
+                serde_json::Value::String(s) if s.is_empty() => None,
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Null => None,
+                other => Some(other.to_string()),
+            }
+        };
+        let required =
+            |key: &str| text(key).ok_or_else(|| ElsetError::MissingField(key.to_string()));
+
+        if let Some(frame) = text("REF_FRAME")
+            && frame != "TEME"
+        {
+            return Err(ElsetError::InvalidValue {
+                field: "REF_FRAME".to_string(),
+                message: format!("SGP4 elements must be in TEME, found {}", frame),
+            });
+        }
+        if let Some(theory) = text("MEAN_ELEMENT_THEORY")
+            && !theory.starts_with("SGP4")
+        {
+            return Err(ElsetError::InvalidValue {
+                field: "MEAN_ELEMENT_THEORY".to_string(),
+                message: format!("expected SGP4, found {}", theory),
+            });
+        }
+
+        Ok(Omm {
+            object_name: text("OBJECT_NAME"),
+            object_id: text("OBJECT_ID"),
+            norad_cat_id: text("NORAD_CAT_ID")
+                .map(|id| parse_catalog_number(&id))
+                .transpose()?,
+            classification_type: text("CLASSIFICATION_TYPE"),
+            epoch: parse_epoch(&required("EPOCH")?)?,
+            mean_motion_rev_per_day: parse_field("MEAN_MOTION", &required("MEAN_MOTION")?)?,
+            eccentricity: parse_field("ECCENTRICITY", &required("ECCENTRICITY")?)?,
+            inclination_deg: parse_field("INCLINATION", &required("INCLINATION")?)?,
+            ra_of_asc_node_deg: parse_field("RA_OF_ASC_NODE", &required("RA_OF_ASC_NODE")?)?,
+            arg_of_pericenter_deg: parse_field(
+                "ARG_OF_PERICENTER",
+                &required("ARG_OF_PERICENTER")?,
+            )?,
+            mean_anomaly_deg: parse_field("MEAN_ANOMALY", &required("MEAN_ANOMALY")?)?,
+            ephemeris_type: text("EPHEMERIS_TYPE")
+                .map(|v| parse_field("EPHEMERIS_TYPE", &v))
+                .transpose()?
+                .unwrap_or(0),
+            element_set_no: text("ELEMENT_SET_NO")
+                .map(|v| parse_field("ELEMENT_SET_NO", &v))
+                .transpose()?
+                .unwrap_or(0),
+            rev_at_epoch: text("REV_AT_EPOCH")
+                .map(|v| parse_field("REV_AT_EPOCH", &v))
+                .transpose()?
+                .unwrap_or(0),
+            bstar: text("BSTAR")
+                .map(|v| parse_field("BSTAR", &v))
+                .transpose()?
+                .unwrap_or(0.0),
+            mean_motion_dot: text("MEAN_MOTION_DOT")
+                .map(|v| parse_field("MEAN_MOTION_DOT", &v))
+                .transpose()?
+                .unwrap_or(0.0),
+            mean_motion_ddot: text("MEAN_MOTION_DDOT")
+                .map(|v| parse_field("MEAN_MOTION_DDOT", &v))
+                .transpose()?
+                .unwrap_or(0.0),
+        })
+    }
+}
+
+/// OMM epochs are written without a time zone suffix and are always UTC.
+fn serialize_epoch<S: Serializer>(epoch: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&epoch.format("%Y-%m-%dT%H:%M:%S%.6f").to_string())
+}
+
+/// Accepts calendar (`2024-03-01T12:00:00.000`) and day-of-year
+/// (`2024-061T12:00:00.000`) epochs, with or without a trailing `Z`.
+fn parse_epoch(value: &str) -> Result<DateTime<Utc>, ElsetError> {
+    let value = value.trim().trim_end_matches('Z');
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%jT%H:%M:%S%.f"))
+        .map(|epoch| epoch.and_utc())
+        .map_err(|err| ElsetError::InvalidValue {
+            field: "EPOCH".to_string(),
+            message: err.to_string(),
+        })
+}
+
+fn parse_field<T: std::str::FromStr>(field: &str, value: &str) -> Result<T, ElsetError> {
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| ElsetError::InvalidValue {
+            field: field.to_string(),
+            message: format!("cannot parse `{}`", value.trim()),
+        })
+}
+
+/// Parses TLE fields with an implied leading decimal point and exponent,
+/// e.g. ` 28098-4` for 0.28098e-4.
+fn parse_implied_decimal(field: &str, value: &str) -> Result<f64, ElsetError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(0.0);
+    }
+    let (sign, digits) = match value.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (mantissa, exponent) = match digits.rfind(['-', '+']) {
+        Some(split) if split > 0 => (&digits[..split], &digits[split..]),
+        _ => (digits, "0"),
+    };
+    let mantissa: f64 = parse_field(field, &format!("0.{}", mantissa))?;
+    let exponent: i32 = parse_field(field, exponent)?;
+    Ok(sign * mantissa * 10f64.powi(exponent))
+}
+
+/// Catalog numbers are five digits, or Alpha-5 with a leading letter standing
+/// for 10-33 (skipping I and O).
+fn parse_catalog_number(value: &str) -> Result<u32, ElsetError> {
+    let value = value.trim();
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_uppercase() && !matches!(first, 'I' | 'O') => {
+            let mut prefix = first as u32 - 'A' as u32 + 10;
+            if first > 'I' {
+                prefix -= 1;
+            }
+            if first > 'O' {
+                prefix -= 1;
+            }
+            let rest: u32 = parse_field("catalog number", chars.as_str())?;
+            Ok(prefix * 10_000 + rest)
+        }
+        _ => parse_field("catalog number", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISS: (&str, &str) = (
+        "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+        "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+    );
+
+    /// Replaces the catalog number on both lines and fixes up the checksums.
+    fn renumber(catalog_number: &str) -> (String, String) {
+        let fix = |line: &str| {
+            let line = format!("{}{}{}", &line[..2], catalog_number, &line[7..68]);
+            let checksum = Tle::checksum(&line);
+            format!("{}{}", line, checksum)
+        };
+        (fix(ISS.0), fix(ISS.1))
+    }
+
+    #[test]
+    fn tles_are_checked_for_checksums_and_layout() {
+        assert!(Tle::new(ISS.0, ISS.1).is_ok());
+        assert!(Tle::parse(&format!("ISS (ZARYA)\n{}\n{}\n", ISS.0, ISS.1)).is_ok());
+
+        let bad_checksum = format!("{}8", &ISS.0[..68]);
+        assert_eq!(
+            Tle::new(&bad_checksum, ISS.1),
+            Err(ElsetError::Checksum {
+                line: 1,
+                expected: 7,
+                found: 8,
+            })
+        );
+        assert!(matches!(
+            Tle::new(&ISS.0[..60], ISS.1),
+            Err(ElsetError::Format(_))
+        ));
+        assert!(matches!(Tle::new(ISS.1, ISS.0), Err(ElsetError::Format(_))));
+        let (_, other) = renumber("25545");
+        assert!(matches!(
+            Tle::new(ISS.0, &other),
+            Err(ElsetError::Format(_))
+        ));
+        assert!(matches!(Tle::parse(ISS.0), Err(ElsetError::Format(_))));
+    }
+
+    #[test]
+    fn alpha5_catalog_numbers_skip_i_and_o() {
+        assert_eq!(Tle::new(ISS.0, ISS.1).unwrap().norad_id(), Ok(25544));
+        for (alpha5, number) in [("A0001", 100_001), ("J5544", 185_544), ("Z9999", 339_999)] {
+            let (line1, line2) = renumber(alpha5);
+            assert_eq!(Tle::new(&line1, &line2).unwrap().norad_id(), Ok(number));
+        }
+        let (line1, line2) = renumber("I0001");
+        assert!(matches!(
+            Tle::new(&line1, &line2).unwrap().norad_id(),
+            Err(ElsetError::InvalidValue { .. })
+        ));
+
+        // decoded or built by hand, so not checked yet
+        let short = Tle {
+            line1: "1 25".to_string(),
+            line2: ISS.1.to_string(),
+        };
+        assert!(matches!(short.norad_id(), Err(ElsetError::Format(_))));
+        let non_ascii = Tle {
+            line1: "1 2554é".to_string(),
+            line2: ISS.1.to_string(),
+        };
+        assert!(non_ascii.norad_id().is_err());
+    }
+
+    #[test]
+    fn implied_decimal_fields_carry_their_exponent() {
+        let close = |value: f64, expected: f64| (value - expected).abs() <= 1e-12 * expected.abs();
+        let value = |text| parse_implied_decimal("bstar", text).unwrap();
+        assert!(close(value(" 28098-4"), 0.28098e-4));
+        assert!(close(value("-11606-4"), -0.11606e-4));
+        assert!(close(value("+12345+1"), 1.2345));
+        assert_eq!(value(" 00000-0"), 0.0);
+        assert_eq!(value("        "), 0.0);
+        assert!(parse_implied_decimal("bstar", " 2809x-4").is_err());
+
+        let omm = Tle::new(ISS.0, ISS.1).unwrap().to_omm().unwrap();
+        assert!(close(omm.bstar, -0.11606e-4));
+        assert_eq!(omm.mean_motion_dot, -0.00002182);
+        assert_eq!(omm.mean_motion_ddot, 0.0);
+        assert_eq!(omm.object_id.as_deref(), Some("1998-067A"));
+        assert_eq!(
+            omm.epoch.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            "2008-09-20T12:25:40"
+        );
+    }
+
+    const OMM_JSON: &str = r#"[{
+        "OBJECT_NAME": "ISS (ZARYA)", "OBJECT_ID": "1998-067A", "EPOCH": "2008-09-20T12:25:40.104192",
+        "MEAN_MOTION": 15.72125391, "ECCENTRICITY": 0.0006703, "INCLINATION": 51.6416,
+        "RA_OF_ASC_NODE": 247.4627, "ARG_OF_PERICENTER": 130.536, "MEAN_ANOMALY": 325.0288,
+        "EPHEMERIS_TYPE": 0, "CLASSIFICATION_TYPE": "U", "NORAD_CAT_ID": 25544,
+        "ELEMENT_SET_NO": 292, "REV_AT_EPOCH": 56353, "BSTAR": -1.1606e-5,
+        "MEAN_MOTION_DOT": -2.182e-5, "MEAN_MOTION_DDOT": 0
+    }]"#;
+
+    const OMM_KVN: &str = "CCSDS_OMM_VERS = 2.0
+COMMENT from a test
+OBJECT_NAME = ISS (ZARYA)
+OBJECT_ID = 1998-067A
+REF_FRAME = TEME
+MEAN_ELEMENT_THEORY = SGP4
+EPOCH = 2008-264T12:25:40.104192
+MEAN_MOTION = 15.72125391 [rev/day]
+ECCENTRICITY = 0.0006703
+INCLINATION = 51.6416 [deg]
+RA_OF_ASC_NODE = 247.4627 [deg]
+ARG_OF_PERICENTER = 130.536 [deg]
+MEAN_ANOMALY = 325.0288 [deg]
+EPHEMERIS_TYPE = 0
+CLASSIFICATION_TYPE = U
+NORAD_CAT_ID = 25544
+ELEMENT_SET_NO = 292
+REV_AT_EPOCH = 56353
+BSTAR = -1.1606e-5
+MEAN_MOTION_DOT = -2.182e-5
+MEAN_MOTION_DDOT = 0
+";
+
+    const OMM_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ndm><omm><body><segment>
+  <metadata>
+    <OBJECT_NAME>ISS (ZARYA)</OBJECT_NAME><OBJECT_ID>1998-067A</OBJECT_ID>
+    <REF_FRAME>TEME</REF_FRAME><MEAN_ELEMENT_THEORY>SGP4</MEAN_ELEMENT_THEORY>
+  </metadata>
+  <data>
+    <meanElements>
+      <EPOCH>2008-09-20T12:25:40.104192Z</EPOCH><MEAN_MOTION>15.72125391</MEAN_MOTION>
+      <ECCENTRICITY>0.0006703</ECCENTRICITY><INCLINATION>51.6416</INCLINATION>
+      <RA_OF_ASC_NODE>247.4627</RA_OF_ASC_NODE><ARG_OF_PERICENTER>130.536</ARG_OF_PERICENTER>
+      <MEAN_ANOMALY>325.0288</MEAN_ANOMALY>
+    </meanElements>
+    <tleParameters>
+      <EPHEMERIS_TYPE>0</EPHEMERIS_TYPE><CLASSIFICATION_TYPE>U</CLASSIFICATION_TYPE>
+      <NORAD_CAT_ID>25544</NORAD_CAT_ID><ELEMENT_SET_NO>292</ELEMENT_SET_NO>
+      <REV_AT_EPOCH>56353</REV_AT_EPOCH><BSTAR>-1.1606e-5</BSTAR>
+      <MEAN_MOTION_DOT>-2.182e-5</MEAN_MOTION_DOT><MEAN_MOTION_DDOT>0</MEAN_MOTION_DDOT>
+    </tleParameters>
+  </data>
+</segment></body></omm></ndm>"#;
+
+    #[test]
+    fn omms_read_the_same_in_every_encoding() {
+        let omm = Omm::from_json(OMM_JSON).unwrap();
+        assert_eq!(omm.norad_cat_id, Some(25544));
+        assert_eq!(omm.mean_motion_rev_per_day, 15.72125391);
+        assert!(omm.validate().is_ok());
+        assert_eq!(Omm::from_kvn(OMM_KVN).unwrap(), omm);
+        assert_eq!(Omm::from_xml(OMM_XML).unwrap(), omm);
+        assert_eq!(Omm::parse(OMM_XML).unwrap(), omm);
+
+        let from_tle = Tle::new(ISS.0, ISS.1).unwrap().to_omm().unwrap();
+        assert_eq!(from_tle.epoch, omm.epoch);
+        assert_eq!(from_tle.rev_at_epoch, omm.rev_at_epoch);
+    }
+
+    #[test]
+    fn malformed_omms_are_rejected() {
+        assert!(matches!(Omm::from_json("[]"), Err(ElsetError::Format(_))));
+        assert!(matches!(Omm::from_json("{"), Err(ElsetError::Format(_))));
+        let no_mean_motion = OMM_JSON.replace(r#""MEAN_MOTION": 15.72125391,"#, "");
+        assert_eq!(
+            Omm::from_json(&no_mean_motion),
+            Err(ElsetError::MissingField("MEAN_MOTION".to_string()))
+        );
+
+        let itrf = OMM_KVN.replace("REF_FRAME = TEME", "REF_FRAME = ITRF");
+        assert!(matches!(
+            Omm::from_kvn(&itrf),
+            Err(ElsetError::InvalidValue { field, .. }) if field == "REF_FRAME"
+        ));
+        let bad_epoch = OMM_KVN.replace("2008-264T", "2008-264 ");
+        assert!(matches!(
+            Omm::from_kvn(&bad_epoch),
+            Err(ElsetError::InvalidValue { field, .. }) if field == "EPOCH"
+        ));
+
+        let unclosed = OMM_XML.replace("</INCLINATION>", "</INCLINATIONS>");
+        assert!(matches!(
+            Omm::from_xml(&unclosed),
+            Err(ElsetError::Format(_))
+        ));
+        let hyperbolic = OMM_XML.replace("0.0006703", "1.2");
+        let omm = Omm::from_xml(&hyperbolic).unwrap();
+        assert!(matches!(
+            omm.validate(),
+            Err(ElsetError::InvalidValue { field, .. }) if field == "ECCENTRICITY"
+        ));
+    }
+}
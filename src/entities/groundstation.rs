@@ -1,25 +1,40 @@
 use chrono::{DateTime, Utc};
-use serde::{Serialize, Deserialize};
+use serde::{Deserialize, Serialize};
+
+use crate::Precondition;
+use crate::units::{Degrees, Km};
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Groundstation {
     pub id: String,
     pub name: String,
-    pub latitude: f64,
-    pub longitude: f64,
+    pub latitude: Degrees,
+    pub longitude: Degrees,
+    /// The API reference does not give a unit; see [`altitude_km`](Self::altitude_km).
     pub altitude: f64,
     pub user_id: String,
     #[serde(rename = "creationEpoch")]
     pub created_at: DateTime<Utc>,
     #[serde(rename = "updateEpoch")]
-    pub updated_at: DateTime<Utc>
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Groundstation {
+    /// Altitude in km, taking `altitude` to be in meters.
+    ///
+    /// The unit of `altitude` is not documented by the API. This is the only
+    /// place that assumes one; the offline solvers and
+    /// [`FOVAccessRequest::new`](crate::FOVAccessRequest::new) go through it.
+    pub fn altitude_km(&self) -> Km {
+        Km(self.altitude / 1000.0)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct GroundstationListResponse {
-    pub ground_stations: Vec<Groundstation>
+    pub ground_stations: Vec<Groundstation>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -27,7 +42,84 @@ pub(crate) struct GroundstationListResponse {
 pub struct GroundstationCreateRequest {
     // only user-settable fields
     pub name: String,
-    pub latitude: f64,
-    pub longitude: f64,
-    pub altitude: f64
-}
\ No newline at end of file
+    pub latitude: Degrees,
+    pub longitude: Degrees,
+    pub altitude: f64,
+}
+
+impl GroundstationCreateRequest {
+    pub fn new(name: &str, latitude: Degrees, longitude: Degrees, altitude: f64) -> Self {
+        GroundstationCreateRequest {
+            name: name.to_string(),
+            latitude,
+            longitude,
+            altitude,
+        }
+    }
+}
+
+/// Copies the settings of an existing ground station, e.g. as the starting
+/// point for [`update_groundstation`](crate::CitraClient::update_groundstation).
+impl From<&Groundstation> for GroundstationCreateRequest {
+    fn from(groundstation: &Groundstation) -> Self {
+        GroundstationCreateRequest {
+            name: groundstation.name.clone(),
+            latitude: groundstation.latitude,
+            longitude: groundstation.longitude,
+            altitude: groundstation.altitude,
+        }
+    }
+}
+
+/// Partial ground station update for
+/// [`patch_groundstation`](crate::CitraClient::patch_groundstation). Only the
+/// fields that are set are sent.
+///
+/// ```
+/// use lemonaid::{GroundstationUpdateRequest, Precondition};
+/// # fn example(groundstation: lemonaid::Groundstation) {
+/// // fails with a conflict if someone else edited it since it was fetched
+/// let update = GroundstationUpdateRequest::new()
+///     .name("Boulder rooftop")
+///     .precondition(Precondition::UnmodifiedSince(groundstation.updated_at));
+/// # }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GroundstationUpdateRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<Degrees>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<Degrees>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altitude: Option<f64>,
+    /// Sent as a request header rather than in the body.
+    #[serde(skip)]
+    pub precondition: Option<Precondition>,
+}
+
+impl GroundstationUpdateRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn location(mut self, latitude: Degrees, longitude: Degrees, altitude: f64) -> Self {
+        self.latitude = Some(latitude);
+        self.longitude = Some(longitude);
+        self.altitude = Some(altitude);
+        self
+    }
+
+    /// Only applies the update if the ground station still meets `precondition`.
+    pub fn precondition(mut self, precondition: Precondition) -> Self {
+        self.precondition = Some(precondition);
+        self
+    }
+}
@@ -4,3 +4,5 @@ pub mod task;
 pub mod antenna;
 pub mod access;
 pub mod rf_observation;
+pub mod satellite;
+pub mod elset;
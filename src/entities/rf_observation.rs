@@ -1,6 +1,5 @@
-use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-
+use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -15,14 +14,14 @@ pub struct RFDetection {
 #[serde(rename_all = "camelCase")]
 pub struct RFPowerSpectralDensity {
     pub frequency_hz: Vec<i64>,
-    pub power_dbm_per_hz: Vec<f64>
+    pub power_dbm_per_hz: Vec<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct RFCaptureData {
     pub detections: Vec<RFDetection>,
-    pub power_spectral_density: RFPowerSpectralDensity
+    pub power_spectral_density: RFPowerSpectralDensity,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -32,7 +31,30 @@ pub struct CreateRFCaptureRequest {
     pub capture_start: DateTime<Utc>,
     pub capture_end: DateTime<Utc>,
     pub data: RFCaptureData,
-    pub task_id: Option<String>
+    pub task_id: Option<String>,
+}
+
+impl CreateRFCaptureRequest {
+    pub fn new(
+        antenna_id: &str,
+        capture_start: DateTime<Utc>,
+        capture_end: DateTime<Utc>,
+        data: RFCaptureData,
+    ) -> Self {
+        CreateRFCaptureRequest {
+            antenna_id: antenna_id.to_string(),
+            capture_start,
+            capture_end,
+            data,
+            task_id: None,
+        }
+    }
+
+    /// Links the capture to the task it was recorded for.
+    pub fn task(mut self, task_id: &str) -> Self {
+        self.task_id = Some(task_id.to_string());
+        self
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -62,4 +84,4 @@ pub struct RFCaptureSummary {
     pub task_id: Option<String>,
     #[serde(rename = "creationEpoch")]
     pub created_at: DateTime<Utc>,
-}
\ No newline at end of file
+}
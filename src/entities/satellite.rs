@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use crate::units::MHz;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SatelliteStatus {
+    Active,
+    Inactive,
+    Decayed,
+    #[serde(other)]
+    Unknown,
+}
+
+/// A frequency range the satellite transmits or receives on.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SatelliteFrequency {
+    pub min_frequency_mhz: MHz,
+    pub max_frequency_mhz: MHz,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Satellite {
+    pub id: String,
+    pub name: String,
+    pub norad_id: Option<u32>,
+    pub cospar_id: Option<String>,
+    pub status: SatelliteStatus,
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub frequencies: Vec<SatelliteFrequency>,
+}
+
+/// Filters for searching the satellite catalog.
+///
+/// ```
+/// use lemonaid::SatelliteQuery;
+/// use lemonaid::units::MHz;
+///
+/// // amateur satellites transmitting in the 70 cm band
+/// let query = SatelliteQuery::new().frequency_band(MHz(435.0), MHz(438.0)).limit(20);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SatelliteQuery {
+    name: Option<String>,
+    norad_id: Option<u32>,
+    min_frequency_mhz: Option<MHz>,
+    max_frequency_mhz: Option<MHz>,
+    limit: Option<u32>,
+}
+
+impl SatelliteQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches satellites whose name contains `name`, ignoring case.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn norad_id(mut self, norad_id: u32) -> Self {
+        self.norad_id = Some(norad_id);
+        self
+    }
+
+    /// Matches satellites with a frequency range overlapping `[min_mhz, max_mhz]`.
+    pub fn frequency_band(mut self, min_mhz: MHz, max_mhz: MHz) -> Self {
+        self.min_frequency_mhz = Some(min_mhz);
+        self.max_frequency_mhz = Some(max_mhz);
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Query string parameters, unencoded.
+    pub(crate) fn to_query_pairs(&self) -> Vec<(&'static str, String)> {
+        [
+            ("name", self.name.clone()),
+            ("noradId", self.norad_id.map(|id| id.to_string())),
+            (
+                "minFrequencyMhz",
+                self.min_frequency_mhz.map(|f| f.value().to_string()),
+            ),
+            (
+                "maxFrequencyMhz",
+                self.max_frequency_mhz.map(|f| f.value().to_string()),
+            ),
+            ("limit", self.limit.map(|limit| limit.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|v| (key, v)))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_use_the_api_parameter_names() {
+        let query = SatelliteQuery::new()
+            .name("ISS (ZARYA)")
+            .norad_id(25544)
+            .frequency_band(MHz(435.0), MHz(437.5))
+            .limit(20);
+        let pairs = query.to_query_pairs();
+        assert_eq!(
+            pairs,
+            [
+                ("name", "ISS (ZARYA)".to_string()),
+                ("noradId", "25544".to_string()),
+                ("minFrequencyMhz", "435".to_string()),
+                ("maxFrequencyMhz", "437.5".to_string()),
+                ("limit", "20".to_string()),
+            ]
+        );
+        assert!(SatelliteQuery::new().to_query_pairs().is_empty());
+
+        // encoded the way the client appends them to the URL
+        let mut url = reqwest::Url::parse("https://dev.api.citra.space/satellites").unwrap();
+        url.query_pairs_mut().extend_pairs(&pairs);
+        assert_eq!(
+            url.query(),
+            Some(
+                "name=ISS+%28ZARYA%29&noradId=25544&minFrequencyMhz=435&maxFrequencyMhz=437.5&limit=20"
+            )
+        );
+    }
+}
@@ -1,13 +1,28 @@
-use chrono::{DateTime, Utc};
-use serde::{Serialize, Deserialize};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+use crate::units::{Degrees, Km};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskStatus {
     Pending,
     Canceled,
     Scheduled,
     Succeeded,
-    Failed
+    Failed,
+}
+
+impl TaskStatus {
+    /// Name used by the API, both in JSON and in query strings.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "Pending",
+            TaskStatus::Canceled => "Canceled",
+            TaskStatus::Scheduled => "Scheduled",
+            TaskStatus::Succeeded => "Succeeded",
+            TaskStatus::Failed => "Failed",
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -36,11 +51,11 @@ pub struct Task {
     pub priority: i32,
     pub scheduled_start: Option<DateTime<Utc>>,
     pub scheduled_stop: Option<DateTime<Utc>>,
-    pub range_km: Option<f64>,
+    pub range_km: Option<Km>,
     pub range_rate_km_s: Option<f64>,
-    pub right_ascension: Option<f64>,
+    pub right_ascension: Option<Degrees>,
     pub right_ascension_rate: Option<f64>,
-    pub declination: Option<f64>,
+    pub declination: Option<Degrees>,
     pub declination_rate: Option<f64>,
 }
 
@@ -49,11 +64,38 @@ pub struct Task {
 pub struct TaskUpdateRequest {
     pub id: String,
     pub status: TaskStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scheduled_start: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scheduled_stop: Option<DateTime<Utc>>,
 }
 
+impl TaskUpdateRequest {
+    /// Changes only the status, leaving priority and schedule to the server.
+    pub fn new(task_id: &str, status: TaskStatus) -> Self {
+        TaskUpdateRequest {
+            id: task_id.to_string(),
+            status,
+            priority: None,
+            scheduled_start: None,
+            scheduled_stop: None,
+        }
+    }
+
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub fn scheduled(mut self, start: DateTime<Utc>, stop: DateTime<Utc>) -> Self {
+        self.scheduled_start = Some(start);
+        self.scheduled_stop = Some(stop);
+        self
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTaskRequest {
@@ -61,5 +103,296 @@ pub struct CreateTaskRequest {
     pub task_stop: DateTime<Utc>,
     pub satellite_id: String,
     pub antenna_id: Option<String>,
-    pub telescope_id: Option<String>
+    pub telescope_id: Option<String>,
+}
+
+impl CreateTaskRequest {
+    /// Observes the satellite with a telescope within `[start, stop]`.
+    pub fn for_telescope(
+        telescope_id: &str,
+        satellite_id: &str,
+        start: DateTime<Utc>,
+        stop: DateTime<Utc>,
+    ) -> Self {
+        CreateTaskRequest {
+            task_start: start,
+            task_stop: stop,
+            satellite_id: satellite_id.to_string(),
+            antenna_id: None,
+            telescope_id: Some(telescope_id.to_string()),
+        }
+    }
+
+    /// Tracks the satellite with an antenna within `[start, stop]`.
+    pub fn for_antenna(
+        antenna_id: &str,
+        satellite_id: &str,
+        start: DateTime<Utc>,
+        stop: DateTime<Utc>,
+    ) -> Self {
+        CreateTaskRequest {
+            task_start: start,
+            task_stop: stop,
+            satellite_id: satellite_id.to_string(),
+            antenna_id: Some(antenna_id.to_string()),
+            telescope_id: None,
+        }
+    }
+}
+
+/// Field to order task listings by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSortField {
+    TaskStart,
+    TaskStop,
+    Priority,
+    CreatedAt,
+}
+
+impl TaskSortField {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TaskSortField::TaskStart => "taskStart",
+            TaskSortField::TaskStop => "taskStop",
+            TaskSortField::Priority => "priority",
+            TaskSortField::CreatedAt => "creationEpoch",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Filters for task listings, usable against telescopes, antennas, ground
+/// stations and the global task list.
+///
+/// ```
+/// use lemonaid::{SortOrder, TaskQuery, TaskSortField, TaskStatus};
+///
+/// let query = TaskQuery::new()
+///     .status(TaskStatus::Pending)
+///     .status(TaskStatus::Scheduled)
+///     .satellite("25544")
+///     .sort_by(TaskSortField::TaskStart, SortOrder::Ascending)
+///     .limit(50);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TaskQuery {
+    statuses: Vec<TaskStatus>,
+    start_after: Option<DateTime<Utc>>,
+    start_before: Option<DateTime<Utc>>,
+    stop_after: Option<DateTime<Utc>>,
+    stop_before: Option<DateTime<Utc>>,
+    satellite_id: Option<String>,
+    groundstation_id: Option<String>,
+    task_type: Option<String>,
+    min_priority: Option<i32>,
+    max_priority: Option<i32>,
+    sort: Option<(TaskSortField, SortOrder)>,
+    limit: Option<u32>,
+}
+
+impl TaskQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches tasks with this status. Repeat to match any of several statuses.
+    pub fn status(mut self, status: TaskStatus) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    pub fn statuses(mut self, statuses: impl IntoIterator<Item = TaskStatus>) -> Self {
+        self.statuses.extend(statuses);
+        self
+    }
+
+    /// Matches tasks whose `task_start` lies within `[from, to]`.
+    pub fn starting_between(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.start_after = Some(from);
+        self.start_before = Some(to);
+        self
+    }
+
+    pub fn starting_after(mut self, time: DateTime<Utc>) -> Self {
+        self.start_after = Some(time);
+        self
+    }
+
+    pub fn starting_before(mut self, time: DateTime<Utc>) -> Self {
+        self.start_before = Some(time);
+        self
+    }
+
+    /// Matches tasks whose `task_stop` lies within `[from, to]`.
+    pub fn stopping_between(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.stop_after = Some(from);
+        self.stop_before = Some(to);
+        self
+    }
+
+    pub fn stopping_after(mut self, time: DateTime<Utc>) -> Self {
+        self.stop_after = Some(time);
+        self
+    }
+
+    pub fn stopping_before(mut self, time: DateTime<Utc>) -> Self {
+        self.stop_before = Some(time);
+        self
+    }
+
+    pub fn satellite(mut self, satellite_id: &str) -> Self {
+        self.satellite_id = Some(satellite_id.to_string());
+        self
+    }
+
+    pub fn groundstation(mut self, groundstation_id: &str) -> Self {
+        self.groundstation_id = Some(groundstation_id.to_string());
+        self
+    }
+
+    pub fn task_type(mut self, task_type: &str) -> Self {
+        self.task_type = Some(task_type.to_string());
+        self
+    }
+
+    /// Matches tasks with `min <= priority <= max`.
+    pub fn priority_range(mut self, min: i32, max: i32) -> Self {
+        self.min_priority = Some(min);
+        self.max_priority = Some(max);
+        self
+    }
+
+    pub fn min_priority(mut self, min: i32) -> Self {
+        self.min_priority = Some(min);
+        self
+    }
+
+    pub fn max_priority(mut self, max: i32) -> Self {
+        self.max_priority = Some(max);
+        self
+    }
+
+    pub fn sort_by(mut self, field: TaskSortField, order: SortOrder) -> Self {
+        self.sort = Some((field, order));
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Query string parameters, unencoded.
+    pub(crate) fn to_query_pairs(&self) -> Vec<(&'static str, String)> {
+        let time = |t: &DateTime<Utc>| t.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+        let mut pairs: Vec<(&'static str, String)> = self
+            .statuses
+            .iter()
+            .map(|status| ("statuses", status.as_str().to_string()))
+            .collect();
+        let optional = [
+            ("startAfter", self.start_after.as_ref().map(time)),
+            ("startBefore", self.start_before.as_ref().map(time)),
+            ("stopAfter", self.stop_after.as_ref().map(time)),
+            ("stopBefore", self.stop_before.as_ref().map(time)),
+            ("satelliteId", self.satellite_id.clone()),
+            ("groundStationId", self.groundstation_id.clone()),
+            ("type", self.task_type.clone()),
+            ("minPriority", self.min_priority.map(|p| p.to_string())),
+            ("maxPriority", self.max_priority.map(|p| p.to_string())),
+            (
+                "sortBy",
+                self.sort.map(|(field, _)| field.as_str().to_string()),
+            ),
+            (
+                "sortOrder",
+                self.sort.map(|(_, order)| match order {
+                    SortOrder::Ascending => "asc".to_string(),
+                    SortOrder::Descending => "desc".to_string(),
+                }),
+            ),
+            ("limit", self.limit.map(|limit| limit.to_string())),
+        ];
+        pairs.extend(
+            optional
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|v| (key, v))),
+        );
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_updates_leave_other_fields_out() {
+        let update = TaskUpdateRequest::new("task-1", TaskStatus::Canceled);
+        assert_eq!(
+            serde_json::to_string(&update).unwrap(),
+            r#"{"id":"task-1","status":"Canceled"}"#
+        );
+        let update = update.priority(3);
+        assert_eq!(
+            serde_json::to_string(&update).unwrap(),
+            r#"{"id":"task-1","status":"Canceled","priority":3}"#
+        );
+    }
+
+    #[test]
+    fn queries_use_the_api_parameter_names() {
+        let start: DateTime<Utc> = "2025-01-01T00:00:00Z".parse().unwrap();
+        let query = TaskQuery::new()
+            .status(TaskStatus::Pending)
+            .status(TaskStatus::Scheduled)
+            .starting_between(start, start + chrono::Duration::milliseconds(1500))
+            .stopping_after(start + chrono::Duration::hours(1))
+            .satellite("25544")
+            .groundstation("gs-1")
+            .task_type("Track")
+            .min_priority(-2)
+            .sort_by(TaskSortField::CreatedAt, SortOrder::Descending)
+            .limit(10);
+        let pairs = query.to_query_pairs();
+        let pairs: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("statuses", "Pending"),
+                ("statuses", "Scheduled"),
+                ("startAfter", "2025-01-01T00:00:00Z"),
+                ("startBefore", "2025-01-01T00:00:01.500Z"),
+                ("stopAfter", "2025-01-01T01:00:00Z"),
+                ("satelliteId", "25544"),
+                ("groundStationId", "gs-1"),
+                ("type", "Track"),
+                ("minPriority", "-2"),
+                ("sortBy", "creationEpoch"),
+                ("sortOrder", "desc"),
+                ("limit", "10"),
+            ]
+        );
+
+        let query = TaskQuery::new()
+            .max_priority(5)
+            .sort_by(TaskSortField::TaskStart, SortOrder::Ascending);
+        assert_eq!(
+            query.to_query_pairs(),
+            [
+                ("maxPriority", "5".to_string()),
+                ("sortBy", "taskStart".to_string()),
+                ("sortOrder", "asc".to_string()),
+            ]
+        );
+        assert!(TaskQuery::new().to_query_pairs().is_empty());
+    }
 }
@@ -1,5 +1,8 @@
 use chrono::{DateTime, Utc};
-use serde::{Serialize, Deserialize};
+use serde::{Deserialize, Serialize};
+
+use crate::Precondition;
+use crate::units::{Arcsec, Degrees};
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -16,18 +19,271 @@ pub struct Telescope {
     #[serde(rename = "lastConnectionEpoch")]
     pub last_connected_at: Option<DateTime<Utc>>,
     #[serde(rename = "angularNoise")]
-    pub angular_noise_arcsec: f64,
+    pub angular_noise_arcsec: Arcsec,
+    #[serde(rename = "fieldOfView")]
+    pub field_of_view_deg: Degrees,
+    #[serde(rename = "maxMagnitude")]
+    pub limiting_magnitude: f64,
+    #[serde(rename = "minElevation")]
+    pub min_elevation_deg: Degrees,
+    #[serde(rename = "maxSlewRate")]
+    pub max_slew_rate_deg_per_sec: f64,
+    #[serde(rename = "homeAzimuth")]
+    pub home_azimuth_deg: Degrees,
+    #[serde(rename = "homeElevation")]
+    pub home_elevation_deg: Degrees,
+    pub automated_scheduling: bool,
+}
+
+/// User-settable telescope fields, for [`create_telescope`](crate::CitraClient::create_telescope).
+///
+/// ```
+/// use lemonaid::TelescopeCreateRequest;
+/// use lemonaid::units::Degrees;
+///
+/// let telescope = TelescopeCreateRequest::new("Backyard 8\"")
+///     .groundstation("gs-1")
+///     .field_of_view(Degrees(0.8))
+///     .min_elevation(Degrees(25.0));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TelescopeCreateRequest {
+    pub name: String,
+    #[serde(rename = "groundStationId")]
+    pub groundstation_id: Option<String>,
+    pub user_group_id: Option<String>,
+    pub satellite_id: Option<String>,
+    #[serde(rename = "angularNoise")]
+    pub angular_noise_arcsec: Arcsec,
     #[serde(rename = "fieldOfView")]
-    pub field_of_view_deg: f64,
+    pub field_of_view_deg: Degrees,
     #[serde(rename = "maxMagnitude")]
     pub limiting_magnitude: f64,
     #[serde(rename = "minElevation")]
-    pub min_elevation_deg: f64,
+    pub min_elevation_deg: Degrees,
     #[serde(rename = "maxSlewRate")]
     pub max_slew_rate_deg_per_sec: f64,
     #[serde(rename = "homeAzimuth")]
-    pub home_azimuth_deg: f64,
+    pub home_azimuth_deg: Degrees,
     #[serde(rename = "homeElevation")]
-    pub home_elevation_deg: f64,
-    pub automated_scheduling: bool
-}
\ No newline at end of file
+    pub home_elevation_deg: Degrees,
+    pub automated_scheduling: bool,
+}
+
+/// A small amateur telescope: 1° field of view, 2" noise, magnitude 12, 20°
+/// elevation mask, 3°/s slew, parked at the zenith, not auto-scheduled.
+impl Default for TelescopeCreateRequest {
+    fn default() -> Self {
+        TelescopeCreateRequest {
+            name: String::new(),
+            groundstation_id: None,
+            user_group_id: None,
+            satellite_id: None,
+            angular_noise_arcsec: Arcsec(2.0),
+            field_of_view_deg: Degrees(1.0),
+            limiting_magnitude: 12.0,
+            min_elevation_deg: Degrees(20.0),
+            max_slew_rate_deg_per_sec: 3.0,
+            home_azimuth_deg: Degrees(0.0),
+            home_elevation_deg: Degrees(90.0),
+            automated_scheduling: false,
+        }
+    }
+}
+
+impl TelescopeCreateRequest {
+    pub fn new(name: &str) -> Self {
+        TelescopeCreateRequest {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn groundstation(mut self, groundstation_id: &str) -> Self {
+        self.groundstation_id = Some(groundstation_id.to_string());
+        self
+    }
+
+    pub fn user_group(mut self, user_group_id: &str) -> Self {
+        self.user_group_id = Some(user_group_id.to_string());
+        self
+    }
+
+    pub fn angular_noise(mut self, noise: Arcsec) -> Self {
+        self.angular_noise_arcsec = noise;
+        self
+    }
+
+    pub fn field_of_view(mut self, field_of_view: Degrees) -> Self {
+        self.field_of_view_deg = field_of_view;
+        self
+    }
+
+    pub fn limiting_magnitude(mut self, magnitude: f64) -> Self {
+        self.limiting_magnitude = magnitude;
+        self
+    }
+
+    pub fn min_elevation(mut self, elevation: Degrees) -> Self {
+        self.min_elevation_deg = elevation;
+        self
+    }
+
+    pub fn max_slew_rate(mut self, deg_per_sec: f64) -> Self {
+        self.max_slew_rate_deg_per_sec = deg_per_sec;
+        self
+    }
+
+    /// Park position between tasks.
+    pub fn home(mut self, azimuth: Degrees, elevation: Degrees) -> Self {
+        self.home_azimuth_deg = azimuth;
+        self.home_elevation_deg = elevation;
+        self
+    }
+
+    pub fn automated_scheduling(mut self, enabled: bool) -> Self {
+        self.automated_scheduling = enabled;
+        self
+    }
+}
+
+/// Copies the settings of an existing telescope, e.g. to clone it onto another
+/// ground station.
+impl From<&Telescope> for TelescopeCreateRequest {
+    fn from(telescope: &Telescope) -> Self {
+        TelescopeCreateRequest {
+            name: telescope.name.clone(),
+            groundstation_id: telescope.groundstation_id.clone(),
+            user_group_id: telescope.user_group_id.clone(),
+            satellite_id: telescope.satellite_id.clone(),
+            angular_noise_arcsec: telescope.angular_noise_arcsec,
+            field_of_view_deg: telescope.field_of_view_deg,
+            limiting_magnitude: telescope.limiting_magnitude,
+            min_elevation_deg: telescope.min_elevation_deg,
+            max_slew_rate_deg_per_sec: telescope.max_slew_rate_deg_per_sec,
+            home_azimuth_deg: telescope.home_azimuth_deg,
+            home_elevation_deg: telescope.home_elevation_deg,
+            automated_scheduling: telescope.automated_scheduling,
+        }
+    }
+}
+
+/// Partial telescope update for [`patch_telescope`](crate::CitraClient::patch_telescope).
+/// Only the fields that are set are sent, so concurrent edits of other fields
+/// are not overwritten.
+///
+/// ```
+/// use lemonaid::TelescopeUpdateRequest;
+/// use lemonaid::units::Degrees;
+///
+/// let update = TelescopeUpdateRequest::new()
+///     .min_elevation(Degrees(30.0))
+///     .automated_scheduling(true);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TelescopeUpdateRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "groundStationId", skip_serializing_if = "Option::is_none")]
+    pub groundstation_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_group_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub satellite_id: Option<String>,
+    #[serde(rename = "angularNoise", skip_serializing_if = "Option::is_none")]
+    pub angular_noise_arcsec: Option<Arcsec>,
+    #[serde(rename = "fieldOfView", skip_serializing_if = "Option::is_none")]
+    pub field_of_view_deg: Option<Degrees>,
+    #[serde(rename = "maxMagnitude", skip_serializing_if = "Option::is_none")]
+    pub limiting_magnitude: Option<f64>,
+    #[serde(rename = "minElevation", skip_serializing_if = "Option::is_none")]
+    pub min_elevation_deg: Option<Degrees>,
+    #[serde(rename = "maxSlewRate", skip_serializing_if = "Option::is_none")]
+    pub max_slew_rate_deg_per_sec: Option<f64>,
+    #[serde(rename = "homeAzimuth", skip_serializing_if = "Option::is_none")]
+    pub home_azimuth_deg: Option<Degrees>,
+    #[serde(rename = "homeElevation", skip_serializing_if = "Option::is_none")]
+    pub home_elevation_deg: Option<Degrees>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub automated_scheduling: Option<bool>,
+    #[serde(
+        rename = "lastConnectionEpoch",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_connected_at: Option<DateTime<Utc>>,
+    /// Sent as a request header rather than in the body.
+    #[serde(skip)]
+    pub precondition: Option<Precondition>,
+}
+
+impl TelescopeUpdateRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn groundstation(mut self, groundstation_id: &str) -> Self {
+        self.groundstation_id = Some(groundstation_id.to_string());
+        self
+    }
+
+    pub fn user_group(mut self, user_group_id: &str) -> Self {
+        self.user_group_id = Some(user_group_id.to_string());
+        self
+    }
+
+    pub fn angular_noise(mut self, noise: Arcsec) -> Self {
+        self.angular_noise_arcsec = Some(noise);
+        self
+    }
+
+    pub fn field_of_view(mut self, field_of_view: Degrees) -> Self {
+        self.field_of_view_deg = Some(field_of_view);
+        self
+    }
+
+    pub fn limiting_magnitude(mut self, magnitude: f64) -> Self {
+        self.limiting_magnitude = Some(magnitude);
+        self
+    }
+
+    pub fn min_elevation(mut self, elevation: Degrees) -> Self {
+        self.min_elevation_deg = Some(elevation);
+        self
+    }
+
+    pub fn max_slew_rate(mut self, deg_per_sec: f64) -> Self {
+        self.max_slew_rate_deg_per_sec = Some(deg_per_sec);
+        self
+    }
+
+    /// Park position between tasks.
+    pub fn home(mut self, azimuth: Degrees, elevation: Degrees) -> Self {
+        self.home_azimuth_deg = Some(azimuth);
+        self.home_elevation_deg = Some(elevation);
+        self
+    }
+
+    pub fn automated_scheduling(mut self, enabled: bool) -> Self {
+        self.automated_scheduling = Some(enabled);
+        self
+    }
+
+    /// Records that the telescope was in contact with the API at `time`.
+    pub fn last_connected(mut self, time: DateTime<Utc>) -> Self {
+        self.last_connected_at = Some(time);
+        self
+    }
+
+    /// Only applies the update if the telescope still meets `precondition`.
+    pub fn precondition(mut self, precondition: Precondition) -> Self {
+        self.precondition = Some(precondition);
+        self
+    }
+}
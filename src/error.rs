@@ -1,24 +1,287 @@
 use std::fmt;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::retry;
 
 /// The error type for the Lemonaid library.
 #[derive(Debug)]
 pub enum LemonaidError {
     /// An HTTP/network error from the underlying reqwest client.
     Http(reqwest::Error),
+    /// The request timed out while connecting or waiting for the response.
+    Timeout(reqwest::Error),
     /// An API error response with status code and optional message body.
     Api {
         status: reqwest::StatusCode,
         message: String,
     },
+    /// The requested resource does not exist (404).
+    NotFound { message: String },
+    /// The token is missing, invalid or expired (401).
+    Unauthorized { message: String },
+    /// The token is valid but not allowed to access the resource (403).
+    Forbidden { message: String },
+    /// The resource was changed by someone else since it was read (409/412), so
+    /// a conditional update was not applied.
+    Conflict {
+        status: reqwest::StatusCode,
+        message: String,
+    },
+    /// Too many requests (429). `retry_after` is taken from the `Retry-After` header.
+    RateLimited {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// The API rejected the request payload (400/422), with the offending fields
+    /// when the response body lists them.
+    Validation {
+        status: reqwest::StatusCode,
+        message: String,
+        errors: Vec<FieldError>,
+    },
+    /// The request failed client-side validation and was not sent.
+    InvalidRequest { errors: Vec<FieldError> },
+    /// A successful response body could not be decoded into the expected type.
+    Deserialization {
+        source: serde_json::Error,
+        /// Path to the field that failed to decode, e.g. `[3].taskStart`.
+        path: String,
+        /// The raw response body.
+        body: String,
+    },
+    /// A bulk endpoint returned an empty list where at least one item was expected.
+    EmptyBulkResponse,
+    /// A request body could not be serialized to JSON.
+    Serialization(serde_json::Error),
+    /// The client was configured with invalid settings, e.g. a malformed base URL.
+    Config(String),
+    /// An element set failed to parse or validate.
+    Elset(ElsetError),
+    /// Local SGP4 propagation failed.
+    Propagation(PropagationError),
+    /// A [`TelescopeDriver`](crate::agent::TelescopeDriver) failed to carry out a task.
+    Driver(String),
+}
+
+/// Problems found while parsing or validating TLEs and OMMs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElsetError {
+    /// The text does not follow the expected layout.
+    Format(String),
+    /// A TLE line's checksum digit does not match its contents.
+    Checksum { line: u8, expected: u32, found: u32 },
+    /// A required OMM field is absent.
+    MissingField(String),
+    /// A field is present but unparseable or out of range.
+    InvalidValue { field: String, message: String },
+}
+
+/// Problems found while propagating element sets locally.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropagationError {
+    /// No element sets were supplied for the satellite.
+    NoElementSets { satellite_id: String },
+    /// SGP4 could not be initialised from the elements.
+    InvalidElements(String),
+    /// The orbit decayed or diverged at the requested time.
+    Diverged {
+        /// Minutes since the element set epoch.
+        minutes: f64,
+        message: String,
+    },
+}
+
+/// A single field-level problem reported for a rejected request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    /// Dotted path to the field, e.g. `taskStop` or `0.latitude`.
+    pub field: String,
+    pub message: String,
+}
+
+impl LemonaidError {
+    /// HTTP status code associated with this error, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            LemonaidError::Http(err) | LemonaidError::Timeout(err) => err.status(),
+            LemonaidError::Api { status, .. }
+            | LemonaidError::Conflict { status, .. }
+            | LemonaidError::Validation { status, .. } => Some(*status),
+            LemonaidError::NotFound { .. } => Some(StatusCode::NOT_FOUND),
+            LemonaidError::Unauthorized { .. } => Some(StatusCode::UNAUTHORIZED),
+            LemonaidError::Forbidden { .. } => Some(StatusCode::FORBIDDEN),
+            LemonaidError::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            LemonaidError::Deserialization { .. }
+            | LemonaidError::EmptyBulkResponse
+            | LemonaidError::Serialization(_)
+            | LemonaidError::Config(_)
+            | LemonaidError::InvalidRequest { .. }
+            | LemonaidError::Elset(_)
+            | LemonaidError::Propagation(_)
+            | LemonaidError::Driver(_) => None,
+        }
+    }
+
+    /// Whether repeating the same request later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            LemonaidError::Http(err) => retry::is_retryable_error(err),
+            LemonaidError::Timeout(_) | LemonaidError::RateLimited { .. } => true,
+            LemonaidError::Api { status, .. } => retry::is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    /// Builds the error for a non-success response, consuming its body.
+    pub(crate) async fn from_response(response: reqwest::Response) -> LemonaidError {
+        let status = response.status();
+        let retry_after = retry::retry_after(response.headers());
+        let message = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        match status {
+            StatusCode::NOT_FOUND => LemonaidError::NotFound { message },
+            StatusCode::UNAUTHORIZED => LemonaidError::Unauthorized { message },
+            StatusCode::FORBIDDEN => LemonaidError::Forbidden { message },
+            StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED => {
+                LemonaidError::Conflict { status, message }
+            }
+            StatusCode::TOO_MANY_REQUESTS => LemonaidError::RateLimited {
+                retry_after,
+                message,
+            },
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+                let errors = parse_field_errors(&message);
+                LemonaidError::Validation {
+                    status,
+                    message,
+                    errors,
+                }
+            }
+            _ => LemonaidError::Api { status, message },
+        }
+    }
+}
+
+/// Error bodies either follow the `{"detail": [{"loc": [...], "msg": ...}]}` shape
+/// or list `{"errors": [{"field": ..., "message": ...}]}`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ErrorBody {
+    Detail { detail: Vec<DetailItem> },
+    Errors { errors: Vec<FieldErrorItem> },
+}
+
+#[derive(Deserialize)]
+struct DetailItem {
+    #[serde(default)]
+    loc: Vec<serde_json::Value>,
+    msg: String,
+}
+
+#[derive(Deserialize)]
+struct FieldErrorItem {
+    field: String,
+    message: String,
+}
+
+fn parse_field_errors(body: &str) -> Vec<FieldError> {
+    match serde_json::from_str::<ErrorBody>(body) {
+        Ok(ErrorBody::Detail { detail }) => detail
+            .into_iter()
+            .map(|item| {
+                // the first location element names the request part (body, query, path)
+                let field = item
+                    .loc
+                    .iter()
+                    .skip(if item.loc.len() > 1 { 1 } else { 0 })
+                    .map(|part| match part {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(".");
+                FieldError {
+                    field,
+                    message: item.msg,
+                }
+            })
+            .collect(),
+        Ok(ErrorBody::Errors { errors }) => errors
+            .into_iter()
+            .map(|item| FieldError {
+                field: item.field,
+                message: item.message,
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    }
 }
 
 impl fmt::Display for LemonaidError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             LemonaidError::Http(err) => write!(f, "HTTP error: {}", err),
+            LemonaidError::Timeout(err) => write!(f, "Request timed out: {}", err),
             LemonaidError::Api { status, message } => {
                 write!(f, "API error ({}): {}", status, message)
             }
+            LemonaidError::NotFound { message } => write!(f, "Not found: {}", message),
+            LemonaidError::Unauthorized { message } => write!(f, "Unauthorized: {}", message),
+            LemonaidError::Forbidden { message } => write!(f, "Forbidden: {}", message),
+            LemonaidError::Conflict { status, message } => {
+                write!(f, "Conflict ({}): {}", status, message)
+            }
+            LemonaidError::RateLimited {
+                retry_after,
+                message,
+            } => match retry_after {
+                Some(wait) => write!(
+                    f,
+                    "Rate limited (retry after {}s): {}",
+                    wait.as_secs(),
+                    message
+                ),
+                None => write!(f, "Rate limited: {}", message),
+            },
+            LemonaidError::Validation {
+                status,
+                message,
+                errors,
+            } => {
+                if errors.is_empty() {
+                    return write!(f, "Validation error ({}): {}", status, message);
+                }
+                write!(f, "Validation error ({}):", status)?;
+                for error in errors {
+                    write!(f, " {}: {};", error.field, error.message)?;
+                }
+                Ok(())
+            }
+            LemonaidError::InvalidRequest { errors } => {
+                write!(f, "Invalid request:")?;
+                for error in errors {
+                    write!(f, " {}: {};", error.field, error.message)?;
+                }
+                Ok(())
+            }
+            LemonaidError::Deserialization { source, path, .. } => {
+                write!(f, "Failed to decode response at `{}`: {}", path, source)
+            }
+            LemonaidError::EmptyBulkResponse => {
+                write!(f, "Bulk endpoint returned no items")
+            }
+            LemonaidError::Serialization(err) => {
+                write!(f, "Failed to serialize request body: {}", err)
+            }
+            LemonaidError::Config(message) => write!(f, "Invalid configuration: {}", message),
+            LemonaidError::Elset(err) => write!(f, "Invalid element set: {}", err),
+            LemonaidError::Propagation(err) => write!(f, "Propagation failed: {}", err),
+            LemonaidError::Driver(message) => write!(f, "Telescope driver failed: {}", message),
         }
     }
 }
@@ -26,14 +289,73 @@ impl fmt::Display for LemonaidError {
 impl std::error::Error for LemonaidError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            LemonaidError::Http(err) => Some(err),
-            LemonaidError::Api { .. } => None,
+            LemonaidError::Http(err) | LemonaidError::Timeout(err) => Some(err),
+            LemonaidError::Deserialization { source, .. } => Some(source),
+            LemonaidError::Serialization(err) => Some(err),
+            LemonaidError::Elset(err) => Some(err),
+            LemonaidError::Propagation(err) => Some(err),
+            _ => None,
         }
     }
 }
 
 impl From<reqwest::Error> for LemonaidError {
     fn from(err: reqwest::Error) -> Self {
-        LemonaidError::Http(err)
+        if err.is_timeout() {
+            LemonaidError::Timeout(err)
+        } else {
+            LemonaidError::Http(err)
+        }
+    }
+}
+
+impl From<ElsetError> for LemonaidError {
+    fn from(err: ElsetError) -> Self {
+        LemonaidError::Elset(err)
+    }
+}
+
+impl From<PropagationError> for LemonaidError {
+    fn from(err: PropagationError) -> Self {
+        LemonaidError::Propagation(err)
+    }
+}
+
+impl fmt::Display for ElsetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElsetError::Format(message) => write!(f, "{}", message),
+            ElsetError::Checksum {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "checksum of TLE line {} is {}, expected {}",
+                line, found, expected
+            ),
+            ElsetError::MissingField(field) => write!(f, "missing field {}", field),
+            ElsetError::InvalidValue { field, message } => {
+                write!(f, "invalid {}: {}", field, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ElsetError {}
+
+impl fmt::Display for PropagationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropagationError::NoElementSets { satellite_id } => {
+                write!(f, "no element sets for satellite {}", satellite_id)
+            }
+            PropagationError::InvalidElements(message) => write!(f, "{}", message),
+            PropagationError::Diverged { minutes, message } => {
+                write!(f, "{} at {:.1} min from epoch", message, minutes)
+            }
+        }
     }
 }
+
+impl std::error::Error for PropagationError {}
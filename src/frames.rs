@@ -0,0 +1,790 @@
+//! Coordinate frame and time scale conversions.
+//!
+//! Inertial frames follow the IAU-1976/FK5 reduction: TEME is related to J2000 and
+//! GCRF through IAU-1976 precession and IAU-1980 nutation, and to ITRF through
+//! Greenwich mean sidereal time and polar motion. J2000 uses the nutation model
+//! as published, GCRF additionally applies the IERS nutation corrections from
+//! [`EarthOrientation`]. The nutation series is truncated to the terms above
+//! 0.5 mas, which keeps the error well below a metre at LEO distances.
+//!
+//! Positions are in km, velocities in km/s and angles in degrees unless noted.
+//!
+//! ```
+//! use lemonaid::frames::{self, EarthOrientation, Frame};
+//!
+//! let time = "2004-04-06T07:51:28.386009Z".parse().unwrap();
+//! let (position, velocity) = frames::transform(
+//!     [5094.18016210, 6127.64465950, 6380.34453270],
+//!     [-4.746131487, 0.785818041, 5.531931288],
+//!     Frame::Teme,
+//!     Frame::Itrf,
+//!     time,
+//!     &EarthOrientation::default(),
+//! );
+//! let (latitude, longitude, altitude_km) = frames::ecef_to_geodetic(position);
+//! # let _ = (velocity, latitude, longitude, altitude_km);
+//! ```
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+use crate::units::{Degrees, Km};
+use crate::{Groundstation, SensorFrame};
+
+/// A 3×3 rotation matrix, applied to column vectors.
+pub type Matrix = [[f64; 3]; 3];
+
+const ARCSEC_TO_RAD: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+/// Julian date of the J2000.0 epoch.
+const J2000_JD: f64 = 2_451_545.0;
+
+/// Earth rotation rate, in rad/s.
+pub const EARTH_ROTATION_RATE: f64 = 7.292_115_146_706_98e-5;
+
+/// WGS-84 equatorial radius, in km.
+const WGS84_RADIUS_KM: f64 = 6378.137;
+
+/// WGS-84 flattening.
+const WGS84_FLATTENING: f64 = 1.0 / 298.257_223_563;
+
+/// `TAI - UTC` after each leap second since 1972 (date the offset takes effect, seconds).
+const LEAP_SECONDS: &[(i32, u32, u32, f64)] = &[
+    (1972, 1, 1, 10.0),
+    (1972, 7, 1, 11.0),
+    (1973, 1, 1, 12.0),
+    (1974, 1, 1, 13.0),
+    (1975, 1, 1, 14.0),
+    (1976, 1, 1, 15.0),
+    (1977, 1, 1, 16.0),
+    (1978, 1, 1, 17.0),
+    (1979, 1, 1, 18.0),
+    (1980, 1, 1, 19.0),
+    (1981, 7, 1, 20.0),
+    (1982, 7, 1, 21.0),
+    (1983, 7, 1, 22.0),
+    (1985, 7, 1, 23.0),
+    (1988, 1, 1, 24.0),
+    (1990, 1, 1, 25.0),
+    (1991, 1, 1, 26.0),
+    (1992, 7, 1, 27.0),
+    (1993, 7, 1, 28.0),
+    (1994, 7, 1, 29.0),
+    (1996, 1, 1, 30.0),
+    (1997, 7, 1, 31.0),
+    (1999, 1, 1, 32.0),
+    (2006, 1, 1, 33.0),
+    (2009, 1, 1, 34.0),
+    (2012, 7, 1, 35.0),
+    (2015, 7, 1, 36.0),
+    (2017, 1, 1, 37.0),
+];
+
+/// Earth orientation parameters, as published in IERS Bulletin A or B.
+///
+/// The default (all zero) is adequate for pointing at the arcsecond level, apart
+/// from `ut1_minus_utc_s`, which shifts Earth-fixed positions by up to ~400 m.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EarthOrientation {
+    /// `UT1 - UTC`, in seconds.
+    pub ut1_minus_utc_s: f64,
+    /// Polar motion x, in arcseconds.
+    pub polar_x_arcsec: f64,
+    /// Polar motion y, in arcseconds.
+    pub polar_y_arcsec: f64,
+    /// Correction to the IAU-1980 nutation in longitude, in arcseconds.
+    pub dpsi_arcsec: f64,
+    /// Correction to the IAU-1980 nutation in obliquity, in arcseconds.
+    pub deps_arcsec: f64,
+}
+
+/// Astronomical time scales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    /// Coordinated Universal Time.
+    Utc,
+    /// International Atomic Time.
+    Tai,
+    /// Terrestrial Time, used by the precession and nutation models.
+    Tt,
+    /// Universal Time, following the Earth's rotation.
+    Ut1,
+}
+
+impl TimeScale {
+    /// Seconds to add to a UTC reading to get this scale's reading.
+    pub fn offset_from_utc(&self, time: DateTime<Utc>, eop: &EarthOrientation) -> f64 {
+        match self {
+            TimeScale::Utc => 0.0,
+            TimeScale::Tai => tai_minus_utc(time),
+            TimeScale::Tt => tai_minus_utc(time) + 32.184,
+            TimeScale::Ut1 => eop.ut1_minus_utc_s,
+        }
+    }
+
+    /// Calendar reading of this scale at the UTC instant `time`.
+    pub fn from_utc(&self, time: DateTime<Utc>, eop: &EarthOrientation) -> NaiveDateTime {
+        let offset = self.offset_from_utc(time, eop);
+        time.naive_utc() + chrono::Duration::nanoseconds((offset * 1e9).round() as i64)
+    }
+}
+
+/// `TAI - UTC` in seconds at the given instant, from the built-in leap second table.
+///
+/// Instants before 1972 use the 1972 offset.
+pub fn tai_minus_utc(time: DateTime<Utc>) -> f64 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(year, month, day, _)| {
+            let since = Utc
+                .with_ymd_and_hms(*year, *month, *day, 0, 0, 0)
+                .single()
+                .expect("leap second dates are valid");
+            time >= since
+        })
+        .map_or(10.0, |(_, _, _, offset)| *offset)
+}
+
+/// Julian date of the UTC instant `time`, counted in `scale`.
+pub fn julian_date(time: DateTime<Utc>, scale: TimeScale, eop: &EarthOrientation) -> f64 {
+    let seconds = time.timestamp() as f64 + f64::from(time.timestamp_subsec_nanos()) * 1e-9;
+    2_440_587.5 + (seconds + scale.offset_from_utc(time, eop)) / 86_400.0
+}
+
+/// Julian centuries since J2000.0 in `scale`.
+fn julian_centuries(time: DateTime<Utc>, scale: TimeScale, eop: &EarthOrientation) -> f64 {
+    (julian_date(time, scale, eop) - J2000_JD) / 36_525.0
+}
+
+/// Rotation of the coordinate axes about x by `angle` radians.
+pub fn rot1(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    [[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]]
+}
+
+/// Rotation of the coordinate axes about y by `angle` radians.
+pub fn rot2(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    [[c, 0.0, -s], [0.0, 1.0, 0.0], [s, 0.0, c]]
+}
+
+/// Rotation of the coordinate axes about z by `angle` radians.
+pub fn rot3(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    [[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]]
+}
+
+pub fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            *cell = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    out
+}
+
+pub fn transpose(m: &Matrix) -> Matrix {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in m.iter().enumerate() {
+        for (j, value) in row.iter().enumerate() {
+            out[j][i] = *value;
+        }
+    }
+    out
+}
+
+pub fn apply(m: &Matrix, v: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+/// IAU-1976 precession from J2000 to the mean-of-date frame, `t` in Julian
+/// centuries of TT.
+fn precession(t: f64) -> Matrix {
+    let zeta = (2306.2181 * t + 0.30188 * t * t + 0.017998 * t * t * t) * ARCSEC_TO_RAD;
+    let theta = (2004.3109 * t - 0.42665 * t * t - 0.041833 * t * t * t) * ARCSEC_TO_RAD;
+    let z = (2306.2181 * t + 1.09468 * t * t + 0.018203 * t * t * t) * ARCSEC_TO_RAD;
+    multiply(&multiply(&rot3(-z), &rot2(theta)), &rot3(-zeta))
+}
+
+/// Mean obliquity of the ecliptic (IAU-1980), in radians.
+fn mean_obliquity(t: f64) -> f64 {
+    (84_381.448 - 46.8150 * t - 0.00059 * t * t + 0.001813 * t * t * t) * ARCSEC_TO_RAD
+}
+
+/// IAU-1980 nutation terms: multipliers of (l, l', F, D, Ω), then
+/// Δψ and Δε coefficients in units of 0.1 mas (constant, per century).
+#[rustfmt::skip]
+const NUTATION_TERMS: &[([f64; 5], f64, f64, f64, f64)] = &[
+    ([0.0, 0.0, 0.0, 0.0, 1.0], -171_996.0, -174.2, 92_025.0, 8.9),
+    ([0.0, 0.0, 2.0, -2.0, 2.0], -13_187.0, -1.6, 5_736.0, -3.1),
+    ([0.0, 0.0, 2.0, 0.0, 2.0], -2_274.0, -0.2, 977.0, -0.5),
+    ([0.0, 0.0, 0.0, 0.0, 2.0], 2_062.0, 0.2, -895.0, 0.5),
+    ([0.0, 1.0, 0.0, 0.0, 0.0], 1_426.0, -3.4, 54.0, -0.1),
+    ([1.0, 0.0, 0.0, 0.0, 0.0], 712.0, 0.1, -7.0, 0.0),
+    ([0.0, 1.0, 2.0, -2.0, 2.0], -517.0, 1.2, 224.0, -0.6),
+    ([0.0, 0.0, 2.0, 0.0, 1.0], -386.0, -0.4, 200.0, 0.0),
+    ([1.0, 0.0, 2.0, 0.0, 2.0], -301.0, 0.0, 129.0, -0.1),
+    ([0.0, -1.0, 2.0, -2.0, 2.0], 217.0, -0.5, -95.0, 0.3),
+    ([1.0, 0.0, 0.0, -2.0, 0.0], -158.0, 0.0, -1.0, 0.0),
+    ([0.0, 0.0, 2.0, -2.0, 1.0], 129.0, 0.1, -70.0, 0.0),
+    ([-1.0, 0.0, 2.0, 0.0, 2.0], 123.0, 0.0, -53.0, 0.0),
+    ([1.0, 0.0, 0.0, 0.0, 1.0], 63.0, 0.1, -33.0, 0.0),
+    ([0.0, 0.0, 0.0, 2.0, 0.0], 63.0, 0.0, -2.0, 0.0),
+    ([-1.0, 0.0, 2.0, 2.0, 2.0], -59.0, 0.0, 26.0, 0.0),
+    ([-1.0, 0.0, 0.0, 0.0, 1.0], -58.0, -0.1, 32.0, 0.0),
+    ([1.0, 0.0, 2.0, 0.0, 1.0], -51.0, 0.0, 27.0, 0.0),
+    ([2.0, 0.0, 0.0, -2.0, 0.0], 48.0, 0.0, 1.0, 0.0),
+    ([-2.0, 0.0, 2.0, 0.0, 1.0], 46.0, 0.0, -24.0, 0.0),
+    ([0.0, 0.0, 2.0, 2.0, 2.0], -38.0, 0.0, 16.0, 0.0),
+    ([2.0, 0.0, 2.0, 0.0, 2.0], -31.0, 0.0, 13.0, 0.0),
+    ([2.0, 0.0, 0.0, 0.0, 0.0], 29.0, 0.0, -1.0, 0.0),
+    ([1.0, 0.0, 2.0, -2.0, 2.0], 29.0, 0.0, -12.0, 0.0),
+    ([0.0, 0.0, 2.0, 0.0, 0.0], 26.0, 0.0, -1.0, 0.0),
+    ([0.0, 0.0, 2.0, -2.0, 0.0], -22.0, 0.0, 0.0, 0.0),
+    ([-1.0, 0.0, 2.0, 0.0, 1.0], 21.0, 0.0, -10.0, 0.0),
+    ([0.0, 2.0, 0.0, 0.0, 0.0], 17.0, -0.1, 0.0, 0.0),
+    ([0.0, 2.0, 2.0, -2.0, 2.0], -16.0, 0.1, 7.0, 0.0),
+    ([-1.0, 0.0, 0.0, 2.0, 1.0], 16.0, 0.0, -8.0, 0.0),
+    ([0.0, 1.0, 0.0, 0.0, 1.0], -15.0, 0.0, 9.0, 0.0),
+    ([1.0, 0.0, 0.0, -2.0, 1.0], -13.0, 0.0, 7.0, 0.0),
+    ([0.0, -1.0, 0.0, 0.0, 1.0], -12.0, 0.0, 6.0, 0.0),
+    ([2.0, 0.0, -2.0, 0.0, 0.0], 11.0, 0.0, 0.0, 0.0),
+    ([-1.0, 0.0, 2.0, 2.0, 1.0], -10.0, 0.0, 5.0, 0.0),
+    ([1.0, 0.0, 2.0, 2.0, 2.0], -8.0, 0.0, 3.0, 0.0),
+    ([0.0, -1.0, 2.0, 0.0, 2.0], -7.0, 0.0, 3.0, 0.0),
+    ([0.0, 0.0, 2.0, 2.0, 1.0], -7.0, 0.0, 3.0, 0.0),
+    ([1.0, 1.0, 0.0, -2.0, 0.0], -7.0, 0.0, 0.0, 0.0),
+    ([0.0, 1.0, 2.0, 0.0, 2.0], 7.0, 0.0, -3.0, 0.0),
+    ([-2.0, 0.0, 0.0, 2.0, 1.0], -6.0, 0.0, 3.0, 0.0),
+    ([0.0, 0.0, 0.0, 2.0, 1.0], -6.0, 0.0, 3.0, 0.0),
+    ([2.0, 0.0, 2.0, -2.0, 2.0], 6.0, 0.0, -3.0, 0.0),
+    ([1.0, 0.0, 0.0, 2.0, 0.0], 6.0, 0.0, 0.0, 0.0),
+    ([1.0, 0.0, 2.0, -2.0, 1.0], 6.0, 0.0, -3.0, 0.0),
+    ([0.0, 0.0, 0.0, -2.0, 1.0], -5.0, 0.0, 3.0, 0.0),
+    ([0.0, -1.0, 2.0, -2.0, 1.0], -5.0, 0.0, 3.0, 0.0),
+    ([2.0, 0.0, 2.0, 0.0, 1.0], -5.0, 0.0, 3.0, 0.0),
+    ([1.0, -1.0, 0.0, 0.0, 0.0], 5.0, 0.0, 0.0, 0.0),
+];
+
+/// Nutation in longitude and obliquity (IAU-1980), in radians.
+fn nutation(t: f64) -> (f64, f64) {
+    let degrees = |base: f64, arcsec: [f64; 4]| {
+        let seconds =
+            arcsec[0] * t + arcsec[1] * t * t + arcsec[2] * t.powi(3) + arcsec[3] * t.powi(4);
+        (base + seconds / 3600.0).to_radians()
+    };
+    // Delaunay arguments: Moon and Sun mean anomalies, Moon argument of latitude,
+    // Moon elongation and longitude of the Moon's ascending node
+    let arguments = [
+        degrees(
+            134.96340251,
+            [1717915923.2178, 31.8792, 0.051635, -0.00024470],
+        ),
+        degrees(
+            357.52910918,
+            [129596581.0481, -0.5532, 0.000136, -0.00001149],
+        ),
+        degrees(
+            93.27209062,
+            [1739527262.8478, -12.7512, -0.001037, 0.00000417],
+        ),
+        degrees(
+            297.85019547,
+            [1602961601.2090, -6.3706, 0.006593, -0.00003169],
+        ),
+        degrees(125.04455501, [-6962890.2665, 7.4722, 0.007702, -0.00005939]),
+    ];
+
+    let (mut dpsi, mut deps) = (0.0, 0.0);
+    for (multipliers, psi, psi_t, eps, eps_t) in NUTATION_TERMS {
+        let angle: f64 = multipliers
+            .iter()
+            .zip(arguments.iter())
+            .map(|(m, a)| m * a)
+            .sum();
+        dpsi += (psi + psi_t * t) * angle.sin();
+        deps += (eps + eps_t * t) * angle.cos();
+    }
+    (dpsi * 1e-4 * ARCSEC_TO_RAD, deps * 1e-4 * ARCSEC_TO_RAD)
+}
+
+/// Rotation from TEME to the mean equator and equinox of J2000, through the
+/// true-of-date and mean-of-date frames. `corrections` are added to the modelled
+/// nutation, in radians.
+fn teme_to_mean_j2000(time: DateTime<Utc>, corrections: (f64, f64)) -> Matrix {
+    let t = julian_centuries(time, TimeScale::Tt, &EarthOrientation::default());
+    let (dpsi, deps) = nutation(t);
+    let (dpsi, deps) = (dpsi + corrections.0, deps + corrections.1);
+    let mean_eps = mean_obliquity(t);
+    let true_eps = mean_eps + deps;
+    let equation_of_equinoxes = dpsi * mean_eps.cos();
+
+    let teme_to_tod = rot3(-equation_of_equinoxes);
+    let nutation = multiply(&multiply(&rot1(-true_eps), &rot3(-dpsi)), &rot1(mean_eps));
+    let tod_to_mod = transpose(&nutation);
+    let mod_to_j2000 = transpose(&precession(t));
+    multiply(&mod_to_j2000, &multiply(&tod_to_mod, &teme_to_tod))
+}
+
+/// Greenwich mean sidereal time (IAU-1982), in radians.
+pub fn gmst(time: DateTime<Utc>, eop: &EarthOrientation) -> f64 {
+    let t = julian_centuries(time, TimeScale::Ut1, eop);
+    let seconds = 67_310.548_41 + (876_600.0 * 3600.0 + 8_640_184.812_866) * t + 0.093_104 * t * t
+        - 6.2e-6 * t * t * t;
+    (seconds % 86_400.0 / 240.0)
+        .to_radians()
+        .rem_euclid(std::f64::consts::TAU)
+}
+
+/// Reference frames for Cartesian states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frame {
+    /// True equator, mean equinox: the output frame of SGP4.
+    Teme,
+    /// Mean equator and equinox of J2000.0 (FK5, without nutation corrections).
+    J2000,
+    /// Geocentric celestial reference frame, realised through FK5 with the IERS
+    /// nutation corrections.
+    Gcrf,
+    /// International terrestrial reference frame (Earth-fixed).
+    Itrf,
+}
+
+impl From<SensorFrame> for Frame {
+    fn from(frame: SensorFrame) -> Self {
+        match frame {
+            SensorFrame::TEME => Frame::Teme,
+            SensorFrame::J2000 => Frame::J2000,
+        }
+    }
+}
+
+/// Rotation from TEME to an inertial frame.
+fn teme_to_inertial(frame: Frame, time: DateTime<Utc>, eop: &EarthOrientation) -> Matrix {
+    match frame {
+        Frame::Teme => rot3(0.0),
+        Frame::J2000 => teme_to_mean_j2000(time, (0.0, 0.0)),
+        Frame::Gcrf => teme_to_mean_j2000(
+            time,
+            (
+                eop.dpsi_arcsec * ARCSEC_TO_RAD,
+                eop.deps_arcsec * ARCSEC_TO_RAD,
+            ),
+        ),
+        Frame::Itrf => unreachable!("ITRF is not inertial"),
+    }
+}
+
+/// Polar motion, taking pseudo Earth-fixed coordinates to ITRF.
+fn polar_motion(eop: &EarthOrientation) -> Matrix {
+    multiply(
+        &rot2(-eop.polar_x_arcsec * ARCSEC_TO_RAD),
+        &rot1(-eop.polar_y_arcsec * ARCSEC_TO_RAD),
+    )
+}
+
+/// Rotation taking coordinates in `from` to coordinates in `to` at `time`.
+///
+/// This orients directions and positions; use [`transform`] for velocities,
+/// which also need the Earth's rotation when one frame is ITRF.
+pub fn rotation(from: Frame, to: Frame, time: DateTime<Utc>, eop: &EarthOrientation) -> Matrix {
+    let to_teme = |frame: Frame| match frame {
+        Frame::Itrf => transpose(&multiply(&polar_motion(eop), &rot3(gmst(time, eop)))),
+        inertial => transpose(&teme_to_inertial(inertial, time, eop)),
+    };
+    let from_teme = transpose(&to_teme(to));
+    multiply(&from_teme, &to_teme(from))
+}
+
+/// Converts a position and velocity between frames at `time`.
+pub fn transform(
+    position: [f64; 3],
+    velocity: [f64; 3],
+    from: Frame,
+    to: Frame,
+    time: DateTime<Utc>,
+    eop: &EarthOrientation,
+) -> ([f64; 3], [f64; 3]) {
+    if from == to {
+        return (position, velocity);
+    }
+    let earth_rotation = rot3(gmst(time, eop));
+    let polar_motion = polar_motion(eop);
+
+    // bring the state into TEME
+    let (position, velocity) = match from {
+        Frame::Itrf => {
+            let position = apply(&transpose(&polar_motion), position);
+            let velocity = apply(&transpose(&polar_motion), velocity);
+            // add back the velocity of the rotating frame, ω × r
+            let velocity = [
+                velocity[0] - EARTH_ROTATION_RATE * position[1],
+                velocity[1] + EARTH_ROTATION_RATE * position[0],
+                velocity[2],
+            ];
+            let to_teme = transpose(&earth_rotation);
+            (apply(&to_teme, position), apply(&to_teme, velocity))
+        }
+        inertial => {
+            let to_teme = transpose(&teme_to_inertial(inertial, time, eop));
+            (apply(&to_teme, position), apply(&to_teme, velocity))
+        }
+    };
+
+    match to {
+        Frame::Itrf => {
+            let position = apply(&earth_rotation, position);
+            let velocity = apply(&earth_rotation, velocity);
+            let velocity = [
+                velocity[0] + EARTH_ROTATION_RATE * position[1],
+                velocity[1] - EARTH_ROTATION_RATE * position[0],
+                velocity[2],
+            ];
+            (
+                apply(&polar_motion, position),
+                apply(&polar_motion, velocity),
+            )
+        }
+        inertial => {
+            let from_teme = teme_to_inertial(inertial, time, eop);
+            (apply(&from_teme, position), apply(&from_teme, velocity))
+        }
+    }
+}
+
+/// Earth-fixed position, in km, of a geodetic (WGS-84) location.
+pub fn geodetic_to_ecef(latitude: Degrees, longitude: Degrees, altitude: Km) -> [f64; 3] {
+    let (sin_lat, cos_lat) = latitude.value().to_radians().sin_cos();
+    let (sin_lon, cos_lon) = longitude.value().to_radians().sin_cos();
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let n = WGS84_RADIUS_KM / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+    let altitude = altitude.value();
+    [
+        (n + altitude) * cos_lat * cos_lon,
+        (n + altitude) * cos_lat * sin_lon,
+        (n * (1.0 - e2) + altitude) * sin_lat,
+    ]
+}
+
+/// Geodetic (WGS-84) latitude, longitude and altitude of an Earth-fixed position in km.
+pub fn ecef_to_geodetic(position: [f64; 3]) -> (Degrees, Degrees, Km) {
+    let [x, y, z] = position;
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let p = x.hypot(y);
+    let mut latitude = z.atan2(p * (1.0 - e2));
+    let mut n = WGS84_RADIUS_KM;
+    for _ in 0..10 {
+        let sin_lat = latitude.sin();
+        n = WGS84_RADIUS_KM / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        latitude = (z + e2 * n * sin_lat).atan2(p);
+    }
+    let (sin_lat, cos_lat) = latitude.sin_cos();
+    let altitude = p * cos_lat + (z + e2 * n * sin_lat) * sin_lat - n;
+    (
+        Degrees(latitude.to_degrees()),
+        Degrees(y.atan2(x).to_degrees()),
+        Km(altitude),
+    )
+}
+
+/// Rotation from Earth-fixed axes to local east/north/up axes at a site.
+pub fn ecef_to_enu(latitude: Degrees, longitude: Degrees) -> Matrix {
+    let (sin_lat, cos_lat) = latitude.value().to_radians().sin_cos();
+    let (sin_lon, cos_lon) = longitude.value().to_radians().sin_cos();
+    [
+        [-sin_lon, cos_lon, 0.0],
+        [-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat],
+        [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat],
+    ]
+}
+
+/// Azimuth (from north towards east), elevation and range of an Earth-fixed
+/// position in km, seen from a geodetic site.
+pub fn ecef_to_topocentric(
+    latitude: Degrees,
+    longitude: Degrees,
+    altitude: Km,
+    position: [f64; 3],
+) -> (Degrees, Degrees, Km) {
+    let site = geodetic_to_ecef(latitude, longitude, altitude);
+    let offset = [
+        position[0] - site[0],
+        position[1] - site[1],
+        position[2] - site[2],
+    ];
+    let [east, north, up] = apply(&ecef_to_enu(latitude, longitude), offset);
+    let (azimuth, elevation) = enu_to_azel([east, north, up]);
+    (azimuth, elevation, Km(east.hypot(north).hypot(up)))
+}
+
+/// Earth-fixed position, in km, at the given azimuth, elevation and range from a
+/// geodetic site.
+pub fn topocentric_to_ecef(
+    latitude: Degrees,
+    longitude: Degrees,
+    altitude: Km,
+    azimuth: Degrees,
+    elevation: Degrees,
+    range: Km,
+) -> [f64; 3] {
+    let site = geodetic_to_ecef(latitude, longitude, altitude);
+    let direction = azel_to_enu(azimuth, elevation);
+    let offset = apply(&transpose(&ecef_to_enu(latitude, longitude)), direction);
+    let range = range.value();
+    [
+        site[0] + range * offset[0],
+        site[1] + range * offset[1],
+        site[2] + range * offset[2],
+    ]
+}
+
+fn enu_to_azel([east, north, up]: [f64; 3]) -> (Degrees, Degrees) {
+    (
+        Degrees(east.atan2(north).to_degrees().rem_euclid(360.0)),
+        Degrees(up.atan2(east.hypot(north)).to_degrees()),
+    )
+}
+
+fn azel_to_enu(azimuth: Degrees, elevation: Degrees) -> [f64; 3] {
+    let (sin_az, cos_az) = azimuth.value().to_radians().sin_cos();
+    let (sin_el, cos_el) = elevation.value().to_radians().sin_cos();
+    [cos_el * sin_az, cos_el * cos_az, sin_el]
+}
+
+/// Rotation from `frame` to the east/north/up axes at a groundstation.
+fn inertial_to_enu(
+    groundstation: &Groundstation,
+    time: DateTime<Utc>,
+    frame: SensorFrame,
+    eop: &EarthOrientation,
+) -> Matrix {
+    multiply(
+        &ecef_to_enu(groundstation.latitude, groundstation.longitude),
+        &rotation(frame.into(), Frame::Itrf, time, eop),
+    )
+}
+
+/// Azimuth and elevation of the topocentric direction `(ra, dec)` in `frame`,
+/// seen from a groundstation at `time`.
+pub fn radec_to_azel(
+    groundstation: &Groundstation,
+    time: DateTime<Utc>,
+    frame: SensorFrame,
+    right_ascension: Degrees,
+    declination: Degrees,
+    eop: &EarthOrientation,
+) -> (Degrees, Degrees) {
+    let (sin_ra, cos_ra) = right_ascension.value().to_radians().sin_cos();
+    let (sin_dec, cos_dec) = declination.value().to_radians().sin_cos();
+    let direction = [cos_dec * cos_ra, cos_dec * sin_ra, sin_dec];
+    let enu = apply(&inertial_to_enu(groundstation, time, frame, eop), direction);
+    enu_to_azel(enu)
+}
+
+/// Topocentric right ascension and declination in `frame` of the direction at
+/// `(azimuth, elevation)` from a groundstation at `time`.
+pub fn azel_to_radec(
+    groundstation: &Groundstation,
+    time: DateTime<Utc>,
+    frame: SensorFrame,
+    azimuth: Degrees,
+    elevation: Degrees,
+    eop: &EarthOrientation,
+) -> (Degrees, Degrees) {
+    let enu = azel_to_enu(azimuth, elevation);
+    let [x, y, z] = apply(
+        &transpose(&inertial_to_enu(groundstation, time, frame, eop)),
+        enu,
+    );
+    (
+        Degrees(y.atan2(x).to_degrees().rem_euclid(360.0)),
+        Degrees(z.atan2(x.hypot(y)).to_degrees()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: [f64; 3], expected: [f64; 3], tolerance: f64) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!(
+                (a - e).abs() < tolerance,
+                "{:?} differs from {:?}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    /// Vallado, Crawford, Hujsak and Kelso, "Revisiting Spacetrack Report #3" (2006),
+    /// TEME example.
+    fn vallado_epoch() -> (DateTime<Utc>, EarthOrientation) {
+        let eop = EarthOrientation {
+            ut1_minus_utc_s: -0.4399619,
+            polar_x_arcsec: -0.140682,
+            polar_y_arcsec: 0.333309,
+            dpsi_arcsec: -0.052195,
+            deps_arcsec: -0.003875,
+        };
+        ("2004-04-06T07:51:28.386009Z".parse().unwrap(), eop)
+    }
+
+    const R_TEME: [f64; 3] = [5094.18016210, 6127.64465950, 6380.34453270];
+    const V_TEME: [f64; 3] = [-4.746131487, 0.785818041, 5.531931288];
+
+    #[test]
+    fn teme_to_itrf_matches_vallado() {
+        let (time, eop) = vallado_epoch();
+        let (r, v) = transform(R_TEME, V_TEME, Frame::Teme, Frame::Itrf, time, &eop);
+        assert_close(r, [-1033.4793830, 7901.2952754, 6380.3565958], 1e-4);
+        assert_close(v, [-3.225636520, -2.872451450, 5.531924446], 1e-7);
+
+        let (r, v) = transform(r, v, Frame::Itrf, Frame::Teme, time, &eop);
+        assert_close(r, R_TEME, 1e-8);
+        assert_close(v, V_TEME, 1e-11);
+    }
+
+    #[test]
+    fn teme_to_gcrf_matches_vallado() {
+        let (time, eop) = vallado_epoch();
+        let (r, v) = transform(R_TEME, V_TEME, Frame::Teme, Frame::Gcrf, time, &eop);
+        assert_close(r, [5102.508958, 6123.011401, 6378.136928], 1e-3);
+        assert_close(v, [-4.74322016, 0.79053650, 5.53375528], 1e-6);
+
+        // ITRF -> GCRF goes through TEME and lands in the same place
+        let (r_itrf, v_itrf) = transform(R_TEME, V_TEME, Frame::Teme, Frame::Itrf, time, &eop);
+        let (r_gcrf, v_gcrf) = transform(r_itrf, v_itrf, Frame::Itrf, Frame::Gcrf, time, &eop);
+        assert_close(r_gcrf, r, 1e-8);
+        assert_close(v_gcrf, v, 1e-11);
+    }
+
+    #[test]
+    fn teme_to_j2000_ignores_nutation_corrections() {
+        let (time, eop) = vallado_epoch();
+        let with = rotation(Frame::Teme, Frame::J2000, time, &eop);
+        let without = rotation(
+            Frame::Teme,
+            Frame::J2000,
+            time,
+            &EarthOrientation::default(),
+        );
+        assert_eq!(with, without);
+        assert_close(
+            apply(&with, R_TEME),
+            [5102.5096, 6123.0115, 6378.1363],
+            1e-3,
+        );
+    }
+
+    #[test]
+    fn gmst_matches_vallado_example_3_5() {
+        // 1992-08-20 12:14 UT1
+        let time = "1992-08-20T12:14:00Z".parse().unwrap();
+        let gmst = gmst(time, &EarthOrientation::default()).to_degrees();
+        assert!((gmst - 152.578787886).abs() < 1e-6, "{}", gmst);
+    }
+
+    #[test]
+    fn time_scales() {
+        let eop = EarthOrientation {
+            ut1_minus_utc_s: -0.4399619,
+            ..EarthOrientation::default()
+        };
+        let before = "2016-12-31T23:59:59Z".parse().unwrap();
+        let after = "2017-01-01T00:00:00Z".parse().unwrap();
+        assert_eq!(tai_minus_utc(before), 36.0);
+        assert_eq!(tai_minus_utc(after), 37.0);
+        assert_eq!(TimeScale::Tt.offset_from_utc(after, &eop), 69.184);
+        assert_eq!(TimeScale::Ut1.offset_from_utc(after, &eop), -0.4399619);
+
+        // J2000.0 is 2000-01-01 12:00 TT
+        let j2000 = "2000-01-01T11:58:55.816Z".parse().unwrap();
+        assert!((julian_date(j2000, TimeScale::Tt, &eop) - J2000_JD).abs() < 1e-9);
+        assert_eq!(
+            TimeScale::Tt.from_utc(j2000, &eop).to_string(),
+            "2000-01-01 12:00:00"
+        );
+    }
+
+    #[test]
+    fn geodetic_matches_vallado_example_3_3() {
+        let (latitude, longitude, altitude) = ecef_to_geodetic([6524.834, 6862.875, 6448.296]);
+        assert!((latitude.value() - 34.352496).abs() < 1e-5, "{}", latitude);
+        assert!((longitude.value() - 46.4464).abs() < 1e-4, "{}", longitude);
+        assert!((altitude.value() - 5085.22).abs() < 1e-2, "{}", altitude);
+
+        let position = geodetic_to_ecef(latitude, longitude, altitude);
+        assert_close(position, [6524.834, 6862.875, 6448.296], 1e-6);
+    }
+
+    #[test]
+    fn topocentric_round_trip() {
+        let (latitude, longitude, altitude) = (Degrees(40.0), Degrees(-105.0), Km(1.6));
+        let position = topocentric_to_ecef(
+            latitude,
+            longitude,
+            altitude,
+            Degrees(135.0),
+            Degrees(25.0),
+            Km(1500.0),
+        );
+        let (azimuth, elevation, range) =
+            ecef_to_topocentric(latitude, longitude, altitude, position);
+        assert_close(
+            [azimuth.value(), elevation.value(), range.value()],
+            [135.0, 25.0, 1500.0],
+            1e-8,
+        );
+    }
+
+    #[test]
+    fn zenith_points_at_local_sidereal_time() {
+        let time: DateTime<Utc> = "2024-03-20T03:00:00Z".parse().unwrap();
+        let groundstation = Groundstation {
+            id: "gs".to_string(),
+            name: "Boulder".to_string(),
+            latitude: Degrees(40.015),
+            longitude: Degrees(-105.27),
+            altitude: 1655.0,
+            user_id: "user".to_string(),
+            created_at: time,
+            updated_at: time,
+        };
+        let eop = EarthOrientation::default();
+        let (ra, dec) = azel_to_radec(
+            &groundstation,
+            time,
+            SensorFrame::TEME,
+            Degrees(0.0),
+            Degrees(90.0),
+            &eop,
+        );
+        let lst = gmst(time, &eop).to_degrees() + groundstation.longitude.value();
+        assert!((ra.value() - lst.rem_euclid(360.0)).abs() < 1e-8);
+        assert!((dec - groundstation.latitude).value().abs() < 1e-8);
+
+        let (azimuth, elevation) = radec_to_azel(
+            &groundstation,
+            time,
+            SensorFrame::J2000,
+            Degrees(80.0),
+            Degrees(20.0),
+            &eop,
+        );
+        let (ra, dec) = azel_to_radec(
+            &groundstation,
+            time,
+            SensorFrame::J2000,
+            azimuth,
+            elevation,
+            &eop,
+        );
+        assert_close([ra.value(), dec.value(), 0.0], [80.0, 20.0, 0.0], 1e-9);
+    }
+}
@@ -1,196 +1,270 @@
+pub mod agent;
+mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
+mod concurrency;
+#[cfg(feature = "config")]
+pub mod config;
 mod entities;
 mod error;
+pub mod frames;
+mod pagination;
+pub mod propagation;
+mod request;
+mod retry;
+pub mod solver;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod units;
+mod validation;
 
 // Re-export types for public API
+pub use async_trait::async_trait;
+#[cfg(feature = "config")]
+pub use auth::CommandToken;
+pub use auth::{AuthProvider, ClientCredentials, EnvToken, FileToken, StaticToken};
+pub use builder::CitraClientBuilder;
+pub use concurrency::{Precondition, Versioned};
 pub use entities::access::{
     FOVAccessRequest, FOVAccessResponse, HorizonAccess, SatelliteAccessToGroundstationRequest,
-    SensorFrame,
+    SensorFrame, TrackingParameters,
 };
-pub use entities::antenna::Antenna;
-pub use entities::groundstation::Groundstation;
-pub use entities::rf_observation::{CreateRFCaptureRequest, RFCapture, RFCaptureSummary};
-pub use entities::task::{CreateTaskRequest, Task, TaskStatus, TaskUpdateRequest};
-pub use entities::telescope::Telescope;
-pub use error::LemonaidError;
+pub use entities::antenna::{Antenna, AntennaCreateRequest, AntennaUpdateRequest};
+pub use entities::elset::{ElementSet, ElementSetUploadRequest, Omm, Tle};
+pub use entities::groundstation::{
+    Groundstation, GroundstationCreateRequest, GroundstationUpdateRequest,
+};
+pub use entities::rf_observation::{
+    CreateRFCaptureRequest, RFCapture, RFCaptureData, RFCaptureSummary, RFDetection,
+    RFPowerSpectralDensity,
+};
+pub use entities::satellite::{Satellite, SatelliteFrequency, SatelliteQuery, SatelliteStatus};
+pub use entities::task::{
+    CreateTaskRequest, SortOrder, Task, TaskQuery, TaskSortField, TaskStatus, TaskUpdateRequest,
+};
+pub use entities::telescope::{Telescope, TelescopeCreateRequest, TelescopeUpdateRequest};
+pub use error::{ElsetError, FieldError, LemonaidError, PropagationError};
+pub use pagination::{Page, PageRequest};
+pub use propagation::{Propagator, StateVector};
+pub use retry::RetryPolicy;
+pub use validation::Validate;
 
-use crate::entities::groundstation::GroundstationCreateRequest;
+use std::sync::Arc;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_util::Stream;
+
+use crate::request::ApiRequest;
 
 pub struct CitraClient {
-    base_url: String,
-    api_key: String,
+    base_url: reqwest::Url,
+    auth: Arc<dyn AuthProvider>,
     client: reqwest::Client,
+    retry_policy: RetryPolicy,
 }
 
 impl CitraClient {
+    /// Creates a client for the hosted dev or prod API with default settings.
+    ///
+    /// Use [`CitraClient::builder`] for a custom base URL, timeouts, proxies or
+    /// authentication other than a personal access token.
     pub fn new(api_key: &str, dev: bool) -> Self {
-        if !dev {
-            return CitraClient {
-                base_url: "https://api.citra.space/".to_string(),
-                api_key: api_key.to_string(),
-                client: reqwest::Client::new(),
-            };
-        }
-        CitraClient {
-            base_url: "https://dev.api.citra.space/".to_string(),
-            api_key: api_key.to_string(),
-            client: reqwest::Client::new(),
-        }
-    }
-
-    /// Helper method to check response status and return appropriate error
-    async fn check_response(
-        &self,
-        response: reqwest::Response,
-    ) -> Result<reqwest::Response, LemonaidError> {
-        let status = response.status();
-        if status.is_success() {
-            Ok(response)
-        } else {
-            let message = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            Err(LemonaidError::Api { status, message })
-        }
+        CitraClientBuilder::new(api_key)
+            .dev(dev)
+            .build()
+            .expect("default HTTP client configuration should be valid")
+    }
+
+    pub fn builder(api_key: &str) -> CitraClientBuilder {
+        CitraClientBuilder::new(api_key)
     }
 
     pub async fn get_telescope(&self, telescope_id: &str) -> Result<Telescope, LemonaidError> {
-        let url = format!("{}telescopes/{}", self.base_url, telescope_id);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let telescope = response.json::<Telescope>().await?;
-        Ok(telescope)
+        self.execute(ApiRequest::get(&["telescopes", telescope_id]))
+            .await
+    }
+
+    /// Fetches a telescope together with its `ETag`, for a later conditional
+    /// [`patch_telescope`](Self::patch_telescope).
+    pub async fn get_telescope_versioned(
+        &self,
+        telescope_id: &str,
+    ) -> Result<Versioned<Telescope>, LemonaidError> {
+        self.execute_versioned(ApiRequest::get(&["telescopes", telescope_id]))
+            .await
     }
 
     pub async fn list_telescopes(&self) -> Result<Vec<Telescope>, LemonaidError> {
-        let url = format!("{}telescopes", self.base_url);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+        self.execute(ApiRequest::get(&["telescopes"])).await
+    }
+
+    pub async fn list_telescopes_page(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<Telescope>, LemonaidError> {
+        let items = self
+            .execute(page.apply(ApiRequest::get(&["telescopes"])))
             .await?;
-        let response = self.check_response(response).await?;
-        let telescopes = response.json::<Vec<Telescope>>().await?;
-        Ok(telescopes)
+        Ok(Page {
+            items,
+            request: page,
+        })
+    }
+
+    /// Streams all telescopes, fetching `page_size` at a time.
+    pub fn stream_telescopes(
+        &self,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<Telescope, LemonaidError>> + '_ {
+        pagination::paginate(page_size, move |page| self.list_telescopes_page(page))
     }
 
     pub async fn create_telescope(
         &self,
-        telescope: &Telescope,
+        telescope: &TelescopeCreateRequest,
     ) -> Result<Telescope, LemonaidError> {
-        // API only implements a bulk create endpoint for telescopes, so we wrap the single telescope in a vector
-        let url = format!("{}telescopes", self.base_url);
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&vec![telescope])
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let telescopes = response.json::<Vec<Telescope>>().await?;
-        Ok(telescopes.into_iter().next().unwrap())
+        // API only implements a bulk create endpoint for telescopes, so we wrap the single telescope in a slice
+        self.create_telescopes(std::slice::from_ref(telescope))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(LemonaidError::EmptyBulkResponse)
+    }
+
+    pub async fn create_telescopes(
+        &self,
+        telescopes: &[TelescopeCreateRequest],
+    ) -> Result<Vec<Telescope>, LemonaidError> {
+        telescopes.validate()?;
+        self.execute(ApiRequest::post(&["telescopes"]).json(telescopes)?)
+            .await
     }
 
     pub async fn delete_telescope(&self, telescope_id: &str) -> Result<(), LemonaidError> {
         // API only implements a bulk delete endpoint, with a vector of IDs
-        let url = format!("{}telescopes", self.base_url);
-        let response = self
-            .client
-            .delete(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&vec![telescope_id])
-            .send()
-            .await?;
-        self.check_response(response).await?;
-        Ok(())
+        self.delete_telescopes(&[telescope_id]).await
+    }
+
+    pub async fn delete_telescopes(&self, telescope_ids: &[&str]) -> Result<(), LemonaidError> {
+        self.execute_empty(ApiRequest::delete(&["telescopes"]).json(telescope_ids)?)
+            .await
     }
 
     pub async fn update_telescope(
         &self,
         telescope: &Telescope,
     ) -> Result<Telescope, LemonaidError> {
-        // API only implements a bulk update endpoint for telescopes, so we wrap the single telescope in a vector
-        let url = format!("{}telescopes", self.base_url);
-        let response = self
-            .client
-            .put(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&vec![telescope])
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let telescopes = response.json::<Vec<Telescope>>().await?;
-        Ok(telescopes.into_iter().next().unwrap())
+        // API only implements a bulk update endpoint for telescopes, so we wrap the single telescope in a slice
+        self.update_telescopes(std::slice::from_ref(telescope))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(LemonaidError::EmptyBulkResponse)
+    }
+
+    pub async fn update_telescopes(
+        &self,
+        telescopes: &[Telescope],
+    ) -> Result<Vec<Telescope>, LemonaidError> {
+        telescopes.validate()?;
+        self.execute(ApiRequest::put(&["telescopes"]).json(telescopes)?)
+            .await
+    }
+
+    /// Changes only the fields set in `update`. Fails with
+    /// [`LemonaidError::Conflict`] if its precondition no longer holds.
+    pub async fn patch_telescope(
+        &self,
+        telescope_id: &str,
+        update: &TelescopeUpdateRequest,
+    ) -> Result<Telescope, LemonaidError> {
+        update.validate()?;
+        self.execute(
+            ApiRequest::patch(&["telescopes", telescope_id])
+                .precondition(update.precondition.as_ref())
+                .json(update)?,
+        )
+        .await
     }
 
     pub async fn get_groundstation(
         &self,
         groundstation_id: &str,
     ) -> Result<Groundstation, LemonaidError> {
-        let url = format!("{}ground-stations/{}", self.base_url, groundstation_id);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let groundstation = response.json::<Groundstation>().await?;
-        Ok(groundstation)
+        self.execute(ApiRequest::get(&["ground-stations", groundstation_id]))
+            .await
+    }
+
+    /// Fetches a ground station together with its `ETag`, for a later
+    /// conditional [`patch_groundstation`](Self::patch_groundstation).
+    pub async fn get_groundstation_versioned(
+        &self,
+        groundstation_id: &str,
+    ) -> Result<Versioned<Groundstation>, LemonaidError> {
+        self.execute_versioned(ApiRequest::get(&["ground-stations", groundstation_id]))
+            .await
     }
 
     pub async fn list_groundstations(&self) -> Result<Vec<Groundstation>, LemonaidError> {
-        let url = format!("{}ground-stations", self.base_url);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let list_response = response
-            .json::<entities::groundstation::GroundstationListResponse>()
-            .await?;
+        let list_response: entities::groundstation::GroundstationListResponse =
+            self.execute(ApiRequest::get(&["ground-stations"])).await?;
         Ok(list_response.ground_stations)
     }
 
+    pub async fn list_groundstations_page(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<Groundstation>, LemonaidError> {
+        let list_response: entities::groundstation::GroundstationListResponse = self
+            .execute(page.apply(ApiRequest::get(&["ground-stations"])))
+            .await?;
+        Ok(Page {
+            items: list_response.ground_stations,
+            request: page,
+        })
+    }
+
+    /// Streams all ground stations, fetching `page_size` at a time.
+    pub fn stream_groundstations(
+        &self,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<Groundstation, LemonaidError>> + '_ {
+        pagination::paginate(page_size, move |page| self.list_groundstations_page(page))
+    }
+
     pub async fn create_groundstation(
         &self,
         groundstation: &GroundstationCreateRequest,
     ) -> Result<Groundstation, LemonaidError> {
-        // API only implements a bulk create endpoint for groundstations, so we wrap the single groundstation in a vector
-        let url = format!("{}ground-stations", self.base_url);
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&vec![groundstation])
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let groundstations = response.json::<Vec<Groundstation>>().await?;
-        Ok(groundstations.into_iter().next().unwrap())
+        // API only implements a bulk create endpoint for groundstations, so we wrap the single groundstation in a slice
+        self.create_groundstations(std::slice::from_ref(groundstation))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(LemonaidError::EmptyBulkResponse)
+    }
+
+    pub async fn create_groundstations(
+        &self,
+        groundstations: &[GroundstationCreateRequest],
+    ) -> Result<Vec<Groundstation>, LemonaidError> {
+        groundstations.validate()?;
+        self.execute(ApiRequest::post(&["ground-stations"]).json(groundstations)?)
+            .await
     }
 
     pub async fn delete_groundstation(&self, groundstation_id: &str) -> Result<(), LemonaidError> {
         // API only implements a bulk delete endpoint, with a vector of IDs
-        let url = format!("{}ground-stations", self.base_url);
-        let response = self
-            .client
-            .delete(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&vec![groundstation_id])
-            .send()
-            .await?;
-        self.check_response(response).await?;
-        Ok(())
+        self.delete_groundstations(&[groundstation_id]).await
+    }
+
+    pub async fn delete_groundstations(
+        &self,
+        groundstation_ids: &[&str],
+    ) -> Result<(), LemonaidError> {
+        self.execute_empty(ApiRequest::delete(&["ground-stations"]).json(groundstation_ids)?)
+            .await
     }
 
     pub async fn update_groundstation(
@@ -198,71 +272,105 @@ impl CitraClient {
         groundstation_id: &str,
         groundstation: &GroundstationCreateRequest,
     ) -> Result<Groundstation, LemonaidError> {
-        // API only implements a bulk update endpoint for groundstations, so we wrap the single groundstation in a vector
-        let url = format!("{}ground-stations/{}", self.base_url, groundstation_id);
-        let response = self
-            .client
-            .put(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&vec![groundstation])
-            .send()
+        groundstation.validate()?;
+        // API only implements a bulk update endpoint for groundstations, so we wrap the single groundstation in a slice
+        let groundstations: Vec<Groundstation> = self
+            .execute(
+                ApiRequest::put(&["ground-stations", groundstation_id])
+                    .json(std::slice::from_ref(groundstation))?,
+            )
             .await?;
-        let response = self.check_response(response).await?;
-        let groundstations = response.json::<Vec<Groundstation>>().await?;
-        Ok(groundstations.into_iter().next().unwrap())
+        groundstations
+            .into_iter()
+            .next()
+            .ok_or(LemonaidError::EmptyBulkResponse)
+    }
+
+    /// Changes only the fields set in `update`. Fails with
+    /// [`LemonaidError::Conflict`] if its precondition no longer holds.
+    pub async fn patch_groundstation(
+        &self,
+        groundstation_id: &str,
+        update: &GroundstationUpdateRequest,
+    ) -> Result<Groundstation, LemonaidError> {
+        update.validate()?;
+        self.execute(
+            ApiRequest::patch(&["ground-stations", groundstation_id])
+                .precondition(update.precondition.as_ref())
+                .json(update)?,
+        )
+        .await
+    }
+
+    pub async fn query_tasks_for_groundstation(
+        &self,
+        groundstation_id: &str,
+        query: &TaskQuery,
+    ) -> Result<Vec<Task>, LemonaidError> {
+        self.execute(
+            ApiRequest::get(&["ground-stations", groundstation_id, "tasks"])
+                .query_pairs(query.to_query_pairs()),
+        )
+        .await
     }
 
     pub async fn solve_access_for_groundstation(
         &self,
         access_request: &SatelliteAccessToGroundstationRequest,
     ) -> Result<Vec<HorizonAccess>, LemonaidError> {
-        let url = format!(
-            "{}access/window/satellites_to_ground_station",
-            self.base_url
-        );
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(access_request)
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let accesses = response.json::<Vec<HorizonAccess>>().await?;
-        Ok(accesses)
+        access_request.validate()?;
+        self.execute(
+            ApiRequest::post(&["access", "window", "satellites_to_ground_station"])
+                .idempotent()
+                .json(access_request)?,
+        )
+        .await
     }
 
     pub async fn solve_fov_access(
         &self,
         fov_request: &FOVAccessRequest,
     ) -> Result<Vec<FOVAccessResponse>, LemonaidError> {
-        let url = format!("{}access/fov", self.base_url);
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(fov_request)
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let fov_responses = response.json::<Vec<FOVAccessResponse>>().await?;
-        Ok(fov_responses)
+        fov_request.validate()?;
+        self.execute(
+            ApiRequest::post(&["access", "fov"])
+                .idempotent()
+                .json(fov_request)?,
+        )
+        .await
     }
 
     pub async fn list_tasks_for_telescope(
         &self,
         telescope_id: &str,
     ) -> Result<Vec<Task>, LemonaidError> {
-        let url = format!("{}telescopes/{}/tasks", self.base_url, telescope_id);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+        self.execute(ApiRequest::get(&["telescopes", telescope_id, "tasks"]))
+            .await
+    }
+
+    pub async fn list_tasks_for_telescope_page(
+        &self,
+        telescope_id: &str,
+        page: PageRequest,
+    ) -> Result<Page<Task>, LemonaidError> {
+        let items = self
+            .execute(page.apply(ApiRequest::get(&["telescopes", telescope_id, "tasks"])))
             .await?;
-        let response = self.check_response(response).await?;
-        let tasks = response.json::<Vec<Task>>().await?;
-        Ok(tasks.into_iter().collect())
+        Ok(Page {
+            items,
+            request: page,
+        })
+    }
+
+    /// Streams all tasks of a telescope, fetching `page_size` at a time.
+    pub fn stream_tasks_for_telescope<'a>(
+        &'a self,
+        telescope_id: &'a str,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<Task, LemonaidError>> + 'a {
+        pagination::paginate(page_size, move |page| {
+            self.list_tasks_for_telescope_page(telescope_id, page)
+        })
     }
 
     pub async fn get_telescope_tasks_by_status(
@@ -270,138 +378,180 @@ impl CitraClient {
         telescope_id: &str,
         statuses: Vec<TaskStatus>,
     ) -> Result<Vec<Task>, LemonaidError> {
-        let status_params: Vec<String> = statuses
-            .iter()
-            .map(|s| format!("statuses={:?}", s))
-            .collect();
-        let query_string = status_params.join("&");
-        let url = format!(
-            "{}telescopes/{}/tasks?{}",
-            self.base_url, telescope_id, query_string
-        );
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let tasks = response.json::<Vec<Task>>().await?;
-        Ok(tasks.into_iter().collect())
+        self.query_tasks_for_telescope(telescope_id, &TaskQuery::new().statuses(statuses))
+            .await
+    }
+
+    pub async fn query_tasks_for_telescope(
+        &self,
+        telescope_id: &str,
+        query: &TaskQuery,
+    ) -> Result<Vec<Task>, LemonaidError> {
+        self.execute(
+            ApiRequest::get(&["telescopes", telescope_id, "tasks"])
+                .query_pairs(query.to_query_pairs()),
+        )
+        .await
     }
 
     pub async fn update_task(&self, task: &TaskUpdateRequest) -> Result<Task, LemonaidError> {
-        let url = format!("{}tasks/{}", self.base_url, task.id);
-        let response = self
-            .client
-            .put(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(task)
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let updated_task = response.json::<Task>().await?;
-        Ok(updated_task)
+        task.validate()?;
+        self.execute(ApiRequest::put(&["tasks", &task.id]).json(task)?)
+            .await
     }
 
     pub async fn create_task(&self, task: &CreateTaskRequest) -> Result<Task, LemonaidError> {
-        let url = format!("{}tasks", self.base_url);
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(task)
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let created_task = response.json::<Task>().await?;
-        Ok(created_task)
+        task.validate()?;
+        self.execute(ApiRequest::post(&["tasks"]).json(task)?).await
+    }
+
+    /// Lists tasks across all telescopes and antennas visible to the current user.
+    pub async fn list_tasks(&self, query: &TaskQuery) -> Result<Vec<Task>, LemonaidError> {
+        self.execute(ApiRequest::get(&["tasks"]).query_pairs(query.to_query_pairs()))
+            .await
+    }
+
+    /// Lists the tasks created by the current user.
+    pub async fn list_my_tasks(&self) -> Result<Vec<Task>, LemonaidError> {
+        self.execute(ApiRequest::get(&["users", "me", "tasks"]))
+            .await
+    }
+
+    pub async fn get_task(&self, task_id: &str) -> Result<Task, LemonaidError> {
+        self.execute(ApiRequest::get(&["tasks", task_id])).await
+    }
+
+    pub async fn delete_task(&self, task_id: &str) -> Result<(), LemonaidError> {
+        self.execute_empty(ApiRequest::delete(&["tasks", task_id]))
+            .await
+    }
+
+    /// Convenience over [`update_task`](Self::update_task) that sets the status to
+    /// [`TaskStatus::Canceled`].
+    pub async fn cancel_task(&self, task_id: &str) -> Result<Task, LemonaidError> {
+        self.update_task(&TaskUpdateRequest::new(task_id, TaskStatus::Canceled))
+            .await
+    }
+
+    pub async fn list_tasks_for_satellite(
+        &self,
+        satellite_id: &str,
+    ) -> Result<Vec<Task>, LemonaidError> {
+        self.execute(ApiRequest::get(&["satellites", satellite_id, "tasks"]))
+            .await
     }
 
     pub async fn list_antennas(&self) -> Result<Vec<Antenna>, LemonaidError> {
-        let url = format!("{}antennas", self.base_url);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+        self.execute(ApiRequest::get(&["antennas"])).await
+    }
+
+    pub async fn list_antennas_page(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<Antenna>, LemonaidError> {
+        let items = self
+            .execute(page.apply(ApiRequest::get(&["antennas"])))
             .await?;
-        let response = self.check_response(response).await?;
-        let antennas = response.json::<Vec<Antenna>>().await?;
-        Ok(antennas)
+        Ok(Page {
+            items,
+            request: page,
+        })
+    }
+
+    /// Streams all antennas, fetching `page_size` at a time.
+    pub fn stream_antennas(
+        &self,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<Antenna, LemonaidError>> + '_ {
+        pagination::paginate(page_size, move |page| self.list_antennas_page(page))
     }
 
     pub async fn get_antenna(&self, antenna_id: &str) -> Result<Antenna, LemonaidError> {
-        let url = format!("{}antennas/{}", self.base_url, antenna_id);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let antenna = response.json::<Antenna>().await?;
-        Ok(antenna)
-    }
-
-    pub async fn create_antenna(&self, antenna: &Antenna) -> Result<Antenna, LemonaidError> {
-        // API only implements a bulk create endpoint for antennas, so we wrap the single antenna in a vector
-        let url = format!("{}antennas", self.base_url);
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&vec![antenna])
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let antennas = response.json::<Vec<Antenna>>().await?;
-        Ok(antennas.into_iter().next().unwrap())
+        self.execute(ApiRequest::get(&["antennas", antenna_id]))
+            .await
+    }
+
+    /// Fetches an antenna together with its `ETag`, for a later conditional
+    /// [`patch_antenna`](Self::patch_antenna).
+    pub async fn get_antenna_versioned(
+        &self,
+        antenna_id: &str,
+    ) -> Result<Versioned<Antenna>, LemonaidError> {
+        self.execute_versioned(ApiRequest::get(&["antennas", antenna_id]))
+            .await
+    }
+
+    pub async fn create_antenna(
+        &self,
+        antenna: &AntennaCreateRequest,
+    ) -> Result<Antenna, LemonaidError> {
+        // API only implements a bulk create endpoint for antennas, so we wrap the single antenna in a slice
+        self.create_antennas(std::slice::from_ref(antenna))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(LemonaidError::EmptyBulkResponse)
+    }
+
+    pub async fn create_antennas(
+        &self,
+        antennas: &[AntennaCreateRequest],
+    ) -> Result<Vec<Antenna>, LemonaidError> {
+        antennas.validate()?;
+        self.execute(ApiRequest::post(&["antennas"]).json(antennas)?)
+            .await
     }
 
     pub async fn delete_antenna(&self, antenna_id: &str) -> Result<(), LemonaidError> {
         // API only implements a bulk delete endpoint, with a vector of IDs
-        let url = format!("{}antennas", self.base_url);
-        let response = self
-            .client
-            .delete(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&vec![antenna_id])
-            .send()
-            .await?;
-        self.check_response(response).await?;
-        Ok(())
+        self.delete_antennas(&[antenna_id]).await
+    }
+
+    pub async fn delete_antennas(&self, antenna_ids: &[&str]) -> Result<(), LemonaidError> {
+        self.execute_empty(ApiRequest::delete(&["antennas"]).json(antenna_ids)?)
+            .await
     }
 
     pub async fn update_antenna(&self, antenna: &Antenna) -> Result<Antenna, LemonaidError> {
-        // API only implements a bulk update endpoint for antennas, so we wrap the single antenna in a vector
-        let url = format!("{}antennas", self.base_url);
-        let response = self
-            .client
-            .put(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&vec![antenna])
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let antennas = response.json::<Vec<Antenna>>().await?;
-        Ok(antennas.into_iter().next().unwrap())
+        // API only implements a bulk update endpoint for antennas, so we wrap the single antenna in a slice
+        self.update_antennas(std::slice::from_ref(antenna))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(LemonaidError::EmptyBulkResponse)
+    }
+
+    pub async fn update_antennas(
+        &self,
+        antennas: &[Antenna],
+    ) -> Result<Vec<Antenna>, LemonaidError> {
+        antennas.validate()?;
+        self.execute(ApiRequest::put(&["antennas"]).json(antennas)?)
+            .await
+    }
+
+    /// Changes only the fields set in `update`. Fails with
+    /// [`LemonaidError::Conflict`] if its precondition no longer holds.
+    pub async fn patch_antenna(
+        &self,
+        antenna_id: &str,
+        update: &AntennaUpdateRequest,
+    ) -> Result<Antenna, LemonaidError> {
+        update.validate()?;
+        self.execute(
+            ApiRequest::patch(&["antennas", antenna_id])
+                .precondition(update.precondition.as_ref())
+                .json(update)?,
+        )
+        .await
     }
 
     pub async fn list_tasks_for_antenna(
         &self,
         antenna_id: &str,
     ) -> Result<Vec<Task>, LemonaidError> {
-        let url = format!("{}antennas/{}/tasks", self.base_url, antenna_id);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let tasks = response.json::<Vec<Task>>().await?;
-        Ok(tasks.into_iter().collect())
+        self.execute(ApiRequest::get(&["antennas", antenna_id, "tasks"]))
+            .await
     }
 
     pub async fn get_antenna_tasks_by_status(
@@ -409,85 +559,172 @@ impl CitraClient {
         antenna_id: &str,
         statuses: Vec<TaskStatus>,
     ) -> Result<Vec<Task>, LemonaidError> {
-        let status_params: Vec<String> = statuses
-            .iter()
-            .map(|s| format!("statuses={:?}", s))
-            .collect();
-        let query_string = status_params.join("&");
-        let url = format!(
-            "{}antennas/{}/tasks?{}",
-            self.base_url, antenna_id, query_string
-        );
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let tasks = response.json::<Vec<Task>>().await?;
-        Ok(tasks.into_iter().collect())
+        self.query_tasks_for_antenna(antenna_id, &TaskQuery::new().statuses(statuses))
+            .await
+    }
+
+    pub async fn query_tasks_for_antenna(
+        &self,
+        antenna_id: &str,
+        query: &TaskQuery,
+    ) -> Result<Vec<Task>, LemonaidError> {
+        self.execute(
+            ApiRequest::get(&["antennas", antenna_id, "tasks"]).query_pairs(query.to_query_pairs()),
+        )
+        .await
     }
 
     pub async fn create_rf_capture(
         &self,
         rf_capture_request: &CreateRFCaptureRequest,
     ) -> Result<RFCapture, LemonaidError> {
-        let url = format!("{}rf-captures", self.base_url);
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(rf_capture_request)
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let rf_capture = response.json::<RFCapture>().await?;
-        Ok(rf_capture)
+        rf_capture_request.validate()?;
+        self.execute(ApiRequest::post(&["rf-captures"]).json(rf_capture_request)?)
+            .await
     }
 
     pub async fn get_rf_capture(&self, rf_capture_id: &str) -> Result<RFCapture, LemonaidError> {
-        let url = format!("{}rf-captures/{}", self.base_url, rf_capture_id);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
-        let response = self.check_response(response).await?;
-        let rf_capture = response.json::<RFCapture>().await?;
-        Ok(rf_capture)
+        self.execute(ApiRequest::get(&["rf-captures", rf_capture_id]))
+            .await
     }
 
     pub async fn list_rf_captures_for_antenna(
         &self,
         antenna_id: &str,
     ) -> Result<Vec<RFCaptureSummary>, LemonaidError> {
-        let url = format!("{}antennas/{}/rf-captures", self.base_url, antenna_id);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+        self.execute(ApiRequest::get(&["antennas", antenna_id, "rf-captures"]))
+            .await
+    }
+
+    pub async fn list_rf_captures_for_antenna_page(
+        &self,
+        antenna_id: &str,
+        page: PageRequest,
+    ) -> Result<Page<RFCaptureSummary>, LemonaidError> {
+        let items = self
+            .execute(page.apply(ApiRequest::get(&["antennas", antenna_id, "rf-captures"])))
             .await?;
-        let response = self.check_response(response).await?;
-        let rf_captures = response.json::<Vec<RFCaptureSummary>>().await?;
-        Ok(rf_captures)
+        Ok(Page {
+            items,
+            request: page,
+        })
+    }
+
+    /// Streams all RF captures of an antenna, fetching `page_size` at a time.
+    pub fn stream_rf_captures_for_antenna<'a>(
+        &'a self,
+        antenna_id: &'a str,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<RFCaptureSummary, LemonaidError>> + 'a {
+        pagination::paginate(page_size, move |page| {
+            self.list_rf_captures_for_antenna_page(antenna_id, page)
+        })
     }
 
     pub async fn list_rf_captures_for_task(
         &self,
         task_id: &str,
     ) -> Result<Vec<RFCaptureSummary>, LemonaidError> {
-        let url = format!("{}tasks/{}/rf-captures", self.base_url, task_id);
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+        self.execute(ApiRequest::get(&["tasks", task_id, "rf-captures"]))
+            .await
+    }
+
+    pub async fn get_satellite(&self, satellite_id: &str) -> Result<Satellite, LemonaidError> {
+        self.execute(ApiRequest::get(&["satellites", satellite_id]))
+            .await
+    }
+
+    pub async fn search_satellites(
+        &self,
+        query: &SatelliteQuery,
+    ) -> Result<Vec<Satellite>, LemonaidError> {
+        self.execute(ApiRequest::get(&["satellites"]).query_pairs(query.to_query_pairs()))
+            .await
+    }
+
+    pub async fn list_satellites(&self) -> Result<Vec<Satellite>, LemonaidError> {
+        self.execute(ApiRequest::get(&["satellites"])).await
+    }
+
+    pub async fn list_satellites_page(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<Satellite>, LemonaidError> {
+        let items = self
+            .execute(page.apply(ApiRequest::get(&["satellites"])))
             .await?;
-        let response = self.check_response(response).await?;
-        let rf_captures = response.json::<Vec<RFCaptureSummary>>().await?;
-        Ok(rf_captures)
+        Ok(Page {
+            items,
+            request: page,
+        })
+    }
+
+    /// Streams the whole satellite catalog, fetching `page_size` at a time.
+    pub fn stream_satellites(
+        &self,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<Satellite, LemonaidError>> + '_ {
+        pagination::paginate(page_size, move |page| self.list_satellites_page(page))
+    }
+
+    /// Most recent element set for a satellite.
+    pub async fn get_latest_elset(&self, satellite_id: &str) -> Result<ElementSet, LemonaidError> {
+        self.execute(ApiRequest::get(&[
+            "satellites",
+            satellite_id,
+            "elsets",
+            "latest",
+        ]))
+        .await
+    }
+
+    /// Historical element sets for a satellite with epochs in `[start, end]`.
+    /// Either bound may be left open.
+    pub async fn list_elsets_for_satellite(
+        &self,
+        satellite_id: &str,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ElementSet>, LemonaidError> {
+        let time = |t: DateTime<Utc>| t.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+        let bounds = [("start", start.map(time)), ("end", end.map(time))];
+        let request = ApiRequest::get(&["satellites", satellite_id, "elsets"]).query_pairs(
+            bounds
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|v| (key, v))),
+        );
+        self.execute(request).await
+    }
+
+    /// Propagator for a satellite built from its catalog entry and latest element set.
+    pub async fn propagator_for_satellite(
+        &self,
+        satellite_id: &str,
+    ) -> Result<Propagator, LemonaidError> {
+        let satellite = self.get_satellite(satellite_id).await?;
+        let elset = self.get_latest_elset(satellite_id).await?;
+        Propagator::for_satellite(&satellite, &[elset])
+    }
+
+    pub async fn upload_elset(
+        &self,
+        elset: &ElementSetUploadRequest,
+    ) -> Result<ElementSet, LemonaidError> {
+        self.upload_elsets(std::slice::from_ref(elset))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(LemonaidError::EmptyBulkResponse)
+    }
+
+    /// Uploads element sets, e.g. orbit determination results. TLE checksums
+    /// and OMM fields are validated before anything is sent.
+    pub async fn upload_elsets(
+        &self,
+        elsets: &[ElementSetUploadRequest],
+    ) -> Result<Vec<ElementSet>, LemonaidError> {
+        elsets.validate()?;
+        self.execute(ApiRequest::post(&["elsets"]).json(elsets)?)
+            .await
     }
 }
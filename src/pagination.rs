@@ -0,0 +1,242 @@
+use std::future::Future;
+
+use futures_util::{Stream, TryStreamExt, stream};
+
+use crate::LemonaidError;
+use crate::request::ApiRequest;
+
+/// Limit/offset window for the paginated list endpoints.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PageRequest {
+    /// Maximum number of items to return. `None` lets the server decide.
+    pub limit: Option<u32>,
+    /// Number of items to skip.
+    pub offset: u32,
+}
+
+impl PageRequest {
+    /// First page of the given size.
+    pub fn new(limit: u32) -> Self {
+        PageRequest {
+            limit: Some(limit),
+            offset: 0,
+        }
+    }
+
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub(crate) fn apply(&self, mut request: ApiRequest) -> ApiRequest {
+        if let Some(limit) = self.limit {
+            request = request.query("limit", limit);
+        }
+        if self.offset > 0 {
+            request = request.query("offset", self.offset);
+        }
+        request
+    }
+}
+
+/// One page of results from a list endpoint.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// The window this page was requested with.
+    pub request: PageRequest,
+}
+
+impl<T> Page<T> {
+    /// The request for the following page, or `None` if this page was the last.
+    ///
+    /// A page shorter than the requested limit marks the end of the collection.
+    pub fn next_page(&self) -> Option<PageRequest> {
+        let limit = self.request.limit?;
+        if self.items.is_empty() || (self.items.len() as u32) < limit {
+            return None;
+        }
+        Some(PageRequest {
+            limit: Some(limit),
+            offset: self.request.offset + self.items.len() as u32,
+        })
+    }
+}
+
+/// Lazily walks all pages, fetching the next one only once the previous
+/// page's items have been consumed. A `page_size` of 0 is treated as 1.
+pub(crate) fn paginate<'a, T, F, Fut>(
+    page_size: u32,
+    fetch: F,
+) -> impl Stream<Item = Result<T, LemonaidError>> + 'a
+where
+    T: 'a,
+    F: Fn(PageRequest) -> Fut + 'a,
+    Fut: Future<Output = Result<Page<T>, LemonaidError>> + 'a,
+{
+    stream::try_unfold(Some(PageRequest::new(page_size.max(1))), move |next| {
+        let page = next.map(&fetch);
+        async move {
+            let Some(page) = page else {
+                return Ok(None);
+            };
+            let page = page.await?;
+            let next = page.next_page();
+            let items = stream::iter(page.items.into_iter().map(Ok));
+            Ok::<_, LemonaidError>(Some((items, next)))
+        }
+    })
+    .try_flatten()
+}
+
+/// Blocking counterpart of [`paginate`].
+#[cfg(feature = "blocking")]
+pub(crate) struct PageIter<T, F> {
+    fetch: F,
+    next: Option<PageRequest>,
+    buffered: std::vec::IntoIter<T>,
+}
+
+#[cfg(feature = "blocking")]
+impl<T, F> PageIter<T, F>
+where
+    F: FnMut(PageRequest) -> Result<Page<T>, LemonaidError>,
+{
+    /// A `page_size` of 0 is treated as 1.
+    pub(crate) fn new(page_size: u32, fetch: F) -> Self {
+        PageIter {
+            fetch,
+            next: Some(PageRequest::new(page_size.max(1))),
+            buffered: Vec::new().into_iter(),
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<T, F> Iterator for PageIter<T, F>
+where
+    F: FnMut(PageRequest) -> Result<Page<T>, LemonaidError>,
+{
+    type Item = Result<T, LemonaidError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.buffered.next() {
+                return Some(Ok(item));
+            }
+            let request = self.next.take()?;
+            match (self.fetch)(request) {
+                Ok(page) => {
+                    self.next = page.next_page();
+                    self.buffered = page.items.into_iter();
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use futures_util::TryStreamExt;
+    use reqwest::StatusCode;
+
+    use super::*;
+
+    /// Serves `0..total` like a limit/offset endpoint, logging each request.
+    fn serve(
+        total: u32,
+        log: &RefCell<Vec<PageRequest>>,
+        request: PageRequest,
+    ) -> Result<Page<u32>, LemonaidError> {
+        log.borrow_mut().push(request);
+        let end = request
+            .limit
+            .map_or(total, |limit| (request.offset + limit).min(total));
+        Ok(Page {
+            items: (request.offset.min(end)..end).collect(),
+            request,
+        })
+    }
+
+    #[test]
+    fn short_or_empty_pages_end_the_collection() {
+        let page = |items: Vec<u32>, request| Page { items, request };
+        let full = page(vec![1, 2], PageRequest::new(2).offset(4));
+        assert_eq!(full.next_page(), Some(PageRequest::new(2).offset(6)));
+        assert_eq!(page(vec![1], PageRequest::new(2)).next_page(), None);
+        assert_eq!(page(vec![], PageRequest::new(2)).next_page(), None);
+        // without a limit the server returned everything
+        assert_eq!(page(vec![1, 2], PageRequest::default()).next_page(), None);
+    }
+
+    #[tokio::test]
+    async fn streams_walk_every_page() {
+        let log = RefCell::new(Vec::new());
+        let items: Vec<u32> = paginate(2, |request| std::future::ready(serve(5, &log, request)))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, [0, 1, 2, 3, 4]);
+        let offsets: Vec<u32> = log.borrow().iter().map(|request| request.offset).collect();
+        assert_eq!(offsets, [0, 2, 4]);
+
+        // an exact multiple of the page size needs one empty page to find the end
+        log.borrow_mut().clear();
+        let items: Vec<u32> = paginate(5, |request| std::future::ready(serve(5, &log, request)))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 5);
+        assert_eq!(log.borrow().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn zero_page_sizes_are_treated_as_one() {
+        let log = RefCell::new(Vec::new());
+        let items: Vec<u32> = paginate(0, |request| std::future::ready(serve(3, &log, request)))
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, [0, 1, 2]);
+        assert!(log.borrow().iter().all(|request| request.limit == Some(1)));
+        assert_eq!(log.borrow().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn pages_are_fetched_lazily_and_errors_end_the_stream() {
+        let log = RefCell::new(Vec::new());
+        let fetch = |request: PageRequest| {
+            let result = if request.offset < 2 {
+                serve(10, &log, request)
+            } else {
+                Err(LemonaidError::Api {
+                    status: StatusCode::INTERNAL_SERVER_ERROR,
+                    message: "boom".to_string(),
+                })
+            };
+            std::future::ready(result)
+        };
+        let mut stream = std::pin::pin!(paginate(2, fetch));
+        assert_eq!(stream.try_next().await.unwrap(), Some(0));
+        assert_eq!(log.borrow().len(), 1);
+        assert_eq!(stream.try_next().await.unwrap(), Some(1));
+        assert!(stream.try_next().await.is_err());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn page_iterators_match_streams() {
+        let log = RefCell::new(Vec::new());
+        let items: Vec<u32> = PageIter::new(0, |request| serve(3, &log, request))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(items, [0, 1, 2]);
+        let items: Vec<u32> = PageIter::new(2, |request| serve(5, &log, request))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(items, [0, 1, 2, 3, 4]);
+    }
+}
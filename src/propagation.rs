@@ -0,0 +1,404 @@
+//! Offline SGP4/SDP4 propagation of a satellite's element sets.
+//!
+//! Element sets are propagated with the WGS-72 constants and AFSPC conventions
+//! they are generated with, so results match the published verification vectors.
+//!
+//! ```no_run
+//! # async fn run(client: &lemonaid::CitraClient) -> Result<(), lemonaid::LemonaidError> {
+//! use lemonaid::SensorFrame;
+//!
+//! let propagator = client.propagator_for_satellite("sat-123").await?;
+//! let state = propagator.propagate(chrono::Utc::now(), SensorFrame::J2000)?;
+//! println!("{:?}", state.position_km);
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::error::PropagationError;
+use crate::frames::EarthOrientation;
+use crate::{
+    ElementSet, LemonaidError, Omm, Satellite, SatelliteFrequency, SensorFrame, Task, frames,
+};
+
+/// Position and velocity of a satellite at one instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateVector {
+    pub epoch: DateTime<Utc>,
+    pub frame: SensorFrame,
+    pub position_km: [f64; 3],
+    pub velocity_km_s: [f64; 3],
+}
+
+impl StateVector {
+    /// The same state expressed in another inertial frame.
+    pub fn in_frame(&self, frame: SensorFrame) -> StateVector {
+        let rotation = frames::rotation(
+            self.frame.into(),
+            frame.into(),
+            self.epoch,
+            &EarthOrientation::default(),
+        );
+        StateVector {
+            epoch: self.epoch,
+            frame,
+            position_km: frames::apply(&rotation, self.position_km),
+            velocity_km_s: frames::apply(&rotation, self.velocity_km_s),
+        }
+    }
+}
+
+struct Model {
+    epoch: DateTime<Utc>,
+    constants: sgp4::Constants,
+}
+
+/// Propagates one satellite from its mean elements.
+///
+/// When built from several element sets, each request is served by the set whose
+/// epoch is closest to the requested time.
+pub struct Propagator {
+    satellite_id: String,
+    satellite_name: Option<String>,
+    frequencies: Vec<SatelliteFrequency>,
+    models: Vec<Model>,
+}
+
+impl Propagator {
+    /// Builds a propagator from one or more OMMs of the same satellite.
+    pub fn new(satellite_id: &str, elements: &[Omm]) -> Result<Self, LemonaidError> {
+        if elements.is_empty() {
+            return Err(PropagationError::NoElementSets {
+                satellite_id: satellite_id.to_string(),
+            }
+            .into());
+        }
+        let mut models = elements
+            .iter()
+            .map(|omm| {
+                let constants =
+                    sgp4::Constants::from_elements_afspc_compatibility_mode(&to_sgp4_elements(omm))
+                        .map_err(|err| PropagationError::InvalidElements(err.to_string()))?;
+                Ok(Model {
+                    epoch: omm.epoch,
+                    constants,
+                })
+            })
+            .collect::<Result<Vec<_>, LemonaidError>>()?;
+        models.sort_by_key(|model| model.epoch);
+        Ok(Propagator {
+            satellite_id: satellite_id.to_string(),
+            satellite_name: elements.iter().find_map(|omm| omm.object_name.clone()),
+            frequencies: Vec::new(),
+            models,
+        })
+    }
+
+    /// Builds a propagator from element sets fetched from the API.
+    pub fn from_elsets(satellite_id: &str, elsets: &[ElementSet]) -> Result<Self, LemonaidError> {
+        let elements = elsets
+            .iter()
+            .map(ElementSet::mean_elements)
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(satellite_id, &elements)
+    }
+
+    /// Builds a propagator carrying the satellite's catalog name and frequencies.
+    pub fn for_satellite(
+        satellite: &Satellite,
+        elsets: &[ElementSet],
+    ) -> Result<Self, LemonaidError> {
+        let mut propagator = Self::from_elsets(&satellite.id, elsets)?;
+        propagator.satellite_name = Some(satellite.name.clone());
+        propagator.frequencies = satellite.frequencies.clone();
+        Ok(propagator)
+    }
+
+    pub fn satellite_id(&self) -> &str {
+        &self.satellite_id
+    }
+
+    pub fn satellite_name(&self) -> Option<&str> {
+        self.satellite_name.as_deref()
+    }
+
+    /// Downlink frequencies from the satellite catalog, if known.
+    pub fn frequencies(&self) -> &[SatelliteFrequency] {
+        &self.frequencies
+    }
+
+    /// Epochs of the element sets, oldest first.
+    pub fn epochs(&self) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        self.models.iter().map(|model| model.epoch)
+    }
+
+    /// State of the satellite at `time` in the requested frame.
+    pub fn propagate(
+        &self,
+        time: DateTime<Utc>,
+        frame: SensorFrame,
+    ) -> Result<StateVector, LemonaidError> {
+        let model = self.model_for(time);
+        let minutes = minutes_between(model.epoch, time);
+        let prediction = model
+            .constants
+            .propagate_afspc_compatibility_mode(sgp4::MinutesSinceEpoch(minutes))
+            .map_err(|err| PropagationError::Diverged {
+                minutes,
+                message: err.to_string(),
+            })?;
+        let state = StateVector {
+            epoch: time,
+            frame: SensorFrame::TEME,
+            position_km: prediction.position,
+            velocity_km_s: prediction.velocity,
+        };
+        Ok(state.in_frame(frame))
+    }
+
+    /// States from `start` to `end` inclusive, every `step`.
+    pub fn ephemeris(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        step: Duration,
+        frame: SensorFrame,
+    ) -> Result<Vec<StateVector>, LemonaidError> {
+        if step <= Duration::zero() {
+            return Err(LemonaidError::Config(
+                "ephemeris step must be positive".to_string(),
+            ));
+        }
+        let mut states = Vec::new();
+        let mut time = start;
+        while time <= end {
+            states.push(self.propagate(time, frame)?);
+            time += step;
+        }
+        Ok(states)
+    }
+
+    /// States covering a task's scheduled window, or its requested window if it
+    /// has not been scheduled yet.
+    pub fn task_ephemeris(
+        &self,
+        task: &Task,
+        step: Duration,
+        frame: SensorFrame,
+    ) -> Result<Vec<StateVector>, LemonaidError> {
+        let start = task.scheduled_start.unwrap_or(task.task_start);
+        let stop = task.scheduled_stop.unwrap_or(task.task_stop);
+        self.ephemeris(start, stop, step, frame)
+    }
+
+    fn model_for(&self, time: DateTime<Utc>) -> &Model {
+        self.models
+            .iter()
+            .min_by_key(|model| (model.epoch - time).abs())
+            .expect("propagators hold at least one element set")
+    }
+}
+
+fn minutes_between(epoch: DateTime<Utc>, time: DateTime<Utc>) -> f64 {
+    let elapsed = time - epoch;
+    elapsed.num_seconds() as f64 / 60.0 + f64::from(elapsed.subsec_nanos()) * 1e-9 / 60.0
+}
+
+fn to_sgp4_elements(omm: &Omm) -> sgp4::Elements {
+    let classification = match omm.classification_type.as_deref() {
+        Some("C") => sgp4::Classification::Classified,
+        Some("S") => sgp4::Classification::Secret,
+        _ => sgp4::Classification::Unclassified,
+    };
+    sgp4::Elements {
+        object_name: omm.object_name.clone(),
+        international_designator: omm.object_id.clone(),
+        norad_id: omm.norad_cat_id.unwrap_or(0).into(),
+        classification,
+        datetime: omm.epoch.naive_utc(),
+        mean_motion_dot: omm.mean_motion_dot,
+        mean_motion_ddot: omm.mean_motion_ddot,
+        drag_term: omm.bstar,
+        element_set_number: omm.element_set_no.into(),
+        inclination: omm.inclination_deg,
+        right_ascension: omm.ra_of_asc_node_deg,
+        eccentricity: omm.eccentricity,
+        argument_of_perigee: omm.arg_of_pericenter_deg,
+        mean_anomaly: omm.mean_anomaly_deg,
+        mean_motion: omm.mean_motion_rev_per_day,
+        revolution_number: omm.rev_at_epoch.into(),
+        ephemeris_type: omm.ephemeris_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tle;
+
+    fn propagator(line1: &str, line2: &str) -> Propagator {
+        let omm = Tle::new(line1, line2).unwrap().to_omm().unwrap();
+        Propagator::new("test", &[omm]).unwrap()
+    }
+
+    fn assert_close(actual: [f64; 3], expected: [f64; 3], tolerance: f64) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!(
+                (a - e).abs() < tolerance,
+                "{:?} differs from {:?}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    fn check(propagator: &Propagator, minutes: f64, position: [f64; 3], velocity: [f64; 3]) {
+        let epoch = propagator.models[0].epoch;
+        let time = epoch + Duration::microseconds((minutes * 60e6) as i64);
+        let state = propagator.propagate(time, SensorFrame::TEME).unwrap();
+        assert_close(state.position_km, position, 1e-3);
+        assert_close(state.velocity_km_s, velocity, 1e-6);
+    }
+
+    // Verification vectors from Vallado et al., "Revisiting Spacetrack Report #3" (2006)
+
+    #[test]
+    fn vallado_00005_near_earth() {
+        let propagator = propagator(
+            "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+            "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+        );
+        check(
+            &propagator,
+            0.0,
+            [7022.46529266, -1400.08296755, 0.03995155],
+            [1.893841015, 6.405893759, 4.534807250],
+        );
+        check(
+            &propagator,
+            360.0,
+            [-7154.03120202, -3783.17682504, -3536.19412294],
+            [4.741887409, -4.151817765, -2.093935425],
+        );
+        check(
+            &propagator,
+            2880.0,
+            [-8650.73082219, -1914.93811525, -3007.03603443],
+            [3.067165127, -4.828384068, -2.515322836],
+        );
+    }
+
+    #[test]
+    fn vallado_06251_drag() {
+        let propagator = propagator(
+            "1 06251U 62025E   06176.82412014  .00008885  00000-0  12808-3 0  3985",
+            "2 06251  58.0579  54.0425 0030035 139.1568 221.1854 15.56387291  6774",
+        );
+        check(
+            &propagator,
+            0.0,
+            [3988.31022699, 5498.96657235, 0.90055879],
+            [-3.290032738, 2.357652820, 6.496623475],
+        );
+        check(
+            &propagator,
+            120.0,
+            [-3935.69800083, 409.10980837, 5471.33577327],
+            [-3.374784183, -6.635211043, -1.942056221],
+        );
+    }
+
+    #[test]
+    fn vallado_28057_deep_space_resonance() {
+        let propagator = propagator(
+            "1 28057U 03049A   06177.78615833  .00000060  00000-0  35940-4 0  1836",
+            "2 28057  98.4283 247.6961 0000884  88.1964 271.9322 14.35478080140550",
+        );
+        check(
+            &propagator,
+            0.0,
+            [-2715.28237486, -6619.26436889, -0.01341443],
+            [-1.008587273, 0.422782003, 7.385272942],
+        );
+    }
+
+    #[test]
+    fn vallado_11801_deep_space() {
+        let propagator = propagator(
+            "1 11801U          80230.29629788  .01431103  00000-0  14311-1 0    13",
+            "2 11801  46.7916 230.4354 7318036  47.4722  10.4117  2.28537848    13",
+        );
+        check(
+            &propagator,
+            0.0,
+            [7473.37102491, 428.94748312, 5828.74846783],
+            [5.107155391, 6.444680305, -0.186133297],
+        );
+        check(
+            &propagator,
+            360.0,
+            [-3305.22148694, 32410.84323331, -24697.16974954],
+            [-1.301137319, -1.151315600, -0.283335823],
+        );
+    }
+
+    #[test]
+    fn teme_to_j2000_matches_vallado_example() {
+        // Vallado, Fundamentals of Astrodynamics, example 3-15 (without EOP corrections)
+        let epoch = "2004-04-06T07:51:28.386009Z".parse().unwrap();
+        let teme = StateVector {
+            epoch,
+            frame: SensorFrame::TEME,
+            position_km: [5094.18016210, 6127.64465950, 6380.34453270],
+            velocity_km_s: [-4.746131487, 0.785818041, 5.531931288],
+        };
+        let j2000 = teme.in_frame(SensorFrame::J2000);
+        assert_eq!(j2000.frame, SensorFrame::J2000);
+        assert_close(j2000.position_km, [5102.5096, 6123.0115, 6378.1363], 1e-3);
+        assert_close(
+            j2000.velocity_km_s,
+            [-4.7432196, 0.7905366, 5.5337561],
+            1e-6,
+        );
+
+        let back = j2000.in_frame(SensorFrame::TEME);
+        assert_close(back.position_km, teme.position_km, 1e-8);
+    }
+
+    #[test]
+    fn picks_the_closest_element_set() {
+        let mut first = Tle::new(
+            "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+            "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+        )
+        .unwrap()
+        .to_omm()
+        .unwrap();
+        let mut second = first.clone();
+        second.epoch = first.epoch + Duration::days(10);
+        second.mean_anomaly_deg += 90.0;
+        first.object_name = Some("VANGUARD 1".to_string());
+        let propagator = Propagator::new("test", &[second.clone(), first.clone()]).unwrap();
+        assert_eq!(propagator.satellite_name(), Some("VANGUARD 1"));
+
+        let near_second = propagator
+            .propagate(second.epoch, SensorFrame::TEME)
+            .unwrap();
+        let only_second = Propagator::new("test", &[second.clone()])
+            .unwrap()
+            .propagate(second.epoch, SensorFrame::TEME)
+            .unwrap();
+        assert_eq!(near_second, only_second);
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert!(matches!(
+            Propagator::new("test", &[]),
+            Err(LemonaidError::Propagation(
+                PropagationError::NoElementSets { .. }
+            ))
+        ));
+    }
+}
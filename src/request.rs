@@ -0,0 +1,248 @@
+use reqwest::Method;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, ETAG, HeaderName};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{CitraClient, LemonaidError, Precondition, Versioned, retry};
+
+/// Description of a single API call, executed by [`CitraClient::execute`].
+///
+/// Path segments are percent-encoded when joined onto the base URL, so ids can
+/// be passed through as-is.
+pub(crate) struct ApiRequest {
+    method: Method,
+    segments: Vec<String>,
+    query: Vec<(String, String)>,
+    headers: Vec<(HeaderName, String)>,
+    body: Option<Vec<u8>>,
+    idempotent: bool,
+}
+
+impl ApiRequest {
+    pub(crate) fn new(method: Method, segments: &[&str]) -> Self {
+        // POST creates resources; everything else may be safely repeated
+        let idempotent = method != Method::POST;
+        ApiRequest {
+            method,
+            segments: segments.iter().map(|s| s.to_string()).collect(),
+            query: Vec::new(),
+            headers: Vec::new(),
+            body: None,
+            idempotent,
+        }
+    }
+
+    pub(crate) fn get(segments: &[&str]) -> Self {
+        Self::new(Method::GET, segments)
+    }
+
+    pub(crate) fn post(segments: &[&str]) -> Self {
+        Self::new(Method::POST, segments)
+    }
+
+    pub(crate) fn put(segments: &[&str]) -> Self {
+        Self::new(Method::PUT, segments)
+    }
+
+    pub(crate) fn patch(segments: &[&str]) -> Self {
+        Self::new(Method::PATCH, segments)
+    }
+
+    pub(crate) fn delete(segments: &[&str]) -> Self {
+        Self::new(Method::DELETE, segments)
+    }
+
+    /// Appends a query parameter. May be repeated for list parameters.
+    pub(crate) fn query(mut self, key: &str, value: impl ToString) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Appends several query parameters in order.
+    pub(crate) fn query_pairs<V: ToString>(
+        mut self,
+        pairs: impl IntoIterator<Item = (&'static str, V)>,
+    ) -> Self {
+        for (key, value) in pairs {
+            self = self.query(key, value);
+        }
+        self
+    }
+
+    /// Adds the conditional header for an optimistic-concurrency check, if any.
+    pub(crate) fn precondition(mut self, precondition: Option<&Precondition>) -> Self {
+        if let Some(precondition) = precondition {
+            self.headers.push(precondition.header());
+        }
+        self
+    }
+
+    /// Sets a JSON request body.
+    pub(crate) fn json<B: Serialize + ?Sized>(mut self, body: &B) -> Result<Self, LemonaidError> {
+        self.body = Some(serde_json::to_vec(body).map_err(LemonaidError::Serialization)?);
+        Ok(self)
+    }
+
+    /// Marks a POST without side effects (e.g. a solver call) as safe to retry.
+    pub(crate) fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+}
+
+impl CitraClient {
+    /// Executes a request and decodes the JSON response body.
+    pub(crate) async fn execute<T: DeserializeOwned>(
+        &self,
+        request: ApiRequest,
+    ) -> Result<T, LemonaidError> {
+        let response = self.execute_raw(request).await?;
+        self.decode(response).await
+    }
+
+    /// Like [`execute`](Self::execute), also returning the response's `ETag`.
+    pub(crate) async fn execute_versioned<T: DeserializeOwned>(
+        &self,
+        request: ApiRequest,
+    ) -> Result<Versioned<T>, LemonaidError> {
+        let response = self.execute_raw(request).await?;
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let value = self.decode(response).await?;
+        Ok(Versioned { value, etag })
+    }
+
+    /// Executes a request whose response body is not needed.
+    pub(crate) async fn execute_empty(&self, request: ApiRequest) -> Result<(), LemonaidError> {
+        self.execute_raw(request).await?;
+        Ok(())
+    }
+
+    /// Executes a request, replaying it once with a fresh token if the API
+    /// rejects the current one and the auth provider can refresh it.
+    async fn execute_raw(&self, request: ApiRequest) -> Result<reqwest::Response, LemonaidError> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .expect("base URL is validated by the builder")
+            .pop_if_empty()
+            .extend(&request.segments);
+        if !request.query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&request.query);
+        }
+
+        let token = self.auth.token().await?;
+        let builder = self.build_request(&request, url.clone(), &token);
+        match self.send(builder, request.idempotent).await {
+            Err(LemonaidError::Unauthorized { .. }) if self.auth.invalidate().await => {
+                let token = self.auth.token().await?;
+                let builder = self.build_request(&request, url, &token);
+                self.send(builder, request.idempotent).await
+            }
+            result => result,
+        }
+    }
+
+    fn build_request(
+        &self,
+        request: &ApiRequest,
+        url: reqwest::Url,
+        token: &str,
+    ) -> reqwest::RequestBuilder {
+        let mut builder = self
+            .client
+            .request(request.method.clone(), url)
+            .header(AUTHORIZATION, format!("Bearer {}", token));
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        match &request.body {
+            Some(body) => builder
+                .header(CONTENT_TYPE, "application/json")
+                .body(body.clone()),
+            None => builder,
+        }
+    }
+
+    /// Sends a request, retrying transient failures according to the retry policy,
+    /// and checks the final response status.
+    ///
+    /// Requests that are not `idempotent` are sent once unless the policy opts in,
+    /// in which case every attempt carries the same `Idempotency-Key`.
+    async fn send(
+        &self,
+        mut request: reqwest::RequestBuilder,
+        idempotent: bool,
+    ) -> Result<reqwest::Response, LemonaidError> {
+        let policy = &self.retry_policy;
+        if !idempotent && policy.retry_non_idempotent {
+            request = request.header("Idempotency-Key", retry::idempotency_key());
+        }
+        let max_attempts = if idempotent || policy.retry_non_idempotent {
+            policy.max_attempts.max(1)
+        } else {
+            1
+        };
+
+        let mut attempt = 1;
+        loop {
+            // bodies are always buffered JSON, so the request can be cloned
+            let this_attempt = request
+                .try_clone()
+                .expect("request body should be cloneable");
+            let delay = match this_attempt.send().await {
+                Ok(response)
+                    if attempt < max_attempts && retry::is_retryable_status(response.status()) =>
+                {
+                    match policy.delay_for_response(attempt, response.headers()) {
+                        Some(delay) => delay,
+                        None => return self.check_response(response).await,
+                    }
+                }
+                Ok(response) => return self.check_response(response).await,
+                Err(err) if attempt < max_attempts && retry::is_retryable_error(&err) => {
+                    policy.backoff(attempt)
+                }
+                Err(err) => return Err(err.into()),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Helper method to check response status and return appropriate error
+    async fn check_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, LemonaidError> {
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(LemonaidError::from_response(response).await)
+        }
+    }
+
+    /// Decodes a JSON response body, reporting the failing field path on error
+    async fn decode<T: DeserializeOwned>(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<T, LemonaidError> {
+        decode(response).await
+    }
+}
+
+/// Decodes a JSON response body, reporting the failing field path on error.
+/// Also used for responses from outside the API, such as token endpoints.
+pub(crate) async fn decode<T: DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T, LemonaidError> {
+    let body = response.text().await?;
+    let deserializer = &mut serde_json::Deserializer::from_str(&body);
+    serde_path_to_error::deserialize(deserializer).map_err(|err| LemonaidError::Deserialization {
+        path: err.path().to_string(),
+        source: err.into_inner(),
+        body,
+    })
+}
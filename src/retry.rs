@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// Controls how transient failures (429/502/503/504 responses, connection
+/// errors and timeouts) are retried.
+///
+/// GET, PUT and DELETE requests are always eligible for retries. POST requests
+/// that create resources are only retried when `retry_non_idempotent` is set, in
+/// which case an `Idempotency-Key` header is sent so the server can drop
+/// duplicates.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the backoff delay and for an honored `Retry-After`.
+    pub max_backoff: Duration,
+    /// Factor the backoff grows by after each attempt.
+    pub multiplier: f64,
+    /// Randomizes each delay between zero and the computed backoff ("full jitter").
+    pub jitter: bool,
+    /// Also retry resource-creating POST requests, guarded by an idempotency key.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Backoff delay after the given (1-based) failed attempt.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        // a negative or NaN multiplier must not reach `Duration::from_secs_f64`
+        let delay = delay.max(0.0).min(self.max_backoff.as_secs_f64());
+        if self.jitter {
+            Duration::from_secs_f64(rand::rng().random_range(0.0..=delay))
+        } else {
+            Duration::from_secs_f64(delay)
+        }
+    }
+
+    /// Delay before retrying a response, or `None` if the server asked us to
+    /// wait longer than `max_backoff`.
+    pub(crate) fn delay_for_response(&self, attempt: u32, headers: &HeaderMap) -> Option<Duration> {
+        match retry_after(headers) {
+            Some(wait) if wait > self.max_backoff => None,
+            Some(wait) => Some(wait),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+pub(crate) fn is_retryable_error(err: &reqwest::Error) -> bool {
+    err.is_connect() || err.is_timeout() || err.is_request()
+}
+
+/// Parses a `Retry-After` header given either as delay-seconds or as an HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value)
+        .ok()?
+        .with_timezone(&Utc);
+    Some((date - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+pub(crate) fn idempotency_key() -> String {
+    format!("{:032x}", rand::rng().random::<u128>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn without_jitter() -> RetryPolicy {
+        RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn backoff_grows_until_the_cap() {
+        let policy = without_jitter();
+        assert_eq!(policy.backoff(1), Duration::from_millis(250));
+        assert_eq!(policy.backoff(2), Duration::from_millis(500));
+        assert_eq!(policy.backoff(3), Duration::from_secs(1));
+        assert_eq!(policy.backoff(10), Duration::from_secs(10));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(10));
+        for attempt in 1..8 {
+            assert!(RetryPolicy::default().backoff(attempt) <= policy.backoff(attempt));
+        }
+    }
+
+    #[test]
+    fn odd_multipliers_do_not_panic() {
+        for multiplier in [-2.0, f64::NAN, f64::INFINITY] {
+            let policy = RetryPolicy {
+                multiplier,
+                ..RetryPolicy::default()
+            };
+            for attempt in 1..4 {
+                assert!(policy.backoff(attempt) <= policy.max_backoff);
+            }
+        }
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_dates() {
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(RETRY_AFTER, value.parse().unwrap());
+            headers
+        };
+        assert_eq!(retry_after(&headers("120")), Some(Duration::from_secs(120)));
+        let soon = (Utc::now() + chrono::Duration::seconds(30)).to_rfc2822();
+        let wait = retry_after(&headers(&soon)).unwrap();
+        assert!(wait > Duration::from_secs(25) && wait <= Duration::from_secs(30));
+        assert_eq!(
+            retry_after(&headers("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(retry_after(&headers("soon")), None);
+        assert_eq!(retry_after(&HeaderMap::new()), None);
+
+        let policy = without_jitter();
+        assert_eq!(policy.delay_for_response(1, &headers("3600")), None);
+        assert_eq!(
+            policy.delay_for_response(2, &HeaderMap::new()),
+            Some(Duration::from_millis(500))
+        );
+    }
+}
@@ -0,0 +1,584 @@
+//! Offline counterparts of the access solver endpoints, working from locally
+//! propagated satellites instead of the service.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::frames::{EarthOrientation, Frame};
+use crate::units::{Degrees, Km, MHz};
+use crate::{
+    FOVAccessRequest, FOVAccessResponse, Groundstation, HorizonAccess, LemonaidError, Propagator,
+    SatelliteAccessToGroundstationRequest, SensorFrame, TrackingParameters, frames,
+};
+
+/// Spacing of the coarse elevation samples used to bracket rise and set times.
+///
+/// A pass that rises above the minimum elevation and sets again between two
+/// samples, e.g. a grazing pass lasting a few seconds, is not found.
+const SEARCH_STEP: Duration = Duration::seconds(10);
+
+/// Rise and set times are refined to this precision.
+const CROSSING_TOLERANCE: Duration = Duration::milliseconds(1);
+
+/// Below this horizontal distance in km the satellite counts as being at the
+/// zenith, where azimuth and its rate are undefined.
+const ZENITH_HORIZONTAL_KM: f64 = 1e-6;
+
+/// Finds the passes of `satellites` over `groundstation`, like
+/// [`CitraClient::solve_access_for_groundstation`](crate::CitraClient::solve_access_for_groundstation).
+///
+/// The request's `groundstation_id` is ignored in favour of `groundstation`. When a
+/// frequency range is given, only satellites with a catalog frequency overlapping it
+/// are considered. Passes already in progress at `start` or still in progress at
+/// `end` are clipped to the requested window. Results are ordered by start time.
+pub fn solve_horizon_access(
+    groundstation: &Groundstation,
+    satellites: &[Propagator],
+    request: &SatelliteAccessToGroundstationRequest,
+) -> Result<Vec<HorizonAccess>, LemonaidError> {
+    let site = Site::new(
+        groundstation.latitude,
+        groundstation.longitude,
+        groundstation.altitude_km(),
+    );
+    let mut accesses = Vec::new();
+    for satellite in satellites {
+        if !matches_frequency(
+            satellite,
+            request.min_frequency_mhz,
+            request.max_frequency_mhz,
+        ) {
+            continue;
+        }
+        for (rise, set) in site.passes(
+            satellite,
+            request.start,
+            request.end,
+            request.min_elevation_deg.value(),
+        )? {
+            let duration_minutes = (set - rise).num_milliseconds() as f64 / 60_000.0;
+            if duration_minutes < request.min_duration_minutes {
+                continue;
+            }
+            accesses.push(HorizonAccess {
+                satellite_id: satellite.satellite_id().to_string(),
+                satellite_name: satellite.satellite_name().map(str::to_string),
+                groundstation_id: groundstation.id.clone(),
+                groundstation_name: Some(groundstation.name.clone()),
+                start: site.track(satellite, rise)?,
+                end: site.track(satellite, set)?,
+                duration_minutes,
+            });
+        }
+    }
+    accesses.sort_by_key(|access| access.start.time);
+    Ok(accesses)
+}
+
+/// Outline of a sensor's field of view on the sky.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SensorShape {
+    /// A cone with the given full opening angle.
+    Circular { field_of_view_deg: Degrees },
+    /// A rectangular frame, e.g. a telescope's imaging sensor.
+    Rectangular {
+        /// Full angular extent along the frame's horizontal axis.
+        width_deg: Degrees,
+        /// Full angular extent along the frame's vertical axis.
+        height_deg: Degrees,
+        /// Position angle of the frame's vertical axis, measured from celestial
+        /// north towards east.
+        rotation_deg: Degrees,
+    },
+}
+
+impl SensorShape {
+    /// Whether the direction `(ra, dec)` falls inside the field of view when
+    /// pointed at `(center_ra, center_dec)`.
+    pub fn contains(
+        &self,
+        center_ra: Degrees,
+        center_dec: Degrees,
+        ra: Degrees,
+        dec: Degrees,
+    ) -> bool {
+        let (sin_dec0, cos_dec0) = center_dec.value().to_radians().sin_cos();
+        let (sin_dec, cos_dec) = dec.value().to_radians().sin_cos();
+        let (sin_dra, cos_dra) = (ra - center_ra).value().to_radians().sin_cos();
+        let cos_separation = sin_dec0 * sin_dec + cos_dec0 * cos_dec * cos_dra;
+        match *self {
+            SensorShape::Circular { field_of_view_deg } => {
+                cos_separation.clamp(-1.0, 1.0).acos().to_degrees()
+                    <= field_of_view_deg.value() / 2.0
+            }
+            SensorShape::Rectangular {
+                width_deg,
+                height_deg,
+                rotation_deg,
+            } => {
+                if cos_separation <= 0.0 {
+                    return false;
+                }
+                // gnomonic projection onto the plane tangent at the boresight
+                let east = cos_dec * sin_dra / cos_separation;
+                let north = (cos_dec0 * sin_dec - sin_dec0 * cos_dec * cos_dra) / cos_separation;
+                let (sin_rot, cos_rot) = rotation_deg.value().to_radians().sin_cos();
+                let horizontal = east * cos_rot - north * sin_rot;
+                let vertical = east * sin_rot + north * cos_rot;
+                horizontal.abs() <= (width_deg.value() / 2.0).to_radians().tan()
+                    && vertical.abs() <= (height_deg.value() / 2.0).to_radians().tan()
+            }
+        }
+    }
+}
+
+/// Finds the satellites inside a circular field of view, like
+/// [`CitraClient::solve_fov_access`](crate::CitraClient::solve_fov_access).
+pub fn solve_fov_access(
+    satellites: &[Propagator],
+    request: &FOVAccessRequest,
+) -> Result<Vec<FOVAccessResponse>, LemonaidError> {
+    let shape = SensorShape::Circular {
+        field_of_view_deg: request.field_of_view_deg,
+    };
+    solve_fov_access_with_shape(satellites, request, &shape)
+}
+
+/// Like [`solve_fov_access`], with `shape` used instead of the request's
+/// `field_of_view_deg`.
+///
+/// Positions are topocentric right ascension and declination as seen from the
+/// sensor, in the request's `sensor_frame`. Satellites below the sensor's horizon
+/// are never reported.
+pub fn solve_fov_access_with_shape(
+    satellites: &[Propagator],
+    request: &FOVAccessRequest,
+    shape: &SensorShape,
+) -> Result<Vec<FOVAccessResponse>, LemonaidError> {
+    let site = Site::new(
+        request.sensor_latitude_deg,
+        request.sensor_longitude_deg,
+        request.sensor_altitude_km,
+    );
+    let mut targets = Vec::new();
+    for satellite in satellites {
+        // the Earth blocks anything below the sensor's horizon
+        if site.elevation_deg(satellite, request.time)? < 0.0 {
+            continue;
+        }
+        let (right_ascension_deg, declination_deg) =
+            site.right_ascension_declination(satellite, request.time, request.sensor_frame)?;
+        if shape.contains(
+            request.right_ascension_deg,
+            request.declination_deg,
+            right_ascension_deg,
+            declination_deg,
+        ) {
+            targets.push(FOVAccessResponse {
+                satellite_id: satellite.satellite_id().to_string(),
+                satellite_name: satellite.satellite_name().map(str::to_string),
+                right_ascension_deg,
+                declination_deg,
+            });
+        }
+    }
+    Ok(targets)
+}
+
+fn matches_frequency(satellite: &Propagator, min_mhz: Option<MHz>, max_mhz: Option<MHz>) -> bool {
+    if min_mhz.is_none() && max_mhz.is_none() {
+        return true;
+    }
+    let min_mhz = min_mhz.unwrap_or(MHz(f64::NEG_INFINITY));
+    let max_mhz = max_mhz.unwrap_or(MHz(f64::INFINITY));
+    satellite
+        .frequencies()
+        .iter()
+        .any(|band| band.max_frequency_mhz >= min_mhz && band.min_frequency_mhz <= max_mhz)
+}
+
+/// Start and end of an interval of visibility.
+type Window = (DateTime<Utc>, DateTime<Utc>);
+
+/// A fixed observer on the WGS-84 ellipsoid.
+pub(crate) struct Site {
+    position_ecef: [f64; 3],
+    ecef_to_enu: frames::Matrix,
+}
+
+impl Site {
+    pub(crate) fn new(latitude: Degrees, longitude: Degrees, altitude: Km) -> Self {
+        Site {
+            position_ecef: frames::geodetic_to_ecef(latitude, longitude, altitude),
+            ecef_to_enu: frames::ecef_to_enu(latitude, longitude),
+        }
+    }
+
+    /// Position and velocity of the satellite relative to the site, in east/north/up axes.
+    fn relative_enu(
+        &self,
+        satellite: &Propagator,
+        time: DateTime<Utc>,
+    ) -> Result<([f64; 3], [f64; 3]), LemonaidError> {
+        let state = satellite.propagate(time, SensorFrame::TEME)?;
+        let (position, velocity) = frames::transform(
+            state.position_km,
+            state.velocity_km_s,
+            Frame::Teme,
+            Frame::Itrf,
+            time,
+            &EarthOrientation::default(),
+        );
+        let offset = [
+            position[0] - self.position_ecef[0],
+            position[1] - self.position_ecef[1],
+            position[2] - self.position_ecef[2],
+        ];
+        Ok((
+            frames::apply(&self.ecef_to_enu, offset),
+            frames::apply(&self.ecef_to_enu, velocity),
+        ))
+    }
+
+    fn elevation_deg(
+        &self,
+        satellite: &Propagator,
+        time: DateTime<Utc>,
+    ) -> Result<f64, LemonaidError> {
+        let ([east, north, up], _) = self.relative_enu(satellite, time)?;
+        Ok(up.atan2(east.hypot(north)).to_degrees())
+    }
+
+    /// Topocentric right ascension and declination in `frame`, in degrees.
+    fn right_ascension_declination(
+        &self,
+        satellite: &Propagator,
+        time: DateTime<Utc>,
+        frame: SensorFrame,
+    ) -> Result<(Degrees, Degrees), LemonaidError> {
+        let eop = EarthOrientation::default();
+        let state = satellite.propagate(time, SensorFrame::TEME)?;
+        let site = frames::apply(
+            &frames::rotation(Frame::Itrf, Frame::Teme, time, &eop),
+            self.position_ecef,
+        );
+        let line_of_sight = [
+            state.position_km[0] - site[0],
+            state.position_km[1] - site[1],
+            state.position_km[2] - site[2],
+        ];
+        let [x, y, z] = frames::apply(
+            &frames::rotation(Frame::Teme, frame.into(), time, &eop),
+            line_of_sight,
+        );
+        Ok((
+            Degrees(y.atan2(x).to_degrees().rem_euclid(360.0)),
+            Degrees(z.atan2(x.hypot(y)).to_degrees()),
+        ))
+    }
+
+    /// Look angles, range and their rates at `time`.
+    pub(crate) fn track(
+        &self,
+        satellite: &Propagator,
+        time: DateTime<Utc>,
+    ) -> Result<TrackingParameters, LemonaidError> {
+        let (position, velocity) = self.relative_enu(satellite, time)?;
+        Ok(tracking_parameters(time, position, velocity))
+    }
+
+    /// Intervals within `[start, end]` during which the satellite is at or above
+    /// `min_elevation_deg`.
+    fn passes(
+        &self,
+        satellite: &Propagator,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_elevation_deg: f64,
+    ) -> Result<Vec<Window>, LemonaidError> {
+        let visible = |time| {
+            Ok::<_, LemonaidError>(self.elevation_deg(satellite, time)? >= min_elevation_deg)
+        };
+        let mut passes = Vec::new();
+        if end <= start {
+            return Ok(passes);
+        }
+
+        let mut previous = start;
+        let mut was_visible = visible(start)?;
+        let mut rise = was_visible.then_some(start);
+        while previous < end {
+            let time = (previous + SEARCH_STEP).min(end);
+            let is_visible = visible(time)?;
+            if is_visible != was_visible {
+                // bisect to the last sample on the previous side of the crossing
+                let (mut before, mut after) = (previous, time);
+                while after - before > CROSSING_TOLERANCE {
+                    let middle = before + (after - before) / 2;
+                    if visible(middle)? == was_visible {
+                        before = middle;
+                    } else {
+                        after = middle;
+                    }
+                }
+                match rise.take() {
+                    Some(rise) => passes.push((rise, before)),
+                    None => rise = Some(after),
+                }
+            }
+            was_visible = is_visible;
+            previous = time;
+        }
+        if let Some(rise) = rise {
+            passes.push((rise, end));
+        }
+        Ok(passes)
+    }
+}
+
+/// Look angles, range and their rates from a satellite's position and velocity
+/// in the site's east-north-up frame. The angle rates are left out at the
+/// zenith, where they divide by the horizontal distance.
+fn tracking_parameters(
+    time: DateTime<Utc>,
+    [east, north, up]: [f64; 3],
+    [v_east, v_north, v_up]: [f64; 3],
+) -> TrackingParameters {
+    let horizontal = east.hypot(north);
+    let range = horizontal.hypot(up);
+    let range_rate = (east * v_east + north * v_north + up * v_up) / range;
+    let (azimuth_rate, elevation_rate) = if horizontal < ZENITH_HORIZONTAL_KM {
+        (None, None)
+    } else {
+        (
+            Some((north * v_east - east * v_north) / (horizontal * horizontal)),
+            Some((v_up - range_rate * up / range) / horizontal),
+        )
+    };
+    TrackingParameters {
+        time,
+        azimuth_deg: Degrees(east.atan2(north).to_degrees().rem_euclid(360.0)),
+        elevation_deg: Degrees(up.atan2(horizontal).to_degrees()),
+        azimuth_rate_deg_s: azimuth_rate.map(f64::to_degrees),
+        elevation_rate_deg_s: elevation_rate.map(f64::to_degrees),
+        range_km: Some(Km(range)),
+        range_rate_km_s: Some(range_rate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SatelliteFrequency, Tle};
+
+    const ISS: (&str, &str) = (
+        "1 25544U 98067A   20194.88612269 -.00002218  00000-0 -31515-4 0  9992",
+        "2 25544  51.6461 221.2784 0001413  89.1723 280.4612 15.49507896236008",
+    );
+
+    fn iss() -> Propagator {
+        let omm = Tle::new(ISS.0, ISS.1).unwrap().to_omm().unwrap();
+        Propagator::new("iss", &[omm]).unwrap()
+    }
+
+    fn groundstation() -> Groundstation {
+        let time = "2020-07-13T00:00:00Z".parse().unwrap();
+        Groundstation {
+            id: "gs".to_string(),
+            name: "Boulder".to_string(),
+            latitude: Degrees(40.015),
+            longitude: Degrees(-105.27),
+            altitude: 1655.0,
+            user_id: "user".to_string(),
+            created_at: time,
+            updated_at: time,
+        }
+    }
+
+    fn request(min_elevation_deg: f64) -> SatelliteAccessToGroundstationRequest {
+        SatelliteAccessToGroundstationRequest {
+            groundstation_id: "gs".to_string(),
+            start: "2020-07-13T00:00:00Z".parse().unwrap(),
+            end: "2020-07-15T00:00:00Z".parse().unwrap(),
+            min_elevation_deg: Degrees(min_elevation_deg),
+            min_duration_minutes: 0.0,
+            min_frequency_mhz: None,
+            max_frequency_mhz: None,
+        }
+    }
+
+    #[test]
+    fn passes_start_and_end_at_the_elevation_mask() {
+        let accesses = solve_horizon_access(&groundstation(), &[iss()], &request(10.0)).unwrap();
+        assert!(!accesses.is_empty());
+        for access in &accesses {
+            assert!((access.start.elevation_deg.value() - 10.0).abs() < 1e-2);
+            assert!((access.end.elevation_deg.value() - 10.0).abs() < 1e-2);
+            assert!(access.start.elevation_rate_deg_s.unwrap() > 0.0);
+            assert!(access.end.elevation_rate_deg_s.unwrap() < 0.0);
+            // approaching at rise, receding at set
+            assert!(access.start.range_rate_km_s.unwrap() < 0.0);
+            assert!(access.end.range_rate_km_s.unwrap() > 0.0);
+            assert!(access.duration_minutes > 0.0 && access.duration_minutes < 15.0);
+        }
+        let lower = solve_horizon_access(&groundstation(), &[iss()], &request(0.0)).unwrap();
+        assert!(lower.len() >= accesses.len());
+    }
+
+    #[test]
+    fn rates_match_finite_differences() {
+        let site = Site::new(Degrees(40.015), Degrees(-105.27), Km(1.655));
+        let satellite = iss();
+        let time: DateTime<Utc> = "2020-07-13T12:00:00Z".parse().unwrap();
+        let now = site.track(&satellite, time).unwrap();
+        let later = site
+            .track(&satellite, time + Duration::milliseconds(100))
+            .unwrap();
+        let rate = |a: f64, b: f64| (b - a) / 0.1;
+        assert!(
+            (rate(
+                now.range_km.unwrap().value(),
+                later.range_km.unwrap().value()
+            ) - now.range_rate_km_s.unwrap())
+            .abs()
+                < 1e-3
+        );
+        assert!(
+            (rate(now.elevation_deg.value(), later.elevation_deg.value())
+                - now.elevation_rate_deg_s.unwrap())
+            .abs()
+                < 1e-3
+        );
+        assert!(
+            (rate(now.azimuth_deg.value(), later.azimuth_deg.value())
+                - now.azimuth_rate_deg_s.unwrap())
+            .abs()
+                < 1e-3
+        );
+    }
+
+    #[test]
+    fn angle_rates_are_left_out_at_the_zenith() {
+        let time = "2020-07-13T12:00:00Z".parse().unwrap();
+        let overhead = tracking_parameters(time, [0.0, 0.0, 420.0], [7.6, 0.0, 0.0]);
+        assert_eq!(overhead.elevation_deg, Degrees(90.0));
+        assert_eq!(overhead.azimuth_rate_deg_s, None);
+        assert_eq!(overhead.elevation_rate_deg_s, None);
+        assert_eq!(overhead.range_rate_km_s, Some(0.0));
+
+        let near = tracking_parameters(time, [0.0, 1.0, 420.0], [7.6, 0.0, 0.0]);
+        assert!(near.azimuth_rate_deg_s.unwrap().is_finite());
+        assert!(near.elevation_rate_deg_s.unwrap().is_finite());
+    }
+
+    fn fov_request(
+        time: DateTime<Utc>,
+        right_ascension_deg: f64,
+        declination_deg: f64,
+    ) -> FOVAccessRequest {
+        FOVAccessRequest {
+            time,
+            right_ascension_deg: Degrees(right_ascension_deg),
+            declination_deg: Degrees(declination_deg),
+            field_of_view_deg: Degrees(2.0),
+            sensor_latitude_deg: Degrees(40.015),
+            sensor_longitude_deg: Degrees(-105.27),
+            sensor_altitude_km: Km(1.655),
+            sensor_frame: SensorFrame::J2000,
+        }
+    }
+
+    #[test]
+    fn fov_finds_a_satellite_at_the_boresight() {
+        let pass = &solve_horizon_access(&groundstation(), &[iss()], &request(30.0)).unwrap()[0];
+        let time = pass.start.time + Duration::seconds(10);
+        let site = Site::new(Degrees(40.015), Degrees(-105.27), Km(1.655));
+        let (ra, dec) = site
+            .right_ascension_declination(&iss(), time, SensorFrame::J2000)
+            .map(|(ra, dec)| (ra.value(), dec.value()))
+            .unwrap();
+
+        let targets = solve_fov_access(&[iss()], &fov_request(time, ra + 0.5, dec)).unwrap();
+        assert_eq!(targets.len(), 1);
+        assert!((targets[0].right_ascension_deg.value() - ra).abs() < 1e-9);
+        assert!((targets[0].declination_deg.value() - dec).abs() < 1e-9);
+        let elsewhere = fov_request(time, ra + 5.0, dec);
+        assert!(solve_fov_access(&[iss()], &elsewhere).unwrap().is_empty());
+
+        // below the horizon an hour later, even with the sensor pointed at it
+        let later = time + Duration::hours(1);
+        let (ra, dec) = site
+            .right_ascension_declination(&iss(), later, SensorFrame::J2000)
+            .map(|(ra, dec)| (ra.value(), dec.value()))
+            .unwrap();
+        assert!(site.elevation_deg(&iss(), later).unwrap() < 0.0);
+        let blocked = fov_request(later, ra, dec);
+        assert!(solve_fov_access(&[iss()], &blocked).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rectangular_sensor_honours_rotation() {
+        let wide = SensorShape::Rectangular {
+            width_deg: Degrees(4.0),
+            height_deg: Degrees(1.0),
+            rotation_deg: Degrees(0.0),
+        };
+        // 1.5 degrees east of the boresight, on the equator
+        assert!(wide.contains(Degrees(10.0), Degrees(0.0), Degrees(11.5), Degrees(0.0)));
+        assert!(!wide.contains(Degrees(10.0), Degrees(0.0), Degrees(10.0), Degrees(1.5)));
+
+        let turned = SensorShape::Rectangular {
+            width_deg: Degrees(4.0),
+            height_deg: Degrees(1.0),
+            rotation_deg: Degrees(90.0),
+        };
+        assert!(!turned.contains(Degrees(10.0), Degrees(0.0), Degrees(11.5), Degrees(0.0)));
+        assert!(turned.contains(Degrees(10.0), Degrees(0.0), Degrees(10.0), Degrees(1.5)));
+        // never matches the opposite hemisphere
+        assert!(!turned.contains(Degrees(10.0), Degrees(0.0), Degrees(190.0), Degrees(0.0)));
+
+        let circle = SensorShape::Circular {
+            field_of_view_deg: Degrees(3.0),
+        };
+        assert!(circle.contains(Degrees(359.5), Degrees(0.0), Degrees(0.5), Degrees(0.0)));
+        assert!(!circle.contains(Degrees(10.0), Degrees(0.0), Degrees(11.0), Degrees(1.2)));
+    }
+
+    #[test]
+    fn frequency_filter_requires_an_overlapping_band() {
+        let mut request = request(0.0);
+        request.min_frequency_mhz = Some(MHz(430.0));
+        request.max_frequency_mhz = Some(MHz(440.0));
+        assert!(
+            solve_horizon_access(&groundstation(), &[iss()], &request)
+                .unwrap()
+                .is_empty()
+        );
+
+        let satellite = crate::Satellite {
+            id: "iss".to_string(),
+            name: "ISS".to_string(),
+            norad_id: Some(25544),
+            cospar_id: None,
+            status: crate::SatelliteStatus::Active,
+            owner: None,
+            frequencies: vec![SatelliteFrequency {
+                min_frequency_mhz: MHz(437.8),
+                max_frequency_mhz: MHz(437.8),
+                description: None,
+            }],
+        };
+        let elset = crate::ElementSet {
+            id: "elset".to_string(),
+            satellite_id: "iss".to_string(),
+            epoch: request.start,
+            source: None,
+            created_at: request.start,
+            tle: Some(Tle::new(ISS.0, ISS.1).unwrap()),
+            omm: None,
+        };
+        let tagged = Propagator::for_satellite(&satellite, &[elset]).unwrap();
+        let accesses = solve_horizon_access(&groundstation(), &[tagged], &request).unwrap();
+        assert!(!accesses.is_empty());
+        assert_eq!(accesses[0].satellite_name.as_deref(), Some("ISS"));
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State as Extract;
+use axum::http::Uri;
+use axum::response::{IntoResponse, Response};
+use reqwest::header::{
+    AUTHORIZATION, CONTENT_TYPE, ETAG, HeaderMap, HeaderName, IF_MATCH, IF_UNMODIFIED_SINCE,
+    RETRY_AFTER, USER_AGENT,
+};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+use crate::{CitraClient, CitraClientBuilder, LemonaidError};
+
+/// Replaces the bearer token wherever it appears in a cassette.
+const REDACTED: &str = "<redacted>";
+
+/// Request headers passed on to the API while recording.
+const FORWARDED_REQUEST_HEADERS: [HeaderName; 6] = [
+    CONTENT_TYPE,
+    AUTHORIZATION,
+    IF_MATCH,
+    IF_UNMODIFIED_SINCE,
+    USER_AGENT,
+    HeaderName::from_static("idempotency-key"),
+];
+
+/// Response headers kept in cassettes, since the client acts on them.
+const RECORDED_RESPONSE_HEADERS: [HeaderName; 2] = [ETAG, RETRY_AFTER];
+
+/// Recorded API traffic, stored as pretty-printed JSON.
+///
+/// Cassettes never contain the bearer token: the `Authorization` header is not
+/// recorded, and occurrences of the token in bodies are replaced by
+/// `<redacted>`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+/// One request and the response the API gave to it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub request: CassetteRequest,
+    pub response: CassetteResponse,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CassetteRequest {
+    pub method: String,
+    /// Path without the query string, e.g. `/telescopes/abc/tasks`.
+    pub path: String,
+    /// Raw query string, without the leading `?`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CassetteResponse {
+    pub status: u16,
+    /// `ETag` and `Retry-After`, if the API sent them.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// The body, if it is JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    /// The body, if it is not empty and not JSON, e.g. a plain-text error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl Cassette {
+    pub fn load(path: impl AsRef<Path>) -> Result<Cassette, LemonaidError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|err| {
+            LemonaidError::Config(format!("cannot read {}: {}", path.display(), err))
+        })?;
+        serde_json::from_str(&text).map_err(|err| {
+            LemonaidError::Config(format!("invalid cassette {}: {}", path.display(), err))
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), LemonaidError> {
+        let path = path.as_ref();
+        let mut text = serde_json::to_string_pretty(self).map_err(LemonaidError::Serialization)?;
+        text.push('\n');
+        std::fs::write(path, text).map_err(|err| {
+            LemonaidError::Config(format!("cannot write {}: {}", path.display(), err))
+        })
+    }
+}
+
+impl CassetteRequest {
+    fn new(method: &Method, uri: &Uri, body: &[u8]) -> Self {
+        CassetteRequest {
+            method: method.to_string(),
+            path: uri.path().to_string(),
+            query: uri.query().map(str::to_string),
+            body: (!body.is_empty()).then(|| {
+                serde_json::from_slice(body)
+                    .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()))
+            }),
+        }
+    }
+}
+
+impl CassetteResponse {
+    fn new(status: StatusCode, headers: &HeaderMap, body: &str) -> Self {
+        let headers = RECORDED_RESPONSE_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = headers.get(name)?.to_str().ok()?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+        let (body, text) = match serde_json::from_str(body) {
+            Ok(json) => (Some(json), None),
+            Err(_) if body.is_empty() => (None, None),
+            Err(_) => (None, Some(body.to_string())),
+        };
+        CassetteResponse {
+            status: status.as_u16(),
+            headers,
+            body,
+            text,
+        }
+    }
+}
+
+impl IntoResponse for &CassetteResponse {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = match (&self.body, &self.text) {
+            (Some(body), _) => (
+                status,
+                [(CONTENT_TYPE, "application/json")],
+                body.to_string(),
+            )
+                .into_response(),
+            (None, Some(text)) => (status, text.clone()).into_response(),
+            (None, None) => status.into_response(),
+        };
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (name.parse::<HeaderName>(), value.parse()) {
+                response.headers_mut().insert(name, value);
+            }
+        }
+        response
+    }
+}
+
+/// A localhost server that either records traffic to the real API into a
+/// cassette, or replays a cassette offline.
+///
+/// Point a client at [`url`](Self::url) in both modes; only the token differs.
+/// While recording, the cassette file is rewritten after every exchange. On
+/// replay, each request is answered by the first unused interaction with the
+/// same method, path, query and JSON body, or by the last matching one once
+/// all are used. Requests without a match get `501 Not Implemented`.
+///
+/// ```no_run
+/// use lemonaid::testing::CassetteServer;
+///
+/// # async fn example() -> Result<(), lemonaid::LemonaidError> {
+/// let path = "tests/cassettes/get_telescope.json";
+/// let server = if std::env::var_os("LEMONAID_RECORD").is_some() {
+///     let token = std::env::var("CITRA_PAT").unwrap();
+///     CassetteServer::record("https://dev.api.citra.space/", path, &token).await?
+/// } else {
+///     CassetteServer::replay(path).await?
+/// };
+/// let telescope = server.client().get_telescope("2b1a...").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct CassetteServer {
+    address: SocketAddr,
+    token: String,
+    state: Arc<Mutex<Player>>,
+    task: JoinHandle<()>,
+}
+
+struct Player {
+    cassette: Cassette,
+    mode: Mode,
+}
+
+enum Mode {
+    Record {
+        upstream: reqwest::Url,
+        client: reqwest::Client,
+        path: PathBuf,
+    },
+    Replay {
+        used: Vec<bool>,
+    },
+}
+
+impl CassetteServer {
+    /// Forwards requests to the API at `upstream`, recording them into a new
+    /// cassette at `path`. Clients made with [`client`](Self::client) use
+    /// `token`.
+    pub async fn record(
+        upstream: &str,
+        path: impl AsRef<Path>,
+        token: &str,
+    ) -> Result<CassetteServer, LemonaidError> {
+        let upstream = reqwest::Url::parse(upstream).map_err(|err| {
+            LemonaidError::Config(format!("invalid upstream URL {}: {}", upstream, err))
+        })?;
+        let path = path.as_ref().to_path_buf();
+        let cassette = Cassette::default();
+        cassette.save(&path)?;
+        let mode = Mode::Record {
+            upstream,
+            client: reqwest::Client::new(),
+            path,
+        };
+        Ok(Self::start(cassette, mode, token).await)
+    }
+
+    /// Serves the cassette at `path` without network access.
+    pub async fn replay(path: impl AsRef<Path>) -> Result<CassetteServer, LemonaidError> {
+        Ok(Self::from_cassette(Cassette::load(path)?).await)
+    }
+
+    /// Serves a cassette built in memory.
+    pub async fn from_cassette(cassette: Cassette) -> CassetteServer {
+        let used = vec![false; cassette.interactions.len()];
+        Self::start(cassette, Mode::Replay { used }, super::MOCK_TOKEN).await
+    }
+
+    async fn start(cassette: Cassette, mode: Mode, token: &str) -> CassetteServer {
+        let state = Arc::new(Mutex::new(Player { cassette, mode }));
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("cassette server should bind a localhost port");
+        let address = listener
+            .local_addr()
+            .expect("bound listener should have an address");
+        let app = Router::new().fallback(handle).with_state(state.clone());
+        let task = tokio::spawn(async move {
+            axum::serve(listener, app)
+                .await
+                .expect("cassette server should run until aborted");
+        });
+        CassetteServer {
+            address,
+            token: token.to_string(),
+            state,
+            task,
+        }
+    }
+
+    /// Base URL of the server, e.g. `http://127.0.0.1:38211/`.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.address)
+    }
+
+    /// A client builder pointing at this server. When recording it carries
+    /// the real token; when replaying any token is accepted.
+    pub fn client_builder(&self) -> CitraClientBuilder {
+        CitraClient::builder(&self.token).base_url(&self.url())
+    }
+
+    pub fn client(&self) -> CitraClient {
+        self.client_builder()
+            .build()
+            .expect("cassette server URL should be a valid base URL")
+    }
+
+    /// The interactions recorded or loaded so far.
+    pub fn cassette(&self) -> Cassette {
+        lock(&self.state).cassette.clone()
+    }
+}
+
+impl Drop for CassetteServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle(
+    Extract(state): Extract<Arc<Mutex<Player>>>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let forward = {
+        let mut player = lock(&state);
+        let Player { cassette, mode } = &mut *player;
+        match mode {
+            Mode::Replay { used } => {
+                let request = CassetteRequest::new(&method, &uri, &body);
+                let matching: Vec<usize> = (0..cassette.interactions.len())
+                    .filter(|&i| cassette.interactions[i].request == request)
+                    .collect();
+                let Some(&index) = matching.iter().find(|&&i| !used[i]).or(matching.last()) else {
+                    let message = format!(
+                        "no recorded interaction for {} {}",
+                        method,
+                        uri.path_and_query().map_or(uri.path(), |p| p.as_str())
+                    );
+                    return (StatusCode::NOT_IMPLEMENTED, message).into_response();
+                };
+                used[index] = true;
+                return cassette.interactions[index].response.into_response();
+            }
+            Mode::Record {
+                upstream, client, ..
+            } => (upstream.clone(), client.clone()),
+        }
+    };
+
+    let (upstream, client) = forward;
+    let (status, response_headers, text) =
+        match send_upstream(&client, &upstream, &method, &uri, &headers, &body).await {
+            Ok(response) => response,
+            Err(err) => {
+                let message = format!("recording failed: {}", err);
+                return (StatusCode::BAD_GATEWAY, message).into_response();
+            }
+        };
+
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .filter(|token| !token.is_empty());
+    let redact = |text: &str| match token {
+        Some(token) => text.replace(token, REDACTED),
+        None => text.to_string(),
+    };
+    let interaction = Interaction {
+        request: CassetteRequest::new(
+            &method,
+            &uri,
+            redact(&String::from_utf8_lossy(&body)).as_bytes(),
+        ),
+        response: CassetteResponse::new(status, &response_headers, &redact(&text)),
+    };
+
+    let mut player = lock(&state);
+    let Player { cassette, mode } = &mut *player;
+    cassette.interactions.push(interaction);
+    if let Mode::Record { path, .. } = mode
+        && let Err(err) = cassette.save(path)
+    {
+        return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response();
+    }
+    drop(player);
+    // the client gets the unredacted response
+    (&CassetteResponse::new(status, &response_headers, &text)).into_response()
+}
+
+async fn send_upstream(
+    client: &reqwest::Client,
+    upstream: &reqwest::Url,
+    method: &Method,
+    uri: &Uri,
+    headers: &HeaderMap,
+    body: &Bytes,
+) -> Result<(StatusCode, HeaderMap, String), reqwest::Error> {
+    // the path is already percent-encoded, so it is joined as-is
+    let mut url = upstream.clone();
+    url.set_path(&format!(
+        "{}{}",
+        upstream.path().trim_end_matches('/'),
+        uri.path()
+    ));
+    url.set_query(uri.query());
+
+    let mut request = client.request(method.clone(), url).body(body.to_vec());
+    for name in FORWARDED_REQUEST_HEADERS.iter() {
+        if let Some(value) = headers.get(name) {
+            request = request.header(name, value);
+        }
+    }
+    let response = request.send().await?;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let text = response.text().await?;
+    Ok((status, headers, text))
+}
+
+fn lock(state: &Mutex<Player>) -> MutexGuard<'_, Player> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
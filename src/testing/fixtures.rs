@@ -0,0 +1,219 @@
+//! Plausible entities for seeding a [`MockServer`](super::MockServer).
+//!
+//! All timestamps are derived from [`epoch`], so fixtures are the same on every
+//! run. Fields are public and can be adjusted before inserting.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use super::MOCK_USER_ID;
+use crate::units::{Arcsec, Degrees, Hz, Km, MHz};
+use crate::{
+    Antenna, ElementSet, FOVAccessResponse, Groundstation, HorizonAccess, RFCapture, RFCaptureData,
+    RFDetection, RFPowerSpectralDensity, Satellite, SatelliteFrequency, SatelliteStatus, Task,
+    TaskStatus, Telescope, Tle, TrackingParameters,
+};
+
+/// 2025-01-01T00:00:00Z, the creation time of all fixtures.
+pub fn epoch() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+}
+
+/// A ground station in Boulder, Colorado.
+pub fn groundstation(id: &str) -> Groundstation {
+    Groundstation {
+        id: id.to_string(),
+        name: format!("Ground station {}", id),
+        latitude: Degrees(40.015),
+        longitude: Degrees(-105.27),
+        altitude: 1655.0,
+        user_id: MOCK_USER_ID.to_string(),
+        created_at: epoch(),
+        updated_at: epoch(),
+    }
+}
+
+/// A telescope with the defaults of [`TelescopeCreateRequest`](crate::TelescopeCreateRequest),
+/// not attached to a ground station.
+pub fn telescope(id: &str) -> Telescope {
+    Telescope {
+        id: id.to_string(),
+        name: format!("Telescope {}", id),
+        groundstation_id: None,
+        user_id: MOCK_USER_ID.to_string(),
+        user_group_id: None,
+        satellite_id: None,
+        created_at: epoch(),
+        last_connected_at: None,
+        angular_noise_arcsec: Arcsec(2.0),
+        field_of_view_deg: Degrees(1.0),
+        limiting_magnitude: 12.0,
+        min_elevation_deg: Degrees(20.0),
+        max_slew_rate_deg_per_sec: 3.0,
+        home_azimuth_deg: Degrees(0.0),
+        home_elevation_deg: Degrees(90.0),
+        automated_scheduling: false,
+    }
+}
+
+/// A UHF antenna covering 430–440 MHz, not attached to a ground station.
+pub fn antenna(id: &str) -> Antenna {
+    Antenna {
+        id: id.to_string(),
+        user_id: MOCK_USER_ID.to_string(),
+        user_group_id: None,
+        groundstation_id: None,
+        satellite_id: None,
+        created_at: epoch(),
+        last_connected_at: None,
+        name: format!("Antenna {}", id),
+        min_frequency_hz: Hz(430e6),
+        max_frequency_hz: Hz(440e6),
+        min_elevation_deg: Degrees(10.0),
+        max_slew_rate_deg_per_sec: 5.0,
+        home_azimuth_deg: Degrees(0.0),
+        home_elevation_deg: Degrees(90.0),
+        half_power_beam_width_deg: Degrees(30.0),
+    }
+}
+
+/// An active satellite with a 435–438 MHz downlink, whose NORAD id is `id` if
+/// it is a number.
+pub fn satellite(id: &str) -> Satellite {
+    Satellite {
+        id: id.to_string(),
+        name: format!("Satellite {}", id),
+        norad_id: id.parse().ok(),
+        cospar_id: None,
+        status: SatelliteStatus::Active,
+        owner: None,
+        frequencies: vec![SatelliteFrequency {
+            min_frequency_mhz: MHz(435.0),
+            max_frequency_mhz: MHz(438.0),
+            description: Some("Downlink".to_string()),
+        }],
+    }
+}
+
+/// An element set of the ISS whose epoch is `epoch`. The TLE is the same for
+/// every epoch.
+pub fn elset(id: &str, epoch: DateTime<Utc>) -> ElementSet {
+    ElementSet {
+        id: id.to_string(),
+        satellite_id: "25544".to_string(),
+        epoch,
+        source: None,
+        created_at: epoch,
+        tle: Some(Tle {
+            line1: "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927"
+                .to_string(),
+            line2: "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537"
+                .to_string(),
+        }),
+        omm: None,
+    }
+}
+
+/// A one-hour task for the ISS on a telescope, starting a day after [`epoch`].
+pub fn task(id: &str, telescope_id: &str, status: TaskStatus) -> Task {
+    let start = epoch() + Duration::days(1);
+    Task {
+        id: id.to_string(),
+        task_type: "Track".to_string(),
+        status,
+        created_at: epoch(),
+        updated_at: epoch(),
+        task_start: start,
+        task_stop: start + Duration::hours(1),
+        user_id: Some(MOCK_USER_ID.to_string()),
+        username: None,
+        satellite_id: "25544".to_string(),
+        satellite_name: Some("ISS (ZARYA)".to_string()),
+        telescope_id: Some(telescope_id.to_string()),
+        antenna_id: None,
+        telescope_name: None,
+        antenna_name: None,
+        ground_station_id: None,
+        ground_station_name: None,
+        priority: 0,
+        scheduled_start: None,
+        scheduled_stop: None,
+        range_km: None,
+        range_rate_km_s: None,
+        right_ascension: None,
+        right_ascension_rate: None,
+        declination: None,
+        declination_rate: None,
+    }
+}
+
+/// A ten-minute capture with a single detection at 437 MHz.
+pub fn rf_capture(id: &str, antenna_id: &str) -> RFCapture {
+    RFCapture {
+        id: id.to_string(),
+        antenna_id: antenna_id.to_string(),
+        user_id: MOCK_USER_ID.to_string(),
+        capture_start: epoch(),
+        capture_end: epoch() + Duration::minutes(10),
+        data: rf_capture_data(),
+        detection_count: 1,
+        task_id: None,
+        created_at: epoch(),
+    }
+}
+
+/// Spectrum data with a single detection at 437 MHz.
+pub fn rf_capture_data() -> RFCaptureData {
+    RFCaptureData {
+        detections: vec![RFDetection {
+            center_frequency_hz: 437_000_000,
+            bandwidth_hz: 12_500,
+            strength_dbm: -110.0,
+            snr_db: 12.0,
+        }],
+        power_spectral_density: RFPowerSpectralDensity {
+            frequency_hz: vec![436_990_000, 437_000_000, 437_010_000],
+            power_dbm_per_hz: vec![-160.0, -150.0, -160.0],
+        },
+    }
+}
+
+/// A ten-minute pass of a satellite over a ground station.
+pub fn access_window(
+    groundstation_id: &str,
+    satellite_id: &str,
+    start: DateTime<Utc>,
+) -> HorizonAccess {
+    let end = start + Duration::minutes(10);
+    let horizon = |time, azimuth| TrackingParameters {
+        time,
+        azimuth_deg: Degrees(azimuth),
+        elevation_deg: Degrees(10.0),
+        azimuth_rate_deg_s: None,
+        elevation_rate_deg_s: None,
+        range_km: Some(Km(1400.0)),
+        range_rate_km_s: None,
+    };
+    HorizonAccess {
+        satellite_id: satellite_id.to_string(),
+        satellite_name: None,
+        groundstation_id: groundstation_id.to_string(),
+        groundstation_name: None,
+        start: horizon(start, 220.0),
+        end: horizon(end, 40.0),
+        duration_minutes: 10.0,
+    }
+}
+
+/// A satellite seen at the given J2000 coordinates.
+pub fn fov_result(
+    satellite_id: &str,
+    right_ascension: Degrees,
+    declination: Degrees,
+) -> FOVAccessResponse {
+    FOVAccessResponse {
+        satellite_id: satellite_id.to_string(),
+        satellite_name: None,
+        right_ascension_deg: right_ascension,
+        declination_deg: declination,
+    }
+}
@@ -0,0 +1,452 @@
+//! An in-process mock of the Citra Space API, for testing code built on
+//! [`CitraClient`] without network access.
+//!
+//! The server listens on a random localhost port and keeps its data in memory.
+//! It implements the telescope, ground station, antenna, task, satellite,
+//! access, RF capture and element set routes. Data can be seeded from
+//! [`fixtures`] and inspected afterwards, and [`FaultRule`]s make selected
+//! requests slow or fail.
+//!
+//! [`CassetteServer`] instead records real API traffic into a file and
+//! replays it later, for any route.
+//!
+//! ```
+//! use lemonaid::TaskStatus;
+//! use lemonaid::testing::{Fault, FaultRule, MockServer, fixtures};
+//! use reqwest::StatusCode;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), lemonaid::LemonaidError> {
+//! let server = MockServer::start().await;
+//! server.insert_telescope(&fixtures::telescope("scope-1"));
+//! server.insert_task(&fixtures::task("task-1", "scope-1", TaskStatus::Scheduled));
+//! server.inject(FaultRule::new(Fault::Status(StatusCode::SERVICE_UNAVAILABLE)).times(1));
+//!
+//! // the first attempt fails with a 503 and is retried
+//! let tasks = server.client().list_tasks_for_telescope("scope-1").await?;
+//! assert_eq!(tasks.len(), 1);
+//! assert_eq!(server.requests().len(), 2);
+//! # Ok(())
+//! # }
+//! ```
+
+mod cassette;
+pub mod fixtures;
+mod routes;
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+pub use cassette::{Cassette, CassetteRequest, CassetteResponse, CassetteServer, Interaction};
+
+use crate::{
+    Antenna, CitraClient, CitraClientBuilder, ElementSet, FOVAccessResponse, Groundstation,
+    HorizonAccess, RFCapture, Satellite, Task, Telescope,
+};
+
+/// Token the clients returned by [`MockServer::client`] authenticate with.
+pub const MOCK_TOKEN: &str = "mock-token";
+
+/// User id the mock server assigns to everything created through it.
+pub const MOCK_USER_ID: &str = "mock-user";
+
+/// A running mock API server. It shuts down when dropped.
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    task: JoinHandle<()>,
+}
+
+/// Something that goes wrong while serving a request.
+#[derive(Debug, Clone)]
+pub enum Fault {
+    /// Waits before handling the request normally.
+    Latency(Duration),
+    /// Answers `429 Too Many Requests`, with a `Retry-After` header in whole
+    /// seconds if given.
+    RateLimited { retry_after: Option<Duration> },
+    /// Answers with this status and a plain-text message.
+    Status(StatusCode),
+    /// Answers `200 OK` with a truncated JSON body.
+    MalformedJson,
+}
+
+/// A fault together with the requests it applies to.
+///
+/// Without further restrictions the fault applies to every request.
+#[derive(Debug, Clone)]
+pub struct FaultRule {
+    fault: Fault,
+    method: Option<Method>,
+    path_prefix: Option<String>,
+    remaining: Option<usize>,
+}
+
+impl FaultRule {
+    pub fn new(fault: Fault) -> Self {
+        FaultRule {
+            fault,
+            method: None,
+            path_prefix: None,
+            remaining: None,
+        }
+    }
+
+    /// Applies only to requests with this method.
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Applies only to requests whose path starts with `prefix`, e.g. `/tasks`.
+    pub fn path(mut self, prefix: &str) -> Self {
+        self.path_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Applies to the next `count` matching requests only.
+    pub fn times(mut self, count: usize) -> Self {
+        self.remaining = Some(count);
+        self
+    }
+
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        self.remaining != Some(0)
+            && self.method.as_ref().is_none_or(|m| m == method)
+            && self
+                .path_prefix
+                .as_deref()
+                .is_none_or(|prefix| path.starts_with(prefix))
+    }
+}
+
+/// A request as received by the mock server, including ones answered with a
+/// fault.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub path: String,
+    /// Decoded query parameters, in order.
+    pub query: Vec<(String, String)>,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// The body decoded as JSON, or `None` if it is empty or not JSON.
+    pub fn json(&self) -> Option<Value> {
+        serde_json::from_slice(&self.body).ok()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct State {
+    collections: [Vec<Record>; Collection::COUNT],
+    faults: Vec<FaultRule>,
+    requests: Vec<RecordedRequest>,
+    required_token: Option<String>,
+    next_id: u64,
+}
+
+/// A stored resource in its JSON form, with the version its ETag is made from.
+pub(crate) struct Record {
+    id: String,
+    value: Value,
+    version: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Collection {
+    Telescopes,
+    Groundstations,
+    Antennas,
+    Tasks,
+    RfCaptures,
+    AccessWindows,
+    FovResults,
+    Elsets,
+    Satellites,
+}
+
+impl Collection {
+    const COUNT: usize = 9;
+
+    /// Prefix of generated ids, and the name used in error messages.
+    fn name(self) -> &'static str {
+        match self {
+            Collection::Telescopes => "telescope",
+            Collection::Groundstations => "ground-station",
+            Collection::Antennas => "antenna",
+            Collection::Tasks => "task",
+            Collection::RfCaptures => "rf-capture",
+            Collection::AccessWindows => "access",
+            Collection::FovResults => "fov",
+            Collection::Elsets => "elset",
+            Collection::Satellites => "satellite",
+        }
+    }
+}
+
+impl State {
+    fn records(&self, collection: Collection) -> &Vec<Record> {
+        &self.collections[collection as usize]
+    }
+
+    fn records_mut(&mut self, collection: Collection) -> &mut Vec<Record> {
+        &mut self.collections[collection as usize]
+    }
+
+    fn get(&self, collection: Collection, id: &str) -> Option<&Record> {
+        self.records(collection)
+            .iter()
+            .find(|record| record.id == id)
+    }
+
+    fn get_mut(&mut self, collection: Collection, id: &str) -> Option<&mut Record> {
+        self.records_mut(collection)
+            .iter_mut()
+            .find(|record| record.id == id)
+    }
+
+    fn values(&self, collection: Collection) -> impl Iterator<Item = &Value> {
+        self.records(collection).iter().map(|record| &record.value)
+    }
+
+    /// A sequential id such as `task-3`, skipping ids taken by fixtures.
+    fn generate_id(&mut self, collection: Collection) -> String {
+        loop {
+            self.next_id += 1;
+            let id = format!("{}-{}", collection.name(), self.next_id);
+            if self.get(collection, &id).is_none() {
+                return id;
+            }
+        }
+    }
+
+    /// Stores a resource, replacing any with the same id. Values without an
+    /// `id` field get a generated one.
+    fn insert(&mut self, collection: Collection, mut value: Value) -> &Record {
+        let id = match value.get("id").and_then(Value::as_str) {
+            Some(id) => id.to_string(),
+            None => {
+                let id = self.generate_id(collection);
+                value["id"] = Value::String(id.clone());
+                id
+            }
+        };
+        let records = self.records_mut(collection);
+        records.retain(|record| record.id != id);
+        records.push(Record {
+            id,
+            value,
+            version: 1,
+        });
+        records.last().expect("record was just pushed")
+    }
+
+    fn remove(&mut self, collection: Collection, id: &str) -> bool {
+        let records = self.records_mut(collection);
+        let before = records.len();
+        records.retain(|record| record.id != id);
+        records.len() != before
+    }
+}
+
+impl Record {
+    fn etag(&self) -> String {
+        format!("\"{}-v{}\"", self.id, self.version)
+    }
+}
+
+impl MockServer {
+    /// Starts an empty server on a random localhost port.
+    ///
+    /// # Panics
+    ///
+    /// If no port can be bound, or outside a Tokio runtime.
+    pub async fn start() -> MockServer {
+        let state = Arc::new(Mutex::new(State::default()));
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("mock server should bind a localhost port");
+        let address = listener
+            .local_addr()
+            .expect("bound listener should have an address");
+        let app = routes::router(state.clone());
+        let task = tokio::spawn(async move {
+            axum::serve(listener, app)
+                .await
+                .expect("mock server should run until aborted");
+        });
+        MockServer {
+            address,
+            state,
+            task,
+        }
+    }
+
+    /// Base URL of the server, e.g. `http://127.0.0.1:38211/`.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.address)
+    }
+
+    /// A client builder pointing at this server, authenticating with
+    /// [`MOCK_TOKEN`].
+    pub fn client_builder(&self) -> CitraClientBuilder {
+        CitraClient::builder(MOCK_TOKEN).base_url(&self.url())
+    }
+
+    /// A client with default settings pointing at this server.
+    pub fn client(&self) -> CitraClient {
+        self.client_builder()
+            .build()
+            .expect("mock server URL should be a valid base URL")
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+
+    fn insert<T: Serialize>(&self, collection: Collection, value: &T) {
+        let value = serde_json::to_value(value).expect("fixtures should serialize to JSON");
+        self.state().insert(collection, value);
+    }
+
+    fn get<T: DeserializeOwned>(&self, collection: Collection, id: &str) -> Option<T> {
+        let state = self.state();
+        let record = state.get(collection, id)?;
+        Some(
+            serde_json::from_value(record.value.clone())
+                .expect("stored resources should decode into their entity type"),
+        )
+    }
+
+    pub fn insert_telescope(&self, telescope: &Telescope) {
+        self.insert(Collection::Telescopes, telescope);
+    }
+
+    pub fn insert_groundstation(&self, groundstation: &Groundstation) {
+        self.insert(Collection::Groundstations, groundstation);
+    }
+
+    pub fn insert_antenna(&self, antenna: &Antenna) {
+        self.insert(Collection::Antennas, antenna);
+    }
+
+    pub fn insert_task(&self, task: &Task) {
+        self.insert(Collection::Tasks, task);
+    }
+
+    pub fn insert_rf_capture(&self, rf_capture: &RFCapture) {
+        self.insert(Collection::RfCaptures, rf_capture);
+    }
+
+    pub fn insert_elset(&self, elset: &ElementSet) {
+        self.insert(Collection::Elsets, elset);
+    }
+
+    /// Adds a satellite to the catalog, which is listed in insertion order.
+    pub fn insert_satellite(&self, satellite: &Satellite) {
+        self.insert(Collection::Satellites, satellite);
+    }
+
+    /// Adds a pass returned by access searches over its ground station that
+    /// cover its start.
+    pub fn insert_access_window(&self, access: &HorizonAccess) {
+        let value = serde_json::to_value(access).expect("fixtures should serialize to JSON");
+        let mut state = self.state();
+        let id = state.generate_id(Collection::AccessWindows);
+        state.records_mut(Collection::AccessWindows).push(Record {
+            id,
+            value,
+            version: 1,
+        });
+    }
+
+    /// Adds a satellite returned by every field-of-view search.
+    pub fn insert_fov_result(&self, result: &FOVAccessResponse) {
+        let value = serde_json::to_value(result).expect("fixtures should serialize to JSON");
+        let mut state = self.state();
+        let id = state.generate_id(Collection::FovResults);
+        state.records_mut(Collection::FovResults).push(Record {
+            id,
+            value,
+            version: 1,
+        });
+    }
+
+    pub fn telescope(&self, id: &str) -> Option<Telescope> {
+        self.get(Collection::Telescopes, id)
+    }
+
+    pub fn groundstation(&self, id: &str) -> Option<Groundstation> {
+        self.get(Collection::Groundstations, id)
+    }
+
+    pub fn antenna(&self, id: &str) -> Option<Antenna> {
+        self.get(Collection::Antennas, id)
+    }
+
+    pub fn task(&self, id: &str) -> Option<Task> {
+        self.get(Collection::Tasks, id)
+    }
+
+    pub fn rf_capture(&self, id: &str) -> Option<RFCapture> {
+        self.get(Collection::RfCaptures, id)
+    }
+
+    /// Element sets inserted or uploaded for a satellite, in that order.
+    pub fn elsets(&self, satellite_id: &str) -> Vec<ElementSet> {
+        self.state()
+            .values(Collection::Elsets)
+            .filter(|elset| elset["satelliteId"].as_str() == Some(satellite_id))
+            .map(|elset| {
+                serde_json::from_value(elset.clone())
+                    .expect("stored resources should decode into their entity type")
+            })
+            .collect()
+    }
+
+    /// Adds a fault. Rules are checked in the order they were added: every
+    /// matching [`Fault::Latency`] delays the request, and the first other
+    /// matching fault answers it.
+    pub fn inject(&self, rule: FaultRule) {
+        self.state().faults.push(rule);
+    }
+
+    pub fn clear_faults(&self) {
+        self.state().faults.clear();
+    }
+
+    /// Rejects requests not authenticated with `token` with `401`. By default
+    /// any bearer token is accepted.
+    pub fn require_token(&self, token: &str) {
+        self.state().required_token = Some(token.to_string());
+    }
+
+    /// All requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state().requests.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Locks the state, ignoring poisoning by a panicked test thread.
+pub(crate) fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,908 @@
+use std::cmp::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State as Extract;
+use axum::http::Uri;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use reqwest::header::{
+    AUTHORIZATION, CONTENT_TYPE, ETAG, HeaderMap, IF_MATCH, IF_UNMODIFIED_SINCE, RETRY_AFTER,
+};
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+use super::{Collection, Fault, MOCK_USER_ID, RecordedRequest, State, lock};
+use crate::{
+    AntennaCreateRequest, AntennaUpdateRequest, CreateRFCaptureRequest, CreateTaskRequest,
+    ElementSetUploadRequest, ElsetError, FOVAccessRequest, GroundstationCreateRequest,
+    GroundstationUpdateRequest, SatelliteAccessToGroundstationRequest, TaskUpdateRequest,
+    Telescope, TelescopeCreateRequest, TelescopeUpdateRequest,
+};
+
+type Shared = Arc<Mutex<State>>;
+
+/// Either outcome of a route is a complete response; errors short-circuit with `?`.
+type Reply = Result<Response, Rejection>;
+
+pub(super) fn router(state: Shared) -> Router {
+    Router::new().fallback(handle).with_state(state)
+}
+
+async fn handle(
+    Extract(state): Extract<Shared>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let path = uri.path().to_string();
+    let query = parse_query(uri.query().unwrap_or_default());
+
+    let (latency, fault, required_token) = {
+        let mut state = lock(&state);
+        state.requests.push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            query: query.clone(),
+            headers: headers.clone(),
+            body: body.to_vec(),
+        });
+        let mut latency = Duration::ZERO;
+        let mut fault = None;
+        for rule in state.faults.iter_mut() {
+            if !rule.matches(&method, &path) {
+                continue;
+            }
+            match &rule.fault {
+                Fault::Latency(delay) => latency += *delay,
+                _ if fault.is_some() => continue,
+                other => fault = Some(other.clone()),
+            }
+            if let Some(remaining) = &mut rule.remaining {
+                *remaining -= 1;
+            }
+        }
+        (latency, fault, state.required_token.clone())
+    };
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+
+    match fault {
+        Some(Fault::RateLimited { retry_after }) => {
+            let mut response =
+                error(StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded").into_response();
+            if let Some(wait) = retry_after {
+                let seconds = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+                response
+                    .headers_mut()
+                    .insert(RETRY_AFTER, seconds.to_string().parse().expect("digits"));
+            }
+            return response;
+        }
+        Some(Fault::Status(status)) => return error(status, "injected fault").into_response(),
+        Some(Fault::MalformedJson) => {
+            return (
+                StatusCode::OK,
+                [(CONTENT_TYPE, "application/json")],
+                r#"{"id": "trunc"#,
+            )
+                .into_response();
+        }
+        Some(Fault::Latency(_)) | None => {}
+    }
+
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if token.is_empty() || required_token.is_some_and(|required| required != token) {
+        return error(StatusCode::UNAUTHORIZED, "invalid or missing token").into_response();
+    }
+
+    let segments: Vec<String> = path
+        .trim_matches('/')
+        .split('/')
+        .map(percent_decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let request = Request {
+        query: &query,
+        headers: &headers,
+        body: &body,
+    };
+    let mut state = lock(&state);
+    dispatch(&mut state, method.as_str(), &segments, &request)
+        .unwrap_or_else(IntoResponse::into_response)
+}
+
+struct Request<'a> {
+    query: &'a [(String, String)],
+    headers: &'a HeaderMap,
+    body: &'a [u8],
+}
+
+impl Request<'_> {
+    fn param(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn params<'k>(&'k self, key: &'k str) -> impl Iterator<Item = &'k str> {
+        self.query
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Decodes the body as the type the client sends, answering `422` like the
+    /// real API if it does not fit.
+    fn body<T: DeserializeOwned>(&self) -> Result<T, Rejection> {
+        serde_json::from_slice(self.body).map_err(|err| Rejection {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            body: json!({ "detail": [{ "loc": ["body"], "msg": err.to_string() }] }).to_string(),
+            json: true,
+        })
+    }
+
+    /// Decodes the body as a request type and returns it as a JSON object.
+    fn object<T: DeserializeOwned + Serialize>(&self) -> Result<Map<String, Value>, Rejection> {
+        Ok(into_object(to_value(&self.body::<T>()?)))
+    }
+}
+
+fn dispatch(state: &mut State, method: &str, segments: &[&str], request: &Request) -> Reply {
+    use Collection::*;
+
+    match (method, segments) {
+        ("GET", ["telescopes"]) => list(state, Telescopes, request),
+        ("POST", ["telescopes"]) => {
+            create_many::<TelescopeCreateRequest>(state, Telescopes, request)
+        }
+        ("PUT", ["telescopes"]) => replace_many::<Telescope>(state, Telescopes, request),
+        ("DELETE", ["telescopes"]) => delete_many(state, Telescopes, request),
+        ("GET", ["telescopes", id]) => get_one(state, Telescopes, id),
+        ("PATCH", ["telescopes", id]) => {
+            patch::<TelescopeUpdateRequest>(state, Telescopes, id, request)
+        }
+        ("GET", ["telescopes", id, "tasks"]) => {
+            tasks_of(state, Some((Telescopes, id)), "telescopeId", request)
+        }
+
+        ("GET", ["ground-stations"]) => {
+            let page = paginate(state.values(Groundstations).cloned().collect(), request)?;
+            Ok(json_response(
+                StatusCode::OK,
+                &json!({ "groundStations": page }),
+            ))
+        }
+        ("POST", ["ground-stations"]) => {
+            create_many::<GroundstationCreateRequest>(state, Groundstations, request)
+        }
+        ("DELETE", ["ground-stations"]) => delete_many(state, Groundstations, request),
+        ("GET", ["ground-stations", id]) => get_one(state, Groundstations, id),
+        ("PUT", ["ground-stations", id]) => {
+            let updates: Vec<GroundstationCreateRequest> = request.body()?;
+            let update = updates.first().ok_or_else(|| {
+                error(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "expected one ground station",
+                )
+            })?;
+            let record = find_mut(state, Groundstations, id)?;
+            merge(&mut record.value, into_object(to_value(update)));
+            record.value["updateEpoch"] = now();
+            record.version += 1;
+            Ok(json_response(StatusCode::OK, &json!([record.value])))
+        }
+        ("PATCH", ["ground-stations", id]) => {
+            patch::<GroundstationUpdateRequest>(state, Groundstations, id, request)
+        }
+        ("GET", ["ground-stations", id, "tasks"]) => tasks_of(
+            state,
+            Some((Groundstations, id)),
+            "groundStationId",
+            request,
+        ),
+
+        ("GET", ["antennas"]) => list(state, Antennas, request),
+        ("POST", ["antennas"]) => create_many::<AntennaCreateRequest>(state, Antennas, request),
+        ("PUT", ["antennas"]) => replace_many::<crate::Antenna>(state, Antennas, request),
+        ("DELETE", ["antennas"]) => delete_many(state, Antennas, request),
+        ("GET", ["antennas", id]) => get_one(state, Antennas, id),
+        ("PATCH", ["antennas", id]) => patch::<AntennaUpdateRequest>(state, Antennas, id, request),
+        ("GET", ["antennas", id, "tasks"]) => {
+            tasks_of(state, Some((Antennas, id)), "antennaId", request)
+        }
+        ("GET", ["antennas", id, "rf-captures"]) => {
+            find(state, Antennas, id)?;
+            rf_captures_where(state, "antennaId", id, request)
+        }
+
+        ("GET", ["tasks"]) => tasks_of(state, None, "", request),
+        ("POST", ["tasks"]) => create_task(state, request),
+        ("GET", ["tasks", id]) => get_one(state, Tasks, id),
+        ("PUT", ["tasks", id]) => update_task(state, id, request),
+        ("DELETE", ["tasks", id]) => {
+            find(state, Tasks, id)?;
+            state.remove(Tasks, id);
+            Ok(StatusCode::NO_CONTENT.into_response())
+        }
+        ("GET", ["tasks", id, "rf-captures"]) => {
+            find(state, Tasks, id)?;
+            rf_captures_where(state, "taskId", id, request)
+        }
+        ("GET", ["satellites"]) => satellites(state, request),
+        ("GET", ["satellites", id]) => get_one(state, Satellites, id),
+        ("GET", ["satellites", id, "tasks"]) => {
+            let matching = filter_tasks(state, "satelliteId", id, request)?;
+            Ok(json_response(StatusCode::OK, &Value::Array(matching)))
+        }
+        ("GET", ["users", "me", "tasks"]) => {
+            let matching = filter_tasks(state, "userId", MOCK_USER_ID, request)?;
+            Ok(json_response(StatusCode::OK, &Value::Array(matching)))
+        }
+
+        ("POST", ["access", "window", "satellites_to_ground_station"]) => {
+            access_windows(state, request)
+        }
+        ("POST", ["access", "fov"]) => fov_results(state, request),
+
+        ("POST", ["rf-captures"]) => create_rf_capture(state, request),
+        ("GET", ["rf-captures", id]) => get_one(state, RfCaptures, id),
+
+        ("GET", ["satellites", id, "elsets"]) => {
+            let elsets = elsets_of(state, id, request)?;
+            Ok(json_response(StatusCode::OK, &Value::Array(elsets)))
+        }
+        ("GET", ["satellites", id, "elsets", "latest"]) => {
+            let latest = elsets_of(state, id, request)?
+                .into_iter()
+                .max_by_key(|elset| time_field(elset, "epoch"))
+                .ok_or_else(|| {
+                    error(
+                        StatusCode::NOT_FOUND,
+                        &format!("no element sets for satellite {}", id),
+                    )
+                })?;
+            Ok(json_response(StatusCode::OK, &latest))
+        }
+        ("POST", ["elsets"]) => create_elsets(state, request),
+
+        _ => Err(error(
+            StatusCode::NOT_FOUND,
+            "no such route in the mock server",
+        )),
+    }
+}
+
+fn list(state: &State, collection: Collection, request: &Request) -> Reply {
+    let page = paginate(state.values(collection).cloned().collect(), request)?;
+    Ok(json_response(StatusCode::OK, &Value::Array(page)))
+}
+
+fn get_one(state: &State, collection: Collection, id: &str) -> Reply {
+    let record = find(state, collection, id)?;
+    Ok(versioned_response(record))
+}
+
+fn create_many<T: DeserializeOwned + Serialize>(
+    state: &mut State,
+    collection: Collection,
+    request: &Request,
+) -> Reply {
+    let items: Vec<T> = request.body()?;
+    let created: Vec<Value> = items
+        .iter()
+        .map(|item| {
+            let mut object = into_object(to_value(item));
+            object.insert("id".to_string(), json!(state.generate_id(collection)));
+            object.insert("userId".to_string(), json!(MOCK_USER_ID));
+            object.insert("creationEpoch".to_string(), now());
+            match collection {
+                Collection::Groundstations => {
+                    object.insert("updateEpoch".to_string(), now());
+                }
+                _ => {
+                    object.insert("lastConnectionEpoch".to_string(), Value::Null);
+                }
+            }
+            state
+                .insert(collection, Value::Object(object))
+                .value
+                .clone()
+        })
+        .collect();
+    Ok(json_response(StatusCode::OK, &Value::Array(created)))
+}
+
+/// Bulk update with full entities, matched by id.
+fn replace_many<T: DeserializeOwned + Serialize>(
+    state: &mut State,
+    collection: Collection,
+    request: &Request,
+) -> Reply {
+    let items: Vec<T> = request.body()?;
+    let values: Vec<Value> = items.iter().map(to_value).collect();
+    for value in &values {
+        find(state, collection, value["id"].as_str().unwrap_or_default())?;
+    }
+    for value in &values {
+        let record = state
+            .get_mut(collection, value["id"].as_str().unwrap_or_default())
+            .expect("existence was checked above");
+        record.value = value.clone();
+        record.version += 1;
+    }
+    Ok(json_response(StatusCode::OK, &Value::Array(values)))
+}
+
+fn delete_many(state: &mut State, collection: Collection, request: &Request) -> Reply {
+    let ids: Vec<String> = request.body()?;
+    for id in &ids {
+        find(state, collection, id)?;
+    }
+    for id in &ids {
+        state.remove(collection, id);
+    }
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Merges the set fields into the resource, honoring `If-Match` and
+/// `If-Unmodified-Since`.
+fn patch<T: DeserializeOwned + Serialize>(
+    state: &mut State,
+    collection: Collection,
+    id: &str,
+    request: &Request,
+) -> Reply {
+    let changes = request.object::<T>()?;
+    let record = find_mut(state, collection, id)?;
+    if let Some(expected) = request.headers.get(IF_MATCH)
+        && expected.to_str().ok() != Some(record.etag().as_str())
+    {
+        return Err(error(
+            StatusCode::PRECONDITION_FAILED,
+            "ETag does not match",
+        ));
+    }
+    if let Some(since) = request.headers.get(IF_UNMODIFIED_SINCE) {
+        let since = since
+            .to_str()
+            .ok()
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+            .ok_or_else(|| error(StatusCode::BAD_REQUEST, "invalid If-Unmodified-Since"))?;
+        let modified = time_field(&record.value, "updateEpoch")
+            .or_else(|| time_field(&record.value, "creationEpoch"));
+        if modified.is_some_and(|modified| modified.timestamp() > since.timestamp()) {
+            return Err(error(
+                StatusCode::PRECONDITION_FAILED,
+                "resource was modified since",
+            ));
+        }
+    }
+    merge(&mut record.value, changes);
+    if record.value.get("updateEpoch").is_some() {
+        record.value["updateEpoch"] = now();
+    }
+    record.version += 1;
+    Ok(versioned_response(record))
+}
+
+fn create_task(state: &mut State, request: &Request) -> Reply {
+    let task: CreateTaskRequest = request.body()?;
+    let (sensor, sensor_key) = match (&task.telescope_id, &task.antenna_id) {
+        (Some(id), _) => (find(state, Collection::Telescopes, id)?, "telescope"),
+        (None, Some(id)) => (find(state, Collection::Antennas, id)?, "antenna"),
+        (None, None) => {
+            return Err(error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "telescopeId or antennaId is required",
+            ));
+        }
+    };
+    let groundstation_id = sensor.value["groundStationId"].clone();
+    let groundstation_name = groundstation_id
+        .as_str()
+        .and_then(|id| state.get(Collection::Groundstations, id))
+        .map(|record| record.value["name"].clone())
+        .unwrap_or(Value::Null);
+    let mut value = json!({
+        "type": "Track",
+        "status": "Pending",
+        "creationEpoch": now(),
+        "updateEpoch": now(),
+        "taskStart": task.task_start,
+        "taskStop": task.task_stop,
+        "userId": MOCK_USER_ID,
+        "satelliteId": task.satellite_id,
+        "telescopeId": task.telescope_id,
+        "antennaId": task.antenna_id,
+        "groundStationId": groundstation_id,
+        "groundStationName": groundstation_name,
+        "priority": 0,
+    });
+    value[format!("{}Name", sensor_key)] = sensor.value["name"].clone();
+    value["id"] = json!(state.generate_id(Collection::Tasks));
+    let record = state.insert(Collection::Tasks, value);
+    Ok(json_response(StatusCode::OK, &record.value))
+}
+
+/// Replaces the status and any other field present in the body; an explicit
+/// `null` clears the field, as a PUT would.
+fn update_task(state: &mut State, id: &str, request: &Request) -> Reply {
+    let update: TaskUpdateRequest = request.body()?;
+    let sent: Map<String, Value> = request.body()?;
+    if update.priority.is_none() && sent.contains_key("priority") {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "priority must be an integer",
+        ));
+    }
+    let record = find_mut(state, Collection::Tasks, id)?;
+    record.value["status"] = to_value(&update.status);
+    for field in ["priority", "scheduledStart", "scheduledStop"] {
+        if let Some(value) = sent.get(field) {
+            record.value[field] = value.clone();
+        }
+    }
+    record.value["updateEpoch"] = now();
+    record.version += 1;
+    Ok(json_response(StatusCode::OK, &record.value))
+}
+
+/// Tasks of a sensor or ground station (which must exist), or all tasks if
+/// `owner` is `None`, filtered and sorted like the real API.
+fn tasks_of(
+    state: &State,
+    owner: Option<(Collection, &str)>,
+    key: &str,
+    request: &Request,
+) -> Reply {
+    let matching = match owner {
+        Some((collection, id)) => {
+            find(state, collection, id)?;
+            filter_tasks(state, key, id, request)?
+        }
+        None => filter_tasks(state, "", "", request)?,
+    };
+    Ok(json_response(StatusCode::OK, &Value::Array(matching)))
+}
+
+/// Tasks whose `key` field equals `id` (all tasks if `key` is empty) that pass
+/// the [`TaskQuery`](crate::TaskQuery) parameters.
+fn filter_tasks(
+    state: &State,
+    key: &str,
+    id: &str,
+    request: &Request,
+) -> Result<Vec<Value>, Rejection> {
+    let statuses: Vec<&str> = request.params("statuses").collect();
+    let time = |name: &str| -> Result<Option<DateTime<Utc>>, Rejection> {
+        request
+            .param(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| error(StatusCode::BAD_REQUEST, &format!("invalid {}", name)))
+            })
+            .transpose()
+    };
+    let number = |name: &str| -> Result<Option<i64>, Rejection> {
+        request
+            .param(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| error(StatusCode::BAD_REQUEST, &format!("invalid {}", name)))
+            })
+            .transpose()
+    };
+    let (start_after, start_before) = (time("startAfter")?, time("startBefore")?);
+    let (stop_after, stop_before) = (time("stopAfter")?, time("stopBefore")?);
+    let (min_priority, max_priority) = (number("minPriority")?, number("maxPriority")?);
+    let within = |value: Option<DateTime<Utc>>,
+                  after: Option<DateTime<Utc>>,
+                  before: Option<DateTime<Utc>>| {
+        value.is_some_and(|value| {
+            after.is_none_or(|after| value >= after) && before.is_none_or(|before| value <= before)
+        })
+    };
+    let equals = |task: &Value, field: &str, param: &str| {
+        request
+            .param(param)
+            .is_none_or(|expected| task[field].as_str() == Some(expected))
+    };
+
+    let mut tasks: Vec<Value> = state
+        .values(Collection::Tasks)
+        .filter(|task| key.is_empty() || task[key].as_str() == Some(id))
+        .filter(|task| {
+            statuses.is_empty()
+                || task["status"]
+                    .as_str()
+                    .is_some_and(|status| statuses.contains(&status))
+        })
+        .filter(|task| within(time_field(task, "taskStart"), start_after, start_before))
+        .filter(|task| within(time_field(task, "taskStop"), stop_after, stop_before))
+        .filter(|task| equals(task, "satelliteId", "satelliteId"))
+        .filter(|task| equals(task, "groundStationId", "groundStationId"))
+        .filter(|task| equals(task, "type", "type"))
+        .filter(|task| {
+            let priority = task["priority"].as_i64().unwrap_or_default();
+            min_priority.is_none_or(|min| priority >= min)
+                && max_priority.is_none_or(|max| priority <= max)
+        })
+        .cloned()
+        .collect();
+
+    if let Some(field) = request.param("sortBy") {
+        tasks.sort_by(|a, b| compare(&a[field], &b[field]));
+        if request.param("sortOrder") == Some("desc") {
+            tasks.reverse();
+        }
+    }
+    paginate(tasks, request)
+}
+
+/// Satellites that pass the [`SatelliteQuery`](crate::SatelliteQuery) parameters.
+fn satellites(state: &State, request: &Request) -> Reply {
+    let number = |name: &str| -> Result<Option<f64>, Rejection> {
+        request
+            .param(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| error(StatusCode::BAD_REQUEST, &format!("invalid {}", name)))
+            })
+            .transpose()
+    };
+    let name = request.param("name").map(str::to_lowercase);
+    let norad_id = number("noradId")?;
+    let (min_mhz, max_mhz) = (number("minFrequencyMhz")?, number("maxFrequencyMhz")?);
+    let overlaps = |range: &Value| {
+        let (low, high) = (
+            range["minFrequencyMhz"].as_f64(),
+            range["maxFrequencyMhz"].as_f64(),
+        );
+        low.zip(high).is_some_and(|(low, high)| {
+            max_mhz.is_none_or(|max| low <= max) && min_mhz.is_none_or(|min| high >= min)
+        })
+    };
+
+    let matching = state
+        .values(Collection::Satellites)
+        .filter(|satellite| {
+            name.as_ref().is_none_or(|name| {
+                satellite["name"]
+                    .as_str()
+                    .is_some_and(|value| value.to_lowercase().contains(name))
+            })
+        })
+        .filter(|satellite| norad_id.is_none_or(|id| satellite["noradId"].as_f64() == Some(id)))
+        .filter(|satellite| {
+            (min_mhz.is_none() && max_mhz.is_none())
+                || satellite["frequencies"]
+                    .as_array()
+                    .is_some_and(|ranges| ranges.iter().any(overlaps))
+        })
+        .cloned()
+        .collect();
+    Ok(json_response(
+        StatusCode::OK,
+        &Value::Array(paginate(matching, request)?),
+    ))
+}
+
+fn access_windows(state: &State, request: &Request) -> Reply {
+    let search: SatelliteAccessToGroundstationRequest = request.body()?;
+    find(state, Collection::Groundstations, &search.groundstation_id)?;
+    let windows: Vec<Value> = state
+        .values(Collection::AccessWindows)
+        .filter(|window| window["groundStationId"].as_str() == Some(&search.groundstation_id))
+        .filter(|window| {
+            time_field(&window["start"], "epoch")
+                .is_some_and(|start| start >= search.start && start <= search.end)
+        })
+        .filter(|window| {
+            window["duration"].as_f64().unwrap_or_default() >= search.min_duration_minutes
+        })
+        .cloned()
+        .collect();
+    Ok(json_response(StatusCode::OK, &Value::Array(windows)))
+}
+
+/// Seeded satellites within half the field of view of the boresight.
+fn fov_results(state: &State, request: &Request) -> Reply {
+    let search: FOVAccessRequest = request.body()?;
+    let (ra, dec) = (
+        search.right_ascension_deg.value().to_radians(),
+        search.declination_deg.value().to_radians(),
+    );
+    let radius = search.field_of_view_deg.value().to_radians() / 2.0;
+    let results: Vec<Value> = state
+        .values(Collection::FovResults)
+        .filter(|result| {
+            let other_ra = result["rightAscension"]
+                .as_f64()
+                .unwrap_or_default()
+                .to_radians();
+            let other_dec = result["declination"]
+                .as_f64()
+                .unwrap_or_default()
+                .to_radians();
+            let cos_separation =
+                dec.sin() * other_dec.sin() + dec.cos() * other_dec.cos() * (ra - other_ra).cos();
+            cos_separation.clamp(-1.0, 1.0).acos() <= radius
+        })
+        .cloned()
+        .collect();
+    Ok(json_response(StatusCode::OK, &Value::Array(results)))
+}
+
+fn create_rf_capture(state: &mut State, request: &Request) -> Reply {
+    let capture: CreateRFCaptureRequest = request.body()?;
+    find(state, Collection::Antennas, &capture.antenna_id)?;
+    let detections = capture.data.detections.len();
+    let mut object = into_object(to_value(&capture));
+    object.insert(
+        "id".to_string(),
+        json!(state.generate_id(Collection::RfCaptures)),
+    );
+    object.insert("userId".to_string(), json!(MOCK_USER_ID));
+    object.insert("detectionCount".to_string(), json!(detections));
+    object.insert("creationEpoch".to_string(), now());
+    let record = state.insert(Collection::RfCaptures, Value::Object(object));
+    Ok(json_response(StatusCode::OK, &record.value))
+}
+
+fn create_elsets(state: &mut State, request: &Request) -> Reply {
+    let uploads: Vec<ElementSetUploadRequest> = request.body()?;
+    let mut created = Vec::new();
+    for upload in &uploads {
+        let elements = match (&upload.omm, &upload.tle) {
+            (Some(omm), _) => Ok(omm.clone()),
+            (None, Some(tle)) => tle.to_omm(),
+            (None, None) => Err(ElsetError::MissingField("tle or omm".to_string())),
+        }
+        .map_err(|err| error(StatusCode::UNPROCESSABLE_ENTITY, &err.to_string()))?;
+        let mut object = into_object(to_value(upload));
+        object.insert(
+            "id".to_string(),
+            json!(state.generate_id(Collection::Elsets)),
+        );
+        object.insert("epoch".to_string(), json!(elements.epoch));
+        object.insert("creationEpoch".to_string(), now());
+        created.push(
+            state
+                .insert(Collection::Elsets, Value::Object(object))
+                .value
+                .clone(),
+        );
+    }
+    Ok(json_response(StatusCode::OK, &Value::Array(created)))
+}
+
+/// Element sets of a satellite with epochs within the `start` and `end` query
+/// parameters, oldest first.
+fn elsets_of(
+    state: &State,
+    satellite_id: &str,
+    request: &Request,
+) -> Result<Vec<Value>, Rejection> {
+    let time = |name: &str| -> Result<Option<DateTime<Utc>>, Rejection> {
+        request
+            .param(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| error(StatusCode::BAD_REQUEST, &format!("invalid {}", name)))
+            })
+            .transpose()
+    };
+    let (start, end) = (time("start")?, time("end")?);
+    let mut elsets: Vec<Value> = state
+        .values(Collection::Elsets)
+        .filter(|elset| elset["satelliteId"].as_str() == Some(satellite_id))
+        .filter(|elset| {
+            time_field(elset, "epoch").is_some_and(|epoch| {
+                start.is_none_or(|start| epoch >= start) && end.is_none_or(|end| epoch <= end)
+            })
+        })
+        .cloned()
+        .collect();
+    elsets.sort_by_key(|elset| time_field(elset, "epoch"));
+    Ok(elsets)
+}
+
+/// Capture summaries, i.e. without their data, whose `key` field equals `id`.
+fn rf_captures_where(state: &State, key: &str, id: &str, request: &Request) -> Reply {
+    let summaries = state
+        .values(Collection::RfCaptures)
+        .filter(|capture| capture[key].as_str() == Some(id))
+        .map(|capture| {
+            let mut summary = capture.clone();
+            if let Some(object) = summary.as_object_mut() {
+                object.remove("data");
+            }
+            summary
+        })
+        .collect();
+    let page = paginate(summaries, request)?;
+    Ok(json_response(StatusCode::OK, &Value::Array(page)))
+}
+
+fn find<'a>(
+    state: &'a State,
+    collection: Collection,
+    id: &str,
+) -> Result<&'a super::Record, Rejection> {
+    state
+        .get(collection, id)
+        .ok_or_else(|| not_found(collection, id))
+}
+
+fn find_mut<'a>(
+    state: &'a mut State,
+    collection: Collection,
+    id: &str,
+) -> Result<&'a mut super::Record, Rejection> {
+    state
+        .get_mut(collection, id)
+        .ok_or_else(|| not_found(collection, id))
+}
+
+fn not_found(collection: Collection, id: &str) -> Rejection {
+    error(
+        StatusCode::NOT_FOUND,
+        &format!("{} {} not found", collection.name(), id),
+    )
+}
+
+/// Applies the `limit` and `offset` query parameters.
+fn paginate(items: Vec<Value>, request: &Request) -> Result<Vec<Value>, Rejection> {
+    let parse = |name: &str| -> Result<Option<usize>, Rejection> {
+        request
+            .param(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| error(StatusCode::BAD_REQUEST, &format!("invalid {}", name)))
+            })
+            .transpose()
+    };
+    let offset = parse("offset")?.unwrap_or(0);
+    let limit = parse("limit")?.unwrap_or(usize::MAX);
+    Ok(items.into_iter().skip(offset).take(limit).collect())
+}
+
+fn merge(target: &mut Value, changes: Map<String, Value>) {
+    if let Some(object) = target.as_object_mut() {
+        object.extend(changes.into_iter().filter(|(_, value)| !value.is_null()));
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => match (a.parse::<DateTime<Utc>>(), b.parse()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        },
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Greater,
+        (_, Value::Null) => Ordering::Less,
+        _ => Ordering::Equal,
+    }
+}
+
+fn time_field(value: &Value, field: &str) -> Option<DateTime<Utc>> {
+    value.get(field)?.as_str()?.parse().ok()
+}
+
+fn now() -> Value {
+    json!(Utc::now())
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).expect("entities should serialize to JSON")
+}
+
+fn into_object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(object) => object,
+        _ => Map::new(),
+    }
+}
+
+fn json_response(status: StatusCode, value: &Value) -> Response {
+    (
+        status,
+        [(CONTENT_TYPE, "application/json")],
+        value.to_string(),
+    )
+        .into_response()
+}
+
+fn versioned_response(record: &super::Record) -> Response {
+    let mut response = json_response(StatusCode::OK, &record.value);
+    let etag = record
+        .etag()
+        .parse()
+        .expect("ETags are valid header values");
+    response.headers_mut().insert(ETAG, etag);
+    response
+}
+
+/// An error answer, kept small so that it can be returned with `?`.
+struct Rejection {
+    status: StatusCode,
+    body: String,
+    json: bool,
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        if self.json {
+            (self.status, [(CONTENT_TYPE, "application/json")], self.body).into_response()
+        } else {
+            (self.status, self.body).into_response()
+        }
+    }
+}
+
+fn error(status: StatusCode, message: &str) -> Rejection {
+    Rejection {
+        status,
+        body: message.to_string(),
+        json: false,
+    }
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (
+                percent_decode(&key.replace('+', " ")),
+                percent_decode(&value.replace('+', " ")),
+            )
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,201 @@
+//! Unit-carrying wrappers for the quantities in the API entities.
+//!
+//! Each type is a transparent newtype over `f64`: it serializes exactly like the
+//! bare number, so the wire format is unchanged, while mixing up e.g. [`Hz`] and
+//! [`MHz`] becomes a type error. Convert between compatible units with `From`:
+//!
+//! ```
+//! use lemonaid::units::{Hz, MHz};
+//!
+//! let downlink = MHz(437.8);
+//! assert_eq!(Hz::from(downlink), Hz(437_800_000.0));
+//! ```
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! unit {
+    ($(#[$doc:meta])* $name:ident, $suffix:literal) => {
+        $(#[$doc])*
+        #[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+        #[serde(transparent)]
+        pub struct $name(pub f64);
+
+        impl $name {
+            /// The bare value.
+            pub fn value(self) -> f64 {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)?;
+                f.write_str($suffix)
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, other: $name) -> $name {
+                $name(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, other: $name) -> $name {
+                $name(self.0 - other.0)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = $name;
+
+            fn mul(self, factor: f64) -> $name {
+                $name(self.0 * factor)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = $name;
+
+            fn div(self, divisor: f64) -> $name {
+                $name(self.0 / divisor)
+            }
+        }
+    };
+}
+
+macro_rules! conversion {
+    ($from:ident => $to:ident, $factor:expr) => {
+        impl From<$from> for $to {
+            fn from(value: $from) -> $to {
+                $to(value.0 * $factor)
+            }
+        }
+
+        impl From<$to> for $from {
+            fn from(value: $to) -> $from {
+                $from(value.0 / $factor)
+            }
+        }
+    };
+}
+
+unit!(
+    /// An angle in degrees.
+    Degrees,
+    "°"
+);
+unit!(
+    /// An angle in radians.
+    Radians,
+    " rad"
+);
+unit!(
+    /// An angle in arcseconds.
+    Arcsec,
+    "\""
+);
+unit!(
+    /// A distance in kilometres.
+    Km,
+    " km"
+);
+unit!(
+    /// A distance in metres.
+    Meters,
+    " m"
+);
+unit!(
+    /// A frequency in hertz.
+    Hz,
+    " Hz"
+);
+unit!(
+    /// A frequency in megahertz.
+    MHz,
+    " MHz"
+);
+
+conversion!(Degrees => Radians, std::f64::consts::PI / 180.0);
+conversion!(Degrees => Arcsec, 3600.0);
+conversion!(Radians => Arcsec, 180.0 * 3600.0 / std::f64::consts::PI);
+conversion!(Km => Meters, 1000.0);
+conversion!(MHz => Hz, 1e6);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that `a` and `b` differ by at most one unit in the last place.
+    fn assert_close(a: f64, b: f64) {
+        assert!(
+            (a - b).abs() <= f64::EPSILON * a.abs().max(b.abs()),
+            "{} != {}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn angles_round_trip() {
+        for degrees in [0.0, 0.8, 30.0, -105.2705, 180.0, 359.999] {
+            let angle = Degrees(degrees);
+            assert_close(Degrees::from(Radians::from(angle)).0, degrees);
+            assert_close(Degrees::from(Arcsec::from(angle)).0, degrees);
+            let arcsec = Arcsec::from(Radians::from(angle));
+            assert_close(Degrees::from(Radians::from(arcsec)).0, degrees);
+        }
+        assert_eq!(Radians::from(Degrees(180.0)), Radians(std::f64::consts::PI));
+        assert_eq!(Arcsec::from(Degrees(0.5)), Arcsec(1800.0));
+        assert_close(Arcsec::from(Radians(1.0)).0, 206_264.806_247_096_36);
+    }
+
+    #[test]
+    fn distances_and_frequencies_round_trip() {
+        for km in [0.0, 1.655, 6378.137, 35_786.0] {
+            assert_close(Km::from(Meters::from(Km(km))).0, km);
+        }
+        assert_eq!(Meters::from(Km(1.5)), Meters(1500.0));
+        for mhz in [0.0, 137.5, 437.8, 2250.125] {
+            assert_close(MHz::from(Hz::from(MHz(mhz))).0, mhz);
+        }
+        // whole-hertz frequencies survive the trip to MHz and back
+        for hz in [437_800_000.0, 2_250_125_000.0, 1.0] {
+            assert_eq!(Hz::from(MHz::from(Hz(hz))), Hz(hz));
+        }
+    }
+
+    #[test]
+    fn units_serialize_as_bare_numbers() {
+        assert_eq!(serde_json::to_string(&Degrees(40.015)).unwrap(), "40.015");
+        assert_eq!(serde_json::to_string(&Radians(1.5)).unwrap(), "1.5");
+        assert_eq!(serde_json::to_string(&Arcsec(2.5)).unwrap(), "2.5");
+        assert_eq!(serde_json::to_string(&Km(812.4)).unwrap(), "812.4");
+        assert_eq!(serde_json::to_string(&Meters(1655.0)).unwrap(), "1655.0");
+        assert_eq!(serde_json::to_string(&Hz(437e6)).unwrap(), "437000000.0");
+        assert_eq!(serde_json::to_string(&MHz(437.8)).unwrap(), "437.8");
+
+        assert_eq!(
+            serde_json::from_str::<Degrees>("-5").unwrap(),
+            Degrees(-5.0)
+        );
+        assert_eq!(serde_json::from_str::<Hz>("430000000").unwrap(), Hz(430e6));
+        assert_eq!(serde_json::from_str::<Option<MHz>>("null").unwrap(), None);
+        assert!(serde_json::from_str::<Km>("{\"value\":1.0}").is_err());
+    }
+}
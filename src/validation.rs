@@ -0,0 +1,483 @@
+//! Client-side checks run on request payloads before they are sent.
+//!
+//! Field names in the reported [`FieldError`]s are the JSON names, matching what
+//! the API itself reports for rejected requests.
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    Antenna, AntennaCreateRequest, AntennaUpdateRequest, CreateRFCaptureRequest, CreateTaskRequest,
+    ElementSetUploadRequest, FOVAccessRequest, FieldError, Groundstation,
+    GroundstationCreateRequest, GroundstationUpdateRequest, LemonaidError,
+    SatelliteAccessToGroundstationRequest, TaskUpdateRequest, Telescope, TelescopeCreateRequest,
+    TelescopeUpdateRequest,
+};
+
+/// Consistency checks for values sent to the API.
+///
+/// The client calls [`validate`](Validate::validate) on every payload before
+/// sending it, so obviously broken requests fail fast with
+/// [`LemonaidError::InvalidRequest`] instead of a round trip.
+///
+/// ```
+/// use lemonaid::units::Degrees;
+/// use lemonaid::{GroundstationCreateRequest, Validate};
+///
+/// let groundstation = GroundstationCreateRequest {
+///     name: "Boulder".to_string(),
+///     latitude: Degrees(200.0),
+///     longitude: Degrees(-105.27),
+///     altitude: 1655.0,
+/// };
+/// let errors = groundstation.field_errors();
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].field, "latitude");
+/// ```
+pub trait Validate {
+    /// Every problem found, empty if the value is valid.
+    fn field_errors(&self) -> Vec<FieldError>;
+
+    fn validate(&self) -> Result<(), LemonaidError> {
+        let errors = self.field_errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(LemonaidError::InvalidRequest { errors })
+        }
+    }
+}
+
+/// Bulk payloads report fields prefixed with the item index, e.g. `0.latitude`.
+impl<T: Validate> Validate for [T] {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        for (index, item) in self.iter().enumerate() {
+            errors.nested(&index.to_string(), item.field_errors());
+        }
+        errors.0
+    }
+}
+
+/// Accumulates field errors for one value.
+#[derive(Default)]
+struct Checks(Vec<FieldError>);
+
+impl Checks {
+    fn check(&mut self, ok: bool, field: &str, message: impl Into<String>) {
+        if !ok {
+            self.0.push(FieldError {
+                field: field.to_string(),
+                message: message.into(),
+            });
+        }
+    }
+
+    fn not_empty(&mut self, field: &str, value: &str) {
+        self.check(!value.trim().is_empty(), field, "must not be empty");
+    }
+
+    /// Also rejects NaN.
+    fn within(&mut self, field: &str, value: f64, min: f64, max: f64) {
+        self.check(
+            (min..=max).contains(&value),
+            field,
+            format!("must be between {} and {}, got {}", min, max, value),
+        );
+    }
+
+    /// Like [`within`](Self::within), but excluding `min`.
+    fn above_within(&mut self, field: &str, value: f64, min: f64, max: f64) {
+        self.check(
+            value > min && value <= max,
+            field,
+            format!("must be above {} and at most {}, got {}", min, max, value),
+        );
+    }
+
+    fn positive(&mut self, field: &str, value: f64) {
+        self.check(
+            value > 0.0,
+            field,
+            format!("must be positive, got {}", value),
+        );
+    }
+
+    fn non_negative(&mut self, field: &str, value: f64) {
+        self.check(
+            value >= 0.0,
+            field,
+            format!("must not be negative, got {}", value),
+        );
+    }
+
+    fn finite(&mut self, field: &str, value: f64) {
+        self.check(value.is_finite(), field, "must be a finite number");
+    }
+
+    fn after(&mut self, field: &str, value: DateTime<Utc>, other: &str, bound: DateTime<Utc>) {
+        self.check(value > bound, field, format!("must be after {}", other));
+    }
+
+    fn latitude(&mut self, field: &str, value: f64) {
+        self.within(field, value, -90.0, 90.0);
+    }
+
+    fn longitude(&mut self, field: &str, value: f64) {
+        self.within(field, value, -180.0, 180.0);
+    }
+
+    fn nested(&mut self, prefix: &str, errors: Vec<FieldError>) {
+        self.0.extend(errors.into_iter().map(|error| FieldError {
+            field: format!("{}.{}", prefix, error.field),
+            message: error.message,
+        }));
+    }
+}
+
+impl Validate for CreateTaskRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.after("taskStop", self.task_stop, "taskStart", self.task_start);
+        errors.not_empty("satelliteId", &self.satellite_id);
+        errors.check(
+            self.antenna_id.is_some() || self.telescope_id.is_some(),
+            "antennaId",
+            "one of antennaId or telescopeId must be set",
+        );
+        errors.0
+    }
+}
+
+impl Validate for TaskUpdateRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("id", &self.id);
+        if let (Some(start), Some(stop)) = (self.scheduled_start, self.scheduled_stop) {
+            errors.after("scheduledStop", stop, "scheduledStart", start);
+        }
+        errors.0
+    }
+}
+
+impl Validate for GroundstationCreateRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("name", &self.name);
+        errors.latitude("latitude", self.latitude.value());
+        errors.longitude("longitude", self.longitude.value());
+        errors.finite("altitude", self.altitude);
+        errors.0
+    }
+}
+
+impl Validate for Groundstation {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("id", &self.id);
+        errors
+            .0
+            .extend(GroundstationCreateRequest::from(self).field_errors());
+        errors.0
+    }
+}
+
+impl Validate for TelescopeCreateRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("name", &self.name);
+        errors.non_negative("angularNoise", self.angular_noise_arcsec.value());
+        errors.above_within("fieldOfView", self.field_of_view_deg.value(), 0.0, 180.0);
+        errors.finite("maxMagnitude", self.limiting_magnitude);
+        errors.within("minElevation", self.min_elevation_deg.value(), -90.0, 90.0);
+        errors.positive("maxSlewRate", self.max_slew_rate_deg_per_sec);
+        errors.within("homeAzimuth", self.home_azimuth_deg.value(), 0.0, 360.0);
+        errors.within(
+            "homeElevation",
+            self.home_elevation_deg.value(),
+            -90.0,
+            90.0,
+        );
+        errors.0
+    }
+}
+
+impl Validate for AntennaCreateRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("name", &self.name);
+        errors.non_negative("minFrequency", self.min_frequency_hz.value());
+        errors.check(
+            self.min_frequency_hz <= self.max_frequency_hz,
+            "maxFrequency",
+            format!("must not be below minFrequency ({})", self.min_frequency_hz),
+        );
+        errors.within("minElevation", self.min_elevation_deg.value(), -90.0, 90.0);
+        errors.positive("maxSlewRate", self.max_slew_rate_deg_per_sec);
+        errors.within("homeAzimuth", self.home_azimuth_deg.value(), 0.0, 360.0);
+        errors.within(
+            "homeElevation",
+            self.home_elevation_deg.value(),
+            -90.0,
+            90.0,
+        );
+        errors.above_within(
+            "halfPowerBeamWidth",
+            self.half_power_beam_width_deg.value(),
+            0.0,
+            360.0,
+        );
+        errors.0
+    }
+}
+
+impl Validate for Telescope {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("id", &self.id);
+        errors
+            .0
+            .extend(TelescopeCreateRequest::from(self).field_errors());
+        errors.0
+    }
+}
+
+impl Validate for Antenna {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("id", &self.id);
+        errors
+            .0
+            .extend(AntennaCreateRequest::from(self).field_errors());
+        errors.0
+    }
+}
+
+impl Validate for GroundstationUpdateRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        if let Some(name) = &self.name {
+            errors.not_empty("name", name);
+        }
+        if let Some(latitude) = self.latitude {
+            errors.latitude("latitude", latitude.value());
+        }
+        if let Some(longitude) = self.longitude {
+            errors.longitude("longitude", longitude.value());
+        }
+        if let Some(altitude) = self.altitude {
+            errors.finite("altitude", altitude);
+        }
+        errors.0
+    }
+}
+
+impl Validate for TelescopeUpdateRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        if let Some(name) = &self.name {
+            errors.not_empty("name", name);
+        }
+        if let Some(noise) = self.angular_noise_arcsec {
+            errors.non_negative("angularNoise", noise.value());
+        }
+        if let Some(field_of_view) = self.field_of_view_deg {
+            errors.above_within("fieldOfView", field_of_view.value(), 0.0, 180.0);
+        }
+        if let Some(magnitude) = self.limiting_magnitude {
+            errors.finite("maxMagnitude", magnitude);
+        }
+        if let Some(elevation) = self.min_elevation_deg {
+            errors.within("minElevation", elevation.value(), -90.0, 90.0);
+        }
+        if let Some(rate) = self.max_slew_rate_deg_per_sec {
+            errors.positive("maxSlewRate", rate);
+        }
+        if let Some(azimuth) = self.home_azimuth_deg {
+            errors.within("homeAzimuth", azimuth.value(), 0.0, 360.0);
+        }
+        if let Some(elevation) = self.home_elevation_deg {
+            errors.within("homeElevation", elevation.value(), -90.0, 90.0);
+        }
+        errors.0
+    }
+}
+
+impl Validate for AntennaUpdateRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        if let Some(name) = &self.name {
+            errors.not_empty("name", name);
+        }
+        if let Some(min) = self.min_frequency_hz {
+            errors.non_negative("minFrequency", min.value());
+        }
+        // a one-sided change is checked against the stored value by the API
+        if let (Some(min), Some(max)) = (self.min_frequency_hz, self.max_frequency_hz) {
+            errors.check(
+                min <= max,
+                "maxFrequency",
+                format!("must not be below minFrequency ({})", min),
+            );
+        }
+        if let Some(elevation) = self.min_elevation_deg {
+            errors.within("minElevation", elevation.value(), -90.0, 90.0);
+        }
+        if let Some(rate) = self.max_slew_rate_deg_per_sec {
+            errors.positive("maxSlewRate", rate);
+        }
+        if let Some(azimuth) = self.home_azimuth_deg {
+            errors.within("homeAzimuth", azimuth.value(), 0.0, 360.0);
+        }
+        if let Some(elevation) = self.home_elevation_deg {
+            errors.within("homeElevation", elevation.value(), -90.0, 90.0);
+        }
+        if let Some(beam_width) = self.half_power_beam_width_deg {
+            errors.above_within("halfPowerBeamWidth", beam_width.value(), 0.0, 360.0);
+        }
+        errors.0
+    }
+}
+
+impl Validate for SatelliteAccessToGroundstationRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("groundStationId", &self.groundstation_id);
+        errors.after("end", self.end, "start", self.start);
+        errors.within("minElevation", self.min_elevation_deg.value(), -90.0, 90.0);
+        errors.non_negative("minDuration", self.min_duration_minutes);
+        if let Some(min) = self.min_frequency_mhz {
+            errors.non_negative("minFrequencyMhz", min.value());
+        }
+        if let (Some(min), Some(max)) = (self.min_frequency_mhz, self.max_frequency_mhz) {
+            errors.check(
+                min <= max,
+                "maxFrequencyMhz",
+                format!("must not be below minFrequencyMhz ({})", min),
+            );
+        }
+        errors.0
+    }
+}
+
+impl Validate for FOVAccessRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.within(
+            "rightAscension",
+            self.right_ascension_deg.value(),
+            0.0,
+            360.0,
+        );
+        errors.within("declination", self.declination_deg.value(), -90.0, 90.0);
+        errors.above_within("fieldOfView", self.field_of_view_deg.value(), 0.0, 180.0);
+        errors.latitude("sensorLatitude", self.sensor_latitude_deg.value());
+        errors.longitude("sensorLongitude", self.sensor_longitude_deg.value());
+        errors.finite("sensorAltitude", self.sensor_altitude_km.value());
+        errors.0
+    }
+}
+
+impl Validate for CreateRFCaptureRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("antennaId", &self.antenna_id);
+        errors.after(
+            "captureEnd",
+            self.capture_end,
+            "captureStart",
+            self.capture_start,
+        );
+        let psd = &self.data.power_spectral_density;
+        errors.check(
+            psd.frequency_hz.len() == psd.power_dbm_per_hz.len(),
+            "data.powerSpectralDensity.powerDbmPerHz",
+            format!(
+                "has {} values for {} frequencies",
+                psd.power_dbm_per_hz.len(),
+                psd.frequency_hz.len()
+            ),
+        );
+        for (index, detection) in self.data.detections.iter().enumerate() {
+            let field = format!("data.detections.{}.bandwidthHz", index);
+            errors.check(
+                detection.bandwidth_hz >= 0,
+                &field,
+                format!("must not be negative, got {}", detection.bandwidth_hz),
+            );
+        }
+        errors.0
+    }
+}
+
+impl Validate for ElementSetUploadRequest {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Checks::default();
+        errors.not_empty("satelliteId", &self.satellite_id);
+        errors.check(
+            self.tle.is_some() || self.omm.is_some(),
+            "tle",
+            "one of tle or omm must be set",
+        );
+        if let Some(Err(err)) = self.tle.as_ref().map(|tle| tle.validate()) {
+            errors.check(false, "tle", err.to_string());
+        }
+        if let Some(Err(err)) = self.omm.as_ref().map(|omm| omm.validate()) {
+            errors.check(false, "omm", err.to_string());
+        }
+        errors.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::{Degrees, Hz};
+
+    fn fields(errors: &[FieldError]) -> Vec<&str> {
+        errors.iter().map(|error| error.field.as_str()).collect()
+    }
+
+    #[test]
+    fn task_must_end_after_it_starts_and_target_a_sensor() {
+        let start = "2025-01-01T00:00:00Z".parse().unwrap();
+        let mut task = CreateTaskRequest {
+            task_start: start,
+            task_stop: start - chrono::Duration::minutes(5),
+            satellite_id: "25544".to_string(),
+            antenna_id: None,
+            telescope_id: None,
+        };
+        assert_eq!(fields(&task.field_errors()), ["taskStop", "antennaId"]);
+
+        task.task_stop = start + chrono::Duration::minutes(5);
+        task.telescope_id = Some("t1".to_string());
+        assert!(task.validate().is_ok());
+    }
+
+    #[test]
+    fn bulk_payloads_report_every_item() {
+        let groundstation = |latitude, longitude| GroundstationCreateRequest {
+            name: "Boulder".to_string(),
+            latitude: Degrees(latitude),
+            longitude: Degrees(longitude),
+            altitude: 1655.0,
+        };
+        let bulk = [
+            groundstation(40.0, -105.0),
+            groundstation(200.0, -105.0),
+            groundstation(f64::NAN, 400.0),
+        ];
+        match bulk.validate() {
+            Err(LemonaidError::InvalidRequest { errors }) => {
+                assert_eq!(fields(&errors), ["1.latitude", "2.latitude", "2.longitude"]);
+            }
+            other => panic!("expected InvalidRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn antenna_frequency_band_must_be_ordered() {
+        let antenna = AntennaCreateRequest::new("UHF", Hz(438e6), Hz(435e6));
+        assert_eq!(fields(&antenna.field_errors()), ["maxFrequency"]);
+    }
+}
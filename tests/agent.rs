@@ -0,0 +1,180 @@
+use chrono::{Duration, Utc};
+use lemonaid::agent::{Agent, DriverStep, SimulatedDriver};
+use lemonaid::testing::{Fault, FaultRule, MockServer, fixtures};
+use lemonaid::{LemonaidError, Task, TaskStatus, Tle};
+use reqwest::{Method, StatusCode};
+
+/// A scheduled task whose window opens `minutes` from now and lasts half an hour.
+fn scheduled(id: &str, minutes: i64) -> Task {
+    let mut task = fixtures::task(id, "scope-1", TaskStatus::Scheduled);
+    let start = Utc::now() + Duration::minutes(minutes);
+    task.scheduled_start = Some(start);
+    task.scheduled_stop = Some(start + Duration::minutes(30));
+    task
+}
+
+/// A telescope with a scheduled task that is due, one that is not, and one
+/// that already ran.
+fn seed(server: &MockServer) {
+    server.insert_telescope(&fixtures::telescope("scope-1"));
+    server.insert_task(&scheduled("task-due", -1));
+    server.insert_task(&scheduled("task-later", 60));
+    server.insert_task(&fixtures::task(
+        "task-done",
+        "scope-1",
+        TaskStatus::Succeeded,
+    ));
+}
+
+fn iss() -> Tle {
+    Tle::new(
+        "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+        "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn due_tasks_are_run_and_reported() {
+    let server = MockServer::start().await;
+    seed(&server);
+    let before = Utc::now();
+    let mut agent = Agent::new(
+        server.client(),
+        "scope-1",
+        SimulatedDriver::new().tle(iss()),
+    );
+
+    let outcomes = agent.poll().await.unwrap();
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].task_id, "task-due");
+    assert_eq!(outcomes[0].status, Some(TaskStatus::Succeeded));
+    assert!(outcomes[0].error.is_none());
+    let steps: Vec<DriverStep> = agent
+        .driver()
+        .history()
+        .iter()
+        .map(|(_, step)| *step)
+        .collect();
+    assert_eq!(
+        steps,
+        [DriverStep::Slew, DriverStep::Expose, DriverStep::Readout]
+    );
+
+    assert_eq!(
+        server.task("task-due").unwrap().status,
+        TaskStatus::Succeeded
+    );
+    assert_eq!(
+        server.task("task-later").unwrap().status,
+        TaskStatus::Scheduled
+    );
+    let telescope = server.telescope("scope-1").unwrap();
+    assert!(telescope.last_connected_at.unwrap() >= before);
+    let elsets = server.elsets("25544");
+    assert_eq!(elsets.len(), 1);
+    assert_eq!(outcomes[0].elsets[0].id, elsets[0].id);
+
+    // nothing is left to do until the next task's window opens
+    assert!(agent.poll().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn driver_and_upload_failures_fail_the_task() {
+    let server = MockServer::start().await;
+    seed(&server);
+    let mut agent = Agent::new(
+        server.client(),
+        "scope-1",
+        SimulatedDriver::new().fail_at(DriverStep::Expose),
+    );
+
+    let outcomes = agent.poll().await.unwrap();
+    assert_eq!(outcomes[0].status, Some(TaskStatus::Failed));
+    assert!(matches!(outcomes[0].error, Some(LemonaidError::Driver(_))));
+    assert_eq!(server.task("task-due").unwrap().status, TaskStatus::Failed);
+    // the readout is skipped once the exposure fails
+    assert_eq!(agent.driver().history().len(), 2);
+
+    server.insert_task(&scheduled("task-2", -1));
+    server.inject(
+        FaultRule::new(Fault::Status(StatusCode::UNPROCESSABLE_ENTITY))
+            .method(Method::POST)
+            .path("/elsets"),
+    );
+    let mut agent = Agent::new(
+        server.client(),
+        "scope-1",
+        SimulatedDriver::new().tle(iss()),
+    );
+    let outcomes = agent.poll().await.unwrap();
+    assert_eq!(outcomes[0].status, Some(TaskStatus::Failed));
+    assert!(matches!(
+        outcomes[0].error,
+        Some(LemonaidError::Validation { .. })
+    ));
+    assert!(server.elsets("25544").is_empty());
+}
+
+#[tokio::test]
+async fn expired_tasks_are_failed_without_running() {
+    let server = MockServer::start().await;
+    server.insert_telescope(&fixtures::telescope("scope-1"));
+    let expired = scheduled("task-expired", -45);
+    server.insert_task(&expired);
+    let mut agent = Agent::new(server.client(), "scope-1", SimulatedDriver::new());
+
+    let outcomes = agent.poll().await.unwrap();
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].status, Some(TaskStatus::Failed));
+    assert!(outcomes[0].error.is_none());
+    assert!(agent.driver().history().is_empty());
+    let task = server.task("task-expired").unwrap();
+    assert_eq!(task.status, TaskStatus::Failed);
+    // the update restates the schedule rather than clearing it
+    assert_eq!(task.scheduled_stop, expired.scheduled_stop);
+}
+
+#[tokio::test]
+async fn tasks_are_claimed_before_the_driver_runs() {
+    let server = MockServer::start().await;
+    server.insert_telescope(&fixtures::telescope("scope-1"));
+    server.insert_task(&scheduled("task-a", -2));
+    server.insert_task(&scheduled("task-b", -1));
+    let mut agent = Agent::new(server.client(), "scope-1", SimulatedDriver::new());
+
+    // a failed claim is reported and the remaining tasks still run
+    server.inject(
+        FaultRule::new(Fault::Status(StatusCode::CONFLICT))
+            .method(Method::PUT)
+            .path("/tasks/task-a")
+            .times(1),
+    );
+    let outcomes = agent.poll().await.unwrap();
+    assert_eq!(outcomes.len(), 2);
+    assert_eq!(outcomes[0].status, None);
+    assert!(matches!(
+        outcomes[0].error,
+        Some(LemonaidError::Conflict { .. })
+    ));
+    assert_eq!(outcomes[1].status, Some(TaskStatus::Succeeded));
+    assert_eq!(agent.driver().history()[0].0, "task-b");
+
+    // a task canceled since it was listed is left alone
+    let stale = server.task("task-a").unwrap();
+    server.client().cancel_task("task-a").await.unwrap();
+    assert!(agent.execute(&stale).await.is_none());
+    assert_eq!(agent.driver().history().len(), 3);
+}
+
+#[tokio::test]
+async fn run_stops_on_errors_retrying_cannot_fix() {
+    let server = MockServer::start().await;
+    seed(&server);
+    server.require_token("another-token");
+    let mut agent = Agent::new(server.client(), "scope-1", SimulatedDriver::new());
+
+    let err = agent.run().await.unwrap_err();
+    assert!(matches!(err, LemonaidError::Unauthorized { .. }));
+    assert!(agent.driver().history().is_empty());
+}
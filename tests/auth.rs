@@ -0,0 +1,154 @@
+use std::fs::File;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use lemonaid::testing::{CassetteServer, MOCK_TOKEN, MockServer};
+use lemonaid::{AuthProvider, ClientCredentials, FileToken, LemonaidError, async_trait};
+use serde_json::json;
+
+/// Hands out tokens in order, moving to the next one on every invalidation.
+struct Rotating {
+    tokens: Vec<&'static str>,
+    current: Mutex<usize>,
+}
+
+impl Rotating {
+    fn new(tokens: &[&'static str]) -> Self {
+        Rotating {
+            tokens: tokens.to_vec(),
+            current: Mutex::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthProvider for Rotating {
+    async fn token(&self) -> Result<String, LemonaidError> {
+        let current = *self.current.lock().unwrap();
+        Ok(self.tokens[current.min(self.tokens.len() - 1)].to_string())
+    }
+
+    async fn invalidate(&self) -> bool {
+        *self.current.lock().unwrap() += 1;
+        true
+    }
+}
+
+#[tokio::test]
+async fn rejected_tokens_are_refreshed_and_replayed_once() {
+    let server = MockServer::start().await;
+    server.require_token(MOCK_TOKEN);
+    let client = server
+        .client_builder()
+        .auth(Rotating::new(&["expired", MOCK_TOKEN]))
+        .build()
+        .unwrap();
+    assert!(client.list_telescopes().await.is_ok());
+    let tokens: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|request| request.headers["authorization"].clone())
+        .collect();
+    assert_eq!(tokens, ["Bearer expired", "Bearer mock-token"]);
+
+    // a second rejection is reported rather than replayed again
+    let server = MockServer::start().await;
+    server.require_token(MOCK_TOKEN);
+    let client = server
+        .client_builder()
+        .auth(Rotating::new(&["expired", "revoked", MOCK_TOKEN]))
+        .build()
+        .unwrap();
+    let err = client.list_telescopes().await.unwrap_err();
+    assert!(matches!(err, LemonaidError::Unauthorized { .. }));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn file_tokens_are_reread_when_the_file_changes() {
+    let path = std::env::temp_dir().join(format!("lemonaid-token-{}", std::process::id()));
+    let write = |token: &str, modified: SystemTime| {
+        std::fs::write(&path, format!("{}\n", token)).unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    };
+    let start = SystemTime::now() - Duration::from_secs(60);
+    write("old-token", start);
+
+    let server = MockServer::start().await;
+    server.require_token("new-token");
+    let client = server
+        .client_builder()
+        .auth(FileToken::new(&path))
+        .build()
+        .unwrap();
+    assert!(matches!(
+        client.list_telescopes().await,
+        Err(LemonaidError::Unauthorized { .. })
+    ));
+
+    write("new-token", start + Duration::from_secs(1));
+    let result = client.list_telescopes().await;
+    std::fs::remove_file(&path).unwrap();
+    assert!(result.is_ok());
+    // the 401 is not replayed since the file was unchanged, and the new
+    // token is picked up without another rejection
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn client_credentials_are_refreshed_before_they_expire() {
+    let form = "grant_type=client_credentials&client_id=agent&client_secret=s3cret";
+    let grant = |token: &str, expires_in: u64| {
+        json!({
+            "request": { "method": "POST", "path": "/oauth/token", "body": form },
+            "response": {
+                "status": 200,
+                "body": { "access_token": token, "token_type": "Bearer", "expires_in": expires_in }
+            }
+        })
+    };
+    let cassette = serde_json::from_value(json!({
+        "interactions": [grant("first", 20), grant("second", 3600), grant("third", 3600)]
+    }))
+    .unwrap();
+    let server = CassetteServer::from_cassette(cassette).await;
+    let credentials = ClientCredentials::new(
+        &format!("{}/oauth/token", server.url().trim_end_matches('/')),
+        "agent",
+        "s3cret",
+    );
+
+    // expiring within the 30 s margin, so it is replaced on the next call
+    assert_eq!(credentials.token().await.unwrap(), "first");
+    assert_eq!(credentials.token().await.unwrap(), "second");
+    assert_eq!(credentials.token().await.unwrap(), "second");
+    assert!(credentials.invalidate().await);
+    assert_eq!(credentials.token().await.unwrap(), "third");
+}
+
+#[tokio::test]
+async fn malformed_token_responses_are_deserialization_errors() {
+    let cassette = serde_json::from_value(json!({
+        "interactions": [{
+            "request": { "method": "POST", "path": "/oauth/token", "body": "grant_type=client_credentials&client_id=agent&client_secret=s3cret" },
+            "response": { "status": 200, "body": { "token": "abc" } }
+        }]
+    }))
+    .unwrap();
+    let server = CassetteServer::from_cassette(cassette).await;
+    let credentials = ClientCredentials::new(
+        &format!("{}/oauth/token", server.url().trim_end_matches('/')),
+        "agent",
+        "s3cret",
+    );
+
+    match credentials.token().await {
+        Err(LemonaidError::Deserialization { body, .. }) => assert_eq!(body, r#"{"token":"abc"}"#),
+        other => panic!("expected a deserialization error, got {:?}", other),
+    }
+}
@@ -0,0 +1,168 @@
+use chrono::{DateTime, Duration, Utc};
+use lemonaid::testing::{Cassette, CassetteServer, MockServer, fixtures};
+use lemonaid::units::Degrees;
+use lemonaid::{
+    CreateTaskRequest, LemonaidError, SatelliteAccessToGroundstationRequest, SatelliteStatus,
+    SensorFrame, TaskQuery, TaskStatus, TelescopeCreateRequest,
+};
+use reqwest::StatusCode;
+
+/// Responses written by hand from the API reference, not recorded from the
+/// API. Re-record them against the dev API to catch drift in the real payloads.
+const HAND_WRITTEN: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/cassettes/hand_written.json"
+);
+
+const TELESCOPE_ID: &str = "5f0c7a1e-6f4b-4a8e-9d1f-2b3c4d5e6f70";
+const GROUNDSTATION_ID: &str = "0a5f2c44-1b7e-4c1d-8f39-6a2d7e9b1c03";
+
+fn time(rfc3339: &str) -> DateTime<Utc> {
+    rfc3339.parse().unwrap()
+}
+
+#[tokio::test]
+async fn recordings_replay_without_the_token() {
+    let upstream = MockServer::start().await;
+    upstream.require_token("secret-token");
+    upstream.insert_telescope(&fixtures::telescope("scope-1"));
+    let path = std::env::temp_dir().join(format!("lemonaid-cassette-{}.json", std::process::id()));
+
+    let recorder = CassetteServer::record(&upstream.url(), &path, "secret-token")
+        .await
+        .unwrap();
+    let client = recorder.client();
+    let fetched = client.get_telescope("scope-1").await.unwrap();
+    let created = client
+        .create_telescope(&TelescopeCreateRequest::new("secret-token's scope"))
+        .await
+        .unwrap();
+    assert_eq!(created.name, "secret-token's scope");
+    drop(recorder);
+
+    let recorded = std::fs::read_to_string(&path).unwrap();
+    let cassette = Cassette::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(!recorded.contains("secret-token"));
+    assert!(recorded.contains("<redacted>'s scope"));
+
+    drop(upstream);
+    let replay = CassetteServer::from_cassette(cassette).await;
+    let client = replay.client();
+    assert_eq!(
+        client.get_telescope("scope-1").await.unwrap().name,
+        fetched.name
+    );
+    let err = client.get_telescope("scope-2").await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::NOT_IMPLEMENTED));
+}
+
+#[tokio::test]
+async fn hardware_entities_decode() {
+    let server = CassetteServer::replay(HAND_WRITTEN).await.unwrap();
+    let client = server.client();
+
+    let telescope = client.get_telescope(TELESCOPE_ID).await.unwrap();
+    assert_eq!(telescope.name, "Backyard 8\"");
+    assert_eq!(
+        telescope.groundstation_id.as_deref(),
+        Some(GROUNDSTATION_ID)
+    );
+    assert_eq!(telescope.field_of_view_deg, Degrees(0.8));
+    assert_eq!(telescope.created_at, time("2024-11-02T17:45:12.481Z"));
+
+    let versioned = client.get_telescope_versioned(TELESCOPE_ID).await.unwrap();
+    assert_eq!(versioned.etag.as_deref(), Some("\"3\""));
+
+    let groundstations = client.list_groundstations().await.unwrap();
+    assert_eq!(groundstations.len(), 1);
+    assert_eq!(groundstations[0].latitude, Degrees(40.015));
+
+    let antenna = client
+        .get_antenna("9c1d2e3f-4a5b-4c6d-8e7f-90a1b2c3d4e5")
+        .await
+        .unwrap();
+    assert_eq!(antenna.min_frequency_hz.0, 430e6);
+    assert!(antenna.last_connected_at.is_none());
+
+    let capture = client
+        .get_rf_capture("7e8f9a0b-1c2d-4e3f-8a9b-0c1d2e3f4a5b")
+        .await
+        .unwrap();
+    assert_eq!(capture.detection_count, capture.data.detections.len());
+    assert_eq!(capture.data.power_spectral_density.frequency_hz.len(), 3);
+}
+
+#[tokio::test]
+async fn scheduling_entities_decode() {
+    let server = CassetteServer::replay(HAND_WRITTEN).await.unwrap();
+    let client = server.client();
+
+    let tasks = client
+        .query_tasks_for_telescope(
+            TELESCOPE_ID,
+            &TaskQuery::new().status(TaskStatus::Scheduled),
+        )
+        .await
+        .unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].status, TaskStatus::Scheduled);
+    assert_eq!(tasks[0].scheduled_start, Some(time("2025-03-07T09:12:40Z")));
+    assert_eq!(tasks[0].declination, Some(Degrees(38.1)));
+
+    let start = time("2025-03-07T00:00:00Z");
+    let windows = client
+        .solve_access_for_groundstation(
+            &SatelliteAccessToGroundstationRequest::new(
+                GROUNDSTATION_ID,
+                start,
+                start + Duration::days(1),
+            )
+            .min_elevation(Degrees(10.0)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(windows[0].satellite_id, "25544");
+    assert_eq!(windows[0].start.elevation_deg, Degrees(10.0));
+    assert!(windows[0].end.azimuth_rate_deg_s.is_none());
+}
+
+#[tokio::test]
+async fn catalog_entities_propagate() {
+    let server = CassetteServer::replay(HAND_WRITTEN).await.unwrap();
+    let client = server.client();
+
+    let satellite = client.get_satellite("25544").await.unwrap();
+    assert_eq!(satellite.status, SatelliteStatus::Active);
+    assert_eq!(satellite.frequencies.len(), 1);
+
+    let propagator = client.propagator_for_satellite("25544").await.unwrap();
+    let state = propagator
+        .propagate(time("2008-09-20T13:00:00Z"), SensorFrame::TEME)
+        .unwrap();
+    let radius = state.position_km.iter().map(|x| x * x).sum::<f64>().sqrt();
+    // the ISS orbits roughly 350 km up
+    assert!((6600.0..6900.0).contains(&radius), "radius {}", radius);
+}
+
+#[tokio::test]
+async fn error_responses_map_to_errors() {
+    let server = CassetteServer::replay(HAND_WRITTEN).await.unwrap();
+    let client = server.client();
+
+    match client.get_telescope("missing").await {
+        Err(LemonaidError::NotFound { message }) => assert_eq!(message, "Telescope not found"),
+        other => panic!("expected not found, got {:?}", other),
+    }
+
+    let start = time("2025-03-07T10:00:00Z");
+    let request =
+        CreateTaskRequest::for_telescope(TELESCOPE_ID, "25544", start, start + Duration::hours(1));
+    match client.create_task(&request).await {
+        Err(LemonaidError::Validation { errors, .. }) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].field, "taskStart");
+        }
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
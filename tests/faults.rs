@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use lemonaid::testing::{Fault, FaultRule, MockServer, fixtures};
+use lemonaid::{LemonaidError, RetryPolicy, TelescopeCreateRequest};
+use reqwest::{Method, StatusCode};
+
+/// Retries without waiting, so the tests stay fast.
+fn quick_retries() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff: Duration::from_millis(1),
+        ..RetryPolicy::default()
+    }
+}
+
+#[tokio::test]
+async fn rate_limits_are_retried() {
+    let server = MockServer::start().await;
+    server.insert_telescope(&fixtures::telescope("scope-1"));
+    server.inject(FaultRule::new(Fault::RateLimited { retry_after: None }).times(2));
+    let client = server
+        .client_builder()
+        .retry_policy(quick_retries())
+        .build()
+        .unwrap();
+
+    let telescope = client.get_telescope("scope-1").await.unwrap();
+    assert_eq!(telescope.id, "scope-1");
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn rate_limit_reports_retry_after_once_retries_run_out() {
+    let server = MockServer::start().await;
+    server.inject(FaultRule::new(Fault::RateLimited {
+        retry_after: Some(Duration::from_secs(120)),
+    }));
+    let client = server.client();
+
+    // a 120 s wait exceeds the policy's max backoff, so the client gives up at once
+    match client.list_telescopes().await {
+        Err(LemonaidError::RateLimited { retry_after, .. }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(120)));
+        }
+        other => panic!("expected a rate limit error, got {:?}", other),
+    }
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn server_errors_are_not_retried() {
+    let server = MockServer::start().await;
+    server.inject(FaultRule::new(Fault::Status(StatusCode::INTERNAL_SERVER_ERROR)).times(1));
+    let client = server
+        .client_builder()
+        .retry_policy(quick_retries())
+        .build()
+        .unwrap();
+
+    let err = client.list_antennas().await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+    assert!(!err.is_retryable());
+    assert!(client.list_antennas().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn creates_are_sent_once_unless_idempotency_keys_are_enabled() {
+    let server = MockServer::start().await;
+    let unavailable = || {
+        FaultRule::new(Fault::Status(StatusCode::SERVICE_UNAVAILABLE))
+            .method(Method::POST)
+            .path("/telescopes")
+            .times(1)
+    };
+    server.inject(unavailable());
+    let client = server
+        .client_builder()
+        .retry_policy(quick_retries())
+        .build()
+        .unwrap();
+    let request = TelescopeCreateRequest::new("Backyard");
+    assert!(client.create_telescope(&request).await.is_err());
+    assert_eq!(server.requests().len(), 1);
+
+    server.inject(unavailable());
+    let client = server
+        .client_builder()
+        .retry_policy(RetryPolicy {
+            retry_non_idempotent: true,
+            ..quick_retries()
+        })
+        .build()
+        .unwrap();
+    client.create_telescope(&request).await.unwrap();
+    let requests = server.requests();
+    let keys: Vec<_> = requests[1..]
+        .iter()
+        .map(|request| request.headers.get("idempotency-key").cloned())
+        .collect();
+    assert_eq!(keys.len(), 2);
+    assert!(keys[0].is_some());
+    assert_eq!(keys[0], keys[1]);
+}
+
+#[tokio::test]
+async fn malformed_json_is_a_deserialization_error() {
+    let server = MockServer::start().await;
+    server.inject(FaultRule::new(Fault::MalformedJson).path("/tasks"));
+    let client = server.client();
+
+    match client.get_task("task-1").await {
+        Err(LemonaidError::Deserialization { body, .. }) => assert!(body.starts_with("{\"id\"")),
+        other => panic!("expected a deserialization error, got {:?}", other),
+    }
+    // other routes are unaffected
+    assert!(client.list_telescopes().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn slow_responses_time_out() {
+    let server = MockServer::start().await;
+    server.inject(FaultRule::new(Fault::Latency(Duration::from_millis(500))).times(1));
+    let client = server
+        .client_builder()
+        .timeout(Duration::from_millis(100))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let err = client.list_telescopes().await.unwrap_err();
+    assert!(matches!(err, LemonaidError::Timeout(_)));
+    assert!(err.is_retryable());
+    assert!(client.list_telescopes().await.is_ok());
+}
+
+#[tokio::test]
+async fn wrong_tokens_are_rejected() {
+    let server = MockServer::start().await;
+    server.require_token("secret");
+
+    let err = server.client().list_telescopes().await.unwrap_err();
+    assert!(matches!(err, LemonaidError::Unauthorized { .. }));
+    let client = lemonaid::CitraClient::builder("secret")
+        .base_url(&server.url())
+        .build()
+        .unwrap();
+    assert!(client.list_telescopes().await.is_ok());
+}
@@ -0,0 +1,431 @@
+use chrono::Duration;
+use futures_util::TryStreamExt;
+use lemonaid::testing::{MockServer, fixtures};
+use lemonaid::units::{Degrees, MHz};
+use lemonaid::{
+    AntennaCreateRequest, CreateRFCaptureRequest, CreateTaskRequest, ElementSetUploadRequest,
+    FOVAccessRequest, GroundstationCreateRequest, GroundstationUpdateRequest, LemonaidError,
+    PageRequest, Precondition, SatelliteAccessToGroundstationRequest, SatelliteQuery, SortOrder,
+    TaskQuery, TaskSortField, TaskStatus, TaskUpdateRequest, TelescopeCreateRequest,
+    TelescopeUpdateRequest, Tle,
+};
+
+#[tokio::test]
+async fn telescopes_round_trip() {
+    let server = MockServer::start().await;
+    let client = server.client();
+
+    let created = client
+        .create_telescope(&TelescopeCreateRequest::new("Backyard").field_of_view(Degrees(0.8)))
+        .await
+        .unwrap();
+    assert_eq!(created.name, "Backyard");
+    assert_eq!(created.field_of_view_deg, Degrees(0.8));
+
+    let mut fetched = client.get_telescope(&created.id).await.unwrap();
+    fetched.limiting_magnitude = 14.0;
+    let updated = client.update_telescope(&fetched).await.unwrap();
+    assert_eq!(updated.limiting_magnitude, 14.0);
+    assert_eq!(client.list_telescopes().await.unwrap().len(), 1);
+
+    client.delete_telescope(&created.id).await.unwrap();
+    assert!(server.telescope(&created.id).is_none());
+    assert!(matches!(
+        client.get_telescope(&created.id).await,
+        Err(LemonaidError::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn patches_honor_preconditions() {
+    let server = MockServer::start().await;
+    server.insert_telescope(&fixtures::telescope("scope-1"));
+    let client = server.client();
+
+    let versioned = client.get_telescope_versioned("scope-1").await.unwrap();
+    let rename = TelescopeUpdateRequest::new()
+        .name("Renamed")
+        .precondition(versioned.precondition().unwrap());
+    let renamed = client.patch_telescope("scope-1", &rename).await.unwrap();
+    assert_eq!(renamed.name, "Renamed");
+    assert_eq!(renamed.limiting_magnitude, 12.0);
+
+    // the ETag is stale now
+    let err = client
+        .patch_telescope("scope-1", &rename)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, LemonaidError::Conflict { .. }));
+
+    server.insert_groundstation(&fixtures::groundstation("gs-1"));
+    let stale =
+        GroundstationUpdateRequest::new()
+            .name("Mesa")
+            .precondition(Precondition::UnmodifiedSince(
+                fixtures::epoch() - Duration::days(1),
+            ));
+    let err = client
+        .patch_groundstation("gs-1", &stale)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, LemonaidError::Conflict { .. }));
+}
+
+#[tokio::test]
+async fn groundstations_list_and_update() {
+    let server = MockServer::start().await;
+    server.insert_groundstation(&fixtures::groundstation("gs-1"));
+    let client = server.client();
+
+    let created = client
+        .create_groundstation(&GroundstationCreateRequest::new(
+            "Hilltop",
+            Degrees(39.7),
+            Degrees(-104.9),
+            1600.0,
+        ))
+        .await
+        .unwrap();
+    let names: Vec<String> = client
+        .list_groundstations()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|gs| gs.name)
+        .collect();
+    assert_eq!(names, ["Ground station gs-1", "Hilltop"]);
+
+    let moved = client
+        .update_groundstation(
+            &created.id,
+            &GroundstationCreateRequest::new("Hilltop", Degrees(39.8), Degrees(-105.0), 1700.0),
+        )
+        .await
+        .unwrap();
+    assert_eq!(moved.altitude, 1700.0);
+    assert!(moved.updated_at >= created.updated_at);
+}
+
+#[tokio::test]
+async fn antennas_stream_across_pages() {
+    let server = MockServer::start().await;
+    for i in 0..5 {
+        server.insert_antenna(&fixtures::antenna(&format!("antenna-{}", i)));
+    }
+    let client = server.client();
+
+    let antennas: Vec<_> = client.stream_antennas(2).try_collect().await.unwrap();
+    assert_eq!(antennas.len(), 5);
+    // pages of 2, 2 and 1
+    assert_eq!(server.requests().len(), 3);
+
+    let created = client
+        .create_antenna(&AntennaCreateRequest::new(
+            "Dish",
+            MHz(2200.0).into(),
+            MHz(2300.0).into(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(server.antenna(&created.id).unwrap().name, "Dish");
+}
+
+#[tokio::test]
+async fn tasks_are_created_filtered_and_canceled() {
+    let server = MockServer::start().await;
+    let mut telescope = fixtures::telescope("scope-1");
+    telescope.groundstation_id = Some("gs-1".to_string());
+    server.insert_groundstation(&fixtures::groundstation("gs-1"));
+    server.insert_telescope(&telescope);
+    let mut later = fixtures::task("task-1", "scope-1", TaskStatus::Scheduled);
+    later.task_start += Duration::days(1);
+    later.priority = 5;
+    server.insert_task(&later);
+    server.insert_task(&fixtures::task("task-2", "scope-1", TaskStatus::Succeeded));
+    let mut shared = fixtures::task("task-3", "scope-2", TaskStatus::Scheduled);
+    shared.user_id = Some("someone-else".to_string());
+    server.insert_task(&shared);
+    let client = server.client();
+
+    let start = fixtures::epoch();
+    let created = client
+        .create_task(&CreateTaskRequest::for_telescope(
+            "scope-1",
+            "25544",
+            start,
+            start + Duration::hours(1),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(created.status, TaskStatus::Pending);
+    assert_eq!(created.ground_station_id.as_deref(), Some("gs-1"));
+    assert_eq!(created.telescope_name.as_deref(), Some("Telescope scope-1"));
+
+    let open = client
+        .query_tasks_for_telescope(
+            "scope-1",
+            &TaskQuery::new()
+                .status(TaskStatus::Pending)
+                .status(TaskStatus::Scheduled)
+                .sort_by(TaskSortField::TaskStart, SortOrder::Descending),
+        )
+        .await
+        .unwrap();
+    let ids: Vec<&str> = open.iter().map(|task| task.id.as_str()).collect();
+    assert_eq!(ids, ["task-1", created.id.as_str()]);
+
+    let urgent = client
+        .list_tasks(&TaskQuery::new().min_priority(1))
+        .await
+        .unwrap();
+    assert_eq!(urgent.len(), 1);
+
+    let rescheduled = client
+        .update_task(&TaskUpdateRequest::new("task-1", TaskStatus::Scheduled).priority(7))
+        .await
+        .unwrap();
+    assert_eq!(rescheduled.priority, 7);
+
+    let canceled = client.cancel_task(&created.id).await.unwrap();
+    assert_eq!(canceled.status, TaskStatus::Canceled);
+    assert_eq!(client.list_tasks(&TaskQuery::new()).await.unwrap().len(), 4);
+    let mine: Vec<String> = client
+        .list_my_tasks()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|task| task.id)
+        .collect();
+    assert!(!mine.contains(&"task-3".to_string()));
+    assert_eq!(mine.len(), 3);
+
+    client.delete_task("task-2").await.unwrap();
+    assert!(server.task("task-2").is_none());
+    assert!(matches!(
+        client
+            .create_task(&CreateTaskRequest::for_antenna(
+                "missing",
+                "25544",
+                start,
+                start + Duration::hours(1),
+            ))
+            .await,
+        Err(LemonaidError::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn access_and_fov_searches_use_seeded_results() {
+    let server = MockServer::start().await;
+    let groundstation = fixtures::groundstation("gs-1");
+    server.insert_groundstation(&groundstation);
+    let start = fixtures::epoch();
+    server.insert_access_window(&fixtures::access_window(
+        "gs-1",
+        "25544",
+        start + Duration::hours(2),
+    ));
+    server.insert_access_window(&fixtures::access_window(
+        "gs-1",
+        "43013",
+        start + Duration::days(3),
+    ));
+    server.insert_fov_result(&fixtures::fov_result("25544", Degrees(10.0), Degrees(20.2)));
+    server.insert_fov_result(&fixtures::fov_result("43013", Degrees(80.0), Degrees(-5.0)));
+    let client = server.client();
+
+    let windows = client
+        .solve_access_for_groundstation(&SatelliteAccessToGroundstationRequest::new(
+            "gs-1",
+            start,
+            start + Duration::days(1),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(windows.len(), 1);
+    assert_eq!(windows[0].satellite_id, "25544");
+
+    let in_view = client
+        .solve_fov_access(&FOVAccessRequest::new(
+            &groundstation,
+            start,
+            Degrees(10.0),
+            Degrees(20.0),
+            Degrees(1.0),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(in_view.len(), 1);
+    assert_eq!(in_view[0].satellite_id, "25544");
+}
+
+#[tokio::test]
+async fn rf_captures_are_listed_as_summaries() {
+    let server = MockServer::start().await;
+    server.insert_antenna(&fixtures::antenna("antenna-1"));
+    server.insert_task(&fixtures::task("task-1", "scope-1", TaskStatus::Succeeded));
+    let client = server.client();
+
+    let start = fixtures::epoch();
+    let created = client
+        .create_rf_capture(
+            &CreateRFCaptureRequest::new(
+                "antenna-1",
+                start,
+                start + Duration::minutes(5),
+                fixtures::rf_capture_data(),
+            )
+            .task("task-1"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(created.detection_count, 1);
+
+    let fetched = client.get_rf_capture(&created.id).await.unwrap();
+    assert_eq!(fetched.data.detections[0].center_frequency_hz, 437_000_000);
+    assert_eq!(
+        client
+            .list_rf_captures_for_antenna("antenna-1")
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    let for_task = client.list_rf_captures_for_task("task-1").await.unwrap();
+    assert_eq!(for_task[0].id, created.id);
+}
+
+#[tokio::test]
+async fn satellites_are_searched_listed_and_paged() {
+    let server = MockServer::start().await;
+    let mut iss = fixtures::satellite("25544");
+    iss.name = "ISS (ZARYA)".to_string();
+    let mut fox = fixtures::satellite("43017");
+    fox.name = "AO-91 (FOX-1B)".to_string();
+    let mut weather = fixtures::satellite("40069");
+    weather.frequencies[0].min_frequency_mhz = MHz(137.0);
+    weather.frequencies[0].max_frequency_mhz = MHz(138.0);
+    for satellite in [&iss, &fox, &weather] {
+        server.insert_satellite(satellite);
+    }
+    let client = server.client();
+    let ids = |satellites: Vec<lemonaid::Satellite>| -> Vec<String> {
+        satellites
+            .into_iter()
+            .map(|satellite| satellite.id)
+            .collect()
+    };
+
+    assert_eq!(client.get_satellite("25544").await.unwrap().name, iss.name);
+    assert!(matches!(
+        client.get_satellite("99999").await,
+        Err(LemonaidError::NotFound { .. })
+    ));
+
+    let search = |query: SatelliteQuery| {
+        let client = &client;
+        async move { ids(client.search_satellites(&query).await.unwrap()) }
+    };
+    assert_eq!(search(SatelliteQuery::new().name("zarya")).await, ["25544"]);
+    assert_eq!(
+        search(SatelliteQuery::new().norad_id(43017)).await,
+        ["43017"]
+    );
+    assert_eq!(
+        search(SatelliteQuery::new().frequency_band(MHz(436.0), MHz(440.0))).await,
+        ["25544", "43017"]
+    );
+    assert_eq!(
+        search(
+            SatelliteQuery::new()
+                .frequency_band(MHz(435.0), MHz(438.0))
+                .limit(1)
+        )
+        .await,
+        ["25544"]
+    );
+
+    assert_eq!(
+        ids(client.list_satellites().await.unwrap()),
+        ["25544", "43017", "40069"]
+    );
+    let second = client
+        .list_satellites_page(PageRequest::new(2).offset(2))
+        .await
+        .unwrap();
+    assert_eq!(ids(second.items), ["40069"]);
+    let requests = server.requests().len();
+    let streamed: Vec<_> = client.stream_satellites(2).try_collect().await.unwrap();
+    assert_eq!(ids(streamed), ["25544", "43017", "40069"]);
+    // pages of 2 and 1
+    assert_eq!(server.requests().len(), requests + 2);
+}
+
+#[tokio::test]
+async fn elsets_are_read_within_epoch_bounds() {
+    let server = MockServer::start().await;
+    server.insert_satellite(&fixtures::satellite("25544"));
+    let day = |days| fixtures::epoch() + Duration::days(days);
+    for (id, days) in [("elset-1", 0), ("elset-3", 2), ("elset-2", 1)] {
+        server.insert_elset(&fixtures::elset(id, day(days)));
+    }
+    let mut other = fixtures::elset("elset-other", day(3));
+    other.satellite_id = "43017".to_string();
+    server.insert_elset(&other);
+    let client = server.client();
+
+    let latest = client.get_latest_elset("25544").await.unwrap();
+    assert_eq!(latest.id, "elset-3");
+    assert!(matches!(
+        client.get_latest_elset("99999").await,
+        Err(LemonaidError::NotFound { .. })
+    ));
+
+    let between = |start, end| {
+        let client = &client;
+        async move {
+            let elsets = client
+                .list_elsets_for_satellite("25544", start, end)
+                .await
+                .unwrap();
+            elsets.into_iter().map(|elset| elset.id).collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(between(None, None).await, ["elset-1", "elset-2", "elset-3"]);
+    assert_eq!(between(Some(day(1)), None).await, ["elset-2", "elset-3"]);
+    assert_eq!(between(None, Some(day(1))).await, ["elset-1", "elset-2"]);
+    assert_eq!(between(Some(day(1)), Some(day(1))).await, ["elset-2"]);
+    let query = server.requests().pop().unwrap().query;
+    assert_eq!(
+        query,
+        [
+            ("start".to_string(), "2025-01-02T00:00:00Z".to_string()),
+            ("end".to_string(), "2025-01-02T00:00:00Z".to_string()),
+        ]
+    );
+
+    assert!(client.propagator_for_satellite("25544").await.is_ok());
+}
+
+#[tokio::test]
+async fn elsets_are_validated_before_upload() {
+    let server = MockServer::start().await;
+    let tle = Tle::new(
+        "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+        "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+    )
+    .unwrap();
+    let elsets = [
+        ElementSetUploadRequest::from_tle("", tle.clone()),
+        ElementSetUploadRequest::from_tle("25544", tle),
+    ];
+
+    match server.client().upload_elsets(&elsets).await {
+        Err(LemonaidError::InvalidRequest { errors }) => {
+            let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+            assert_eq!(fields, ["0.satelliteId"]);
+        }
+        other => panic!("expected an invalid request, got {:?}", other),
+    }
+    assert!(server.requests().is_empty());
+}